@@ -0,0 +1,455 @@
+//! Headless command-line interface (`devport`).
+//!
+//! Drives the same storage and services as the desktop app, so projects,
+//! ports, databases and env profiles can be managed from a terminal or a CI
+//! script without opening the GUI. Every command accepts `--json` for
+//! machine-readable output.
+
+use crate::models::{PortInfo, Project};
+use crate::services::credential_manager::{CredentialManager, MARIADB_ROOT_KEY, POSTGRES_SUPERUSER_KEY};
+use crate::services::env_manager::{EnvManager, EnvProfileType};
+use crate::services::port_scanner::PortScanner;
+use crate::services::process_manager::ProcessManager;
+use crate::services::storage::Storage;
+use crate::services::database_manager::{DatabaseCredentials, DatabaseEngine};
+use crate::services::DatabaseManager;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How often a foreground project is checked for exit
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Parser)]
+#[command(name = "devport", version, about = "ClickDevPort command-line interface")]
+struct Cli {
+    /// Print machine-readable JSON instead of tables
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// List registered projects
+    List,
+    /// Start a project in the foreground; Ctrl-C stops it
+    Start {
+        /// Project ID or name
+        project: String,
+    },
+    /// List ports that are currently in use
+    Ports,
+    /// Database operations
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+    /// .env profile operations
+    Env {
+        #[command(subcommand)]
+        command: EnvCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommand {
+    /// List databases
+    List {
+        #[command(flatten)]
+        auth: DbAuth,
+    },
+    /// Dump a database to the backup directory
+    Dump {
+        /// Database name
+        name: String,
+        /// Project name used to group the backup (defaults to the database name)
+        #[arg(long)]
+        project: Option<String>,
+        #[command(flatten)]
+        auth: DbAuth,
+    },
+}
+
+#[derive(Args)]
+struct DbAuth {
+    /// Database server engine
+    #[arg(long, env = "DEVPORT_DB_ENGINE", value_enum, default_value_t = DbEngine::Mysql)]
+    engine: DbEngine,
+    /// Database server host
+    #[arg(long, env = "DEVPORT_DB_HOST", default_value = "localhost")]
+    host: String,
+    /// Database server port (defaults to the engine's: 3306 or 5432)
+    #[arg(long, env = "DEVPORT_DB_PORT")]
+    port: Option<u16>,
+    /// Database user (defaults to root, or postgres)
+    #[arg(long)]
+    user: Option<String>,
+    /// Database password (falls back to the stored root credential for MySQL)
+    #[arg(long, env = "DEVPORT_DB_PASSWORD", hide_env_values = true)]
+    password: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DbEngine {
+    Mysql,
+    Postgres,
+}
+
+impl From<DbEngine> for DatabaseEngine {
+    fn from(engine: DbEngine) -> Self {
+        match engine {
+            DbEngine::Mysql => DatabaseEngine::Mysql,
+            DbEngine::Postgres => DatabaseEngine::Postgres,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum EnvCommand {
+    /// List the env profiles of a project
+    List {
+        /// Project ID or name (defaults to the current directory)
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Make a profile the active one
+    Switch {
+        /// Profile name (e.g. production) or file name (e.g. .env.production)
+        profile: String,
+        /// Project ID or name (defaults to the current directory)
+        #[arg(long)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectSummary {
+    id: String,
+    name: String,
+    project_type: String,
+    port: u16,
+    path: String,
+    listening: bool,
+}
+
+/// Parse the command line, run the command and return the process exit code
+pub fn run() -> i32 {
+    let cli = Cli::parse();
+    let json = cli.json;
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => return report_error(&format!("Failed to start runtime: {}", e), json),
+    };
+
+    match runtime.block_on(dispatch(cli)) {
+        Ok(code) => code,
+        Err(e) => report_error(&e, json),
+    }
+}
+
+fn report_error(message: &str, json: bool) -> i32 {
+    if json {
+        println!("{}", serde_json::json!({ "error": message }));
+    } else {
+        eprintln!("error: {}", message);
+    }
+    1
+}
+
+async fn dispatch(cli: Cli) -> Result<i32, String> {
+    let json = cli.json;
+    match cli.command {
+        CliCommand::List => list_projects(json).map(|_| 0),
+        CliCommand::Start { project } => start_project(&project, json).await,
+        CliCommand::Ports => list_ports(json).await.map(|_| 0),
+        CliCommand::Db { command } => match command {
+            DbCommand::List { auth } => list_databases(&auth, json).await.map(|_| 0),
+            DbCommand::Dump { name, project, auth } => {
+                dump_database(&name, project.as_deref(), &auth, json).await.map(|_| 0)
+            }
+        },
+        CliCommand::Env { command } => match command {
+            EnvCommand::List { project } => list_profiles(project.as_deref(), json).map(|_| 0),
+            EnvCommand::Switch { profile, project } => {
+                switch_profile(&profile, project.as_deref(), json).map(|_| 0)
+            }
+        },
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let output = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", output);
+    Ok(())
+}
+
+fn load_projects() -> Result<Vec<Project>, String> {
+    Storage::new()
+        .and_then(|s| s.load_projects())
+        .map_err(|e| e.to_string())
+}
+
+/// Look a project up by ID first, then by (case-insensitive) name
+fn find_project(query: &str) -> Result<Project, String> {
+    let projects = load_projects()?;
+
+    if let Some(project) = projects.iter().find(|p| p.id == query) {
+        return Ok(project.clone());
+    }
+
+    let mut matches: Vec<Project> = projects
+        .into_iter()
+        .filter(|p| p.name.eq_ignore_ascii_case(query))
+        .collect();
+    match matches.len() {
+        0 => Err(format!("Project not found: {}", query)),
+        1 => Ok(matches.remove(0)),
+        _ => Err(format!("Project name '{}' is ambiguous; use the project ID", query)),
+    }
+}
+
+fn list_projects(json: bool) -> Result<(), String> {
+    let summaries: Vec<ProjectSummary> = load_projects()?
+        .into_iter()
+        .map(|p| ProjectSummary {
+            listening: p.port > 0 && !PortScanner::is_port_available(p.port),
+            project_type: serde_json::to_value(&p.project_type)
+                .ok()
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or_default(),
+            id: p.id,
+            name: p.name,
+            port: p.port,
+            path: p.path,
+        })
+        .collect();
+
+    if json {
+        return print_json(&summaries);
+    }
+
+    println!("{:<36}  {:<24}  {:<10}  {:>5}  {:<9}  PATH", "ID", "NAME", "TYPE", "PORT", "STATUS");
+    for p in &summaries {
+        println!(
+            "{:<36}  {:<24}  {:<10}  {:>5}  {:<9}  {}",
+            p.id,
+            p.name,
+            p.project_type,
+            p.port,
+            if p.listening { "listening" } else { "-" },
+            p.path
+        );
+    }
+    Ok(())
+}
+
+/// Run a project attached to this terminal until it exits or Ctrl-C is pressed.
+/// Returns the project's exit code.
+async fn start_project(query: &str, json: bool) -> Result<i32, String> {
+    let project = find_project(query)?;
+    let mut manager = ProcessManager::new();
+
+    let info = manager
+        .start_project(&project, None)
+        .map_err(|e| e.to_string())?;
+    if json {
+        println!("{}", serde_json::to_string(&info).map_err(|e| e.to_string())?);
+    } else {
+        eprintln!("Started {} (pid {}) on port {}", project.name, info.pid, info.port);
+    }
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let mut interval = tokio::time::interval(EXIT_POLL_INTERVAL);
+
+    let exit_code = loop {
+        tokio::select! {
+            _ = &mut ctrl_c => {
+                let outcome = manager
+                    .stop_project(&project.id, None)
+                    .map_err(|e| e.to_string())?;
+                if !json {
+                    eprintln!("Stopped {} ({:?})", project.name, outcome.stopped_by);
+                }
+                break outcome.exit_code;
+            }
+            _ = interval.tick() => {
+                if let Some(exit) = manager.reap_exited().into_iter().next() {
+                    if !json {
+                        eprintln!(
+                            "{} exited with code {}",
+                            project.name,
+                            exit.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "unknown".to_string())
+                        );
+                    }
+                    break exit.exit_code;
+                }
+            }
+        }
+    };
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "projectId": project.id,
+                "pid": info.pid,
+                "exitCode": exit_code
+            })
+        );
+    }
+
+    Ok(exit_code.unwrap_or(0))
+}
+
+async fn list_ports(json: bool) -> Result<(), String> {
+    let mut ports: Vec<PortInfo> = tokio::task::spawn_blocking(PortScanner::scan_ports)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    // Attribute ports to registered projects
+    let projects = load_projects().unwrap_or_default();
+    for port in ports.iter_mut().filter(|p| p.project_id.is_none()) {
+        port.project_id = projects
+            .iter()
+            .find(|p| p.port == port.port)
+            .map(|p| p.id.clone());
+    }
+    ports.sort_by_key(|p| p.port);
+
+    if json {
+        return print_json(&ports);
+    }
+
+    println!("{:>5}  {:<5}  {:<12}  {:>7}  {:<20}  PROJECT", "PORT", "PROTO", "STATE", "PID", "PROCESS");
+    for port in &ports {
+        let project_name = port
+            .project_id
+            .as_ref()
+            .and_then(|id| projects.iter().find(|p| &p.id == id))
+            .map(|p| p.name.as_str())
+            .unwrap_or("-");
+        println!(
+            "{:>5}  {:<5}  {:<12}  {:>7}  {:<20}  {}",
+            port.port,
+            port.protocol,
+            port.state,
+            port.pid.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()),
+            port.process_name.as_deref().unwrap_or("-"),
+            project_name
+        );
+    }
+    Ok(())
+}
+
+fn database_manager(auth: &DbAuth) -> Result<DatabaseManager, String> {
+    let engine = DatabaseEngine::from(auth.engine);
+    let defaults = engine.default_admin();
+    let password = match (&auth.password, engine) {
+        (Some(password), _) => password.clone(),
+        (None, DatabaseEngine::Mysql) => CredentialManager::shared()
+            .and_then(|m| m.load_credential(MARIADB_ROOT_KEY))?
+            .unwrap_or_default(),
+        (None, DatabaseEngine::Postgres) => CredentialManager::shared()
+            .and_then(|m| m.load_credential(POSTGRES_SUPERUSER_KEY))?
+            .unwrap_or_default(),
+    };
+
+    let mut manager = DatabaseManager::new();
+    manager.set_root_connection(
+        engine,
+        DatabaseCredentials {
+            host: auth.host.clone(),
+            port: auth.port.unwrap_or(defaults.port),
+            username: auth.user.clone().unwrap_or(defaults.username),
+            password,
+            database: defaults.database,
+        },
+    );
+    Ok(manager)
+}
+
+async fn list_databases(auth: &DbAuth, json: bool) -> Result<(), String> {
+    let databases = database_manager(auth)?
+        .list_databases(auth.engine.into())
+        .await?;
+
+    if json {
+        return print_json(&databases);
+    }
+    for database in &databases {
+        println!("{}", database);
+    }
+    Ok(())
+}
+
+async fn dump_database(name: &str, project: Option<&str>, auth: &DbAuth, json: bool) -> Result<(), String> {
+    let backup = database_manager(auth)?
+        .dump_database(auth.engine.into(), name, project.unwrap_or(name))
+        .await?;
+
+    if json {
+        return print_json(&backup);
+    }
+    println!("Dumped {} to {} ({} bytes)", backup.database, backup.file_path, backup.size);
+    Ok(())
+}
+
+/// Resolve the env manager for a registered project, or the current directory
+fn env_manager(project: Option<&str>) -> Result<EnvManager, String> {
+    let path = match project {
+        Some(query) => PathBuf::from(find_project(query)?.path),
+        None => std::env::current_dir().map_err(|e| e.to_string())?,
+    };
+    Ok(EnvManager::new(path))
+}
+
+fn list_profiles(project: Option<&str>, json: bool) -> Result<(), String> {
+    let profiles = env_manager(project)?.list_profiles()?;
+
+    if json {
+        return print_json(&profiles);
+    }
+
+    println!("  {:<24}  {:<20}  VARIABLES", "FILE", "NAME");
+    for profile in &profiles {
+        println!(
+            "{} {:<24}  {:<20}  {}",
+            if profile.is_active { "*" } else { " " },
+            profile.file_name,
+            profile.name,
+            profile.variable_count
+        );
+    }
+    Ok(())
+}
+
+fn switch_profile(profile: &str, project: Option<&str>, json: bool) -> Result<(), String> {
+    let manager = env_manager(project)?;
+    let profiles = manager.list_profiles()?;
+
+    // Accept a file name, a suffix ("production") or an alias ("prod")
+    let wanted = EnvProfileType::from_file_name(&format!(".env.{}", profile));
+    let file_name = profiles
+        .iter()
+        .find(|p| p.file_name == profile)
+        .or_else(|| profiles.iter().find(|p| p.file_name == format!(".env.{}", profile)))
+        .or_else(|| profiles.iter().find(|p| p.file_name != ".env" && p.profile_type == wanted))
+        .map(|p| p.file_name.clone())
+        .ok_or_else(|| format!("Profile not found: {}", profile))?;
+
+    manager.switch_profile(&file_name)?;
+
+    if json {
+        return print_json(&serde_json::json!({ "activeProfile": file_name }));
+    }
+    println!("Active profile: {}", file_name);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use crate::control_api::{read_or_create_token, rotate_token, SharedControlApi};
+use crate::models::ControlApiConfig;
+use crate::services::storage::Storage;
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlApiStatus {
+    pub config: ControlApiConfig,
+    pub running: bool,
+    pub address: Option<String>,
+    pub token_path: String,
+}
+
+async fn status(control_api: &SharedControlApi) -> Result<ControlApiStatus, String> {
+    let storage = Storage::new().map_err(|e| e.to_string())?;
+    let config = storage.load_control_api_config().map_err(|e| e.to_string())?;
+    let address = control_api.lock().await.address().map(String::from);
+
+    Ok(ControlApiStatus {
+        config,
+        running: address.is_some(),
+        address,
+        token_path: storage.control_api_token_file().to_string_lossy().to_string(),
+    })
+}
+
+#[tauri::command]
+pub async fn get_control_api_status(
+    control_api: State<'_, SharedControlApi>,
+) -> Result<ControlApiStatus, String> {
+    status(&control_api).await
+}
+
+/// Save the control API settings and start, restart or stop the server to match
+#[tauri::command]
+pub async fn set_control_api_config(
+    config: ControlApiConfig,
+    control_api: State<'_, SharedControlApi>,
+    app_handle: AppHandle,
+) -> Result<ControlApiStatus, String> {
+    Storage::new()
+        .and_then(|s| s.save_control_api_config(&config))
+        .map_err(|e| e.to_string())?;
+
+    {
+        let mut server = control_api.lock().await;
+        if config.enabled {
+            server.start(app_handle, &config).await?;
+        } else {
+            server.stop();
+        }
+    }
+
+    status(&control_api).await
+}
+
+#[tauri::command]
+pub fn get_control_api_token() -> Result<String, String> {
+    read_or_create_token()
+}
+
+/// Issue a new token; a running server is restarted so the old token stops working
+#[tauri::command]
+pub async fn rotate_control_api_token(
+    control_api: State<'_, SharedControlApi>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let token = rotate_token()?;
+
+    let mut server = control_api.lock().await;
+    if server.address().is_some() {
+        let config = Storage::new()
+            .and_then(|s| s.load_control_api_config())
+            .map_err(|e| e.to_string())?;
+        server.start(app_handle, &config).await?;
+    }
+
+    Ok(token)
+}
@@ -1,20 +1,34 @@
 //! Tauri commands for credential management
 //!
 //! These commands provide a secure way to store and retrieve database credentials
-//! using Windows DPAPI encryption.
+//! using Windows DPAPI, the Secret Service on Linux, or an encrypted file.
 
-use crate::services::credential_manager::CredentialManager;
+use crate::services::credential_manager::{CredentialManager, SharedCredentialManager};
+use tauri::State;
 
-/// Saves a database credential securely using DPAPI encryption.
+/// Run a credential operation off the main thread; keyring calls block on D-Bus
+async fn with_credentials<T: Send + 'static>(
+    manager: &SharedCredentialManager,
+    operation: impl FnOnce(&CredentialManager) -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    let manager = manager.clone();
+    tokio::task::spawn_blocking(move || operation(&manager))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Saves a database credential securely (DPAPI, Secret Service or encrypted file).
 ///
 /// # Arguments
 /// * `key` - A unique identifier for the credential (e.g., "mariadb_root", "project_myapp_db")
 /// * `password` - The password to encrypt and store
 #[tauri::command]
-pub fn save_db_credential(key: String, password: String) -> Result<(), String> {
-    let manager = CredentialManager::new()?;
-    manager.save_credential(&key, &password)?;
-    Ok(())
+pub async fn save_db_credential(
+    manager: State<'_, SharedCredentialManager>,
+    key: String,
+    password: String,
+) -> Result<(), String> {
+    with_credentials(&manager, move |m| m.save_credential(&key, &password)).await
 }
 
 /// Retrieves a database credential.
@@ -27,9 +41,11 @@ pub fn save_db_credential(key: String, password: String) -> Result<(), String> {
 /// * `Ok(None)` - If the credential does not exist
 /// * `Err(...)` - If decryption fails
 #[tauri::command]
-pub fn get_db_credential(key: String) -> Result<Option<String>, String> {
-    let manager = CredentialManager::new()?;
-    manager.load_credential(&key)
+pub async fn get_db_credential(
+    manager: State<'_, SharedCredentialManager>,
+    key: String,
+) -> Result<Option<String>, String> {
+    with_credentials(&manager, move |m| m.load_credential(&key)).await
 }
 
 /// Deletes a database credential.
@@ -37,9 +53,11 @@ pub fn get_db_credential(key: String) -> Result<Option<String>, String> {
 /// # Arguments
 /// * `key` - The unique identifier for the credential to delete
 #[tauri::command]
-pub fn delete_db_credential(key: String) -> Result<(), String> {
-    let manager = CredentialManager::new()?;
-    manager.delete_credential(&key)
+pub async fn delete_db_credential(
+    manager: State<'_, SharedCredentialManager>,
+    key: String,
+) -> Result<(), String> {
+    with_credentials(&manager, move |m| m.delete_credential(&key)).await
 }
 
 /// Checks if a database credential exists.
@@ -50,9 +68,11 @@ pub fn delete_db_credential(key: String) -> Result<(), String> {
 /// # Returns
 /// * `true` if the credential exists, `false` otherwise
 #[tauri::command]
-pub fn has_db_credential(key: String) -> Result<bool, String> {
-    let manager = CredentialManager::new()?;
-    Ok(manager.has_credential(&key))
+pub async fn has_db_credential(
+    manager: State<'_, SharedCredentialManager>,
+    key: String,
+) -> Result<bool, String> {
+    with_credentials(&manager, move |m| Ok(m.has_credential(&key))).await
 }
 
 /// Lists all stored credential keys.
@@ -60,7 +80,8 @@ pub fn has_db_credential(key: String) -> Result<bool, String> {
 /// # Returns
 /// * A list of all stored credential keys
 #[tauri::command]
-pub fn list_db_credentials() -> Result<Vec<String>, String> {
-    let manager = CredentialManager::new()?;
-    manager.list_credentials()
+pub async fn list_db_credentials(
+    manager: State<'_, SharedCredentialManager>,
+) -> Result<Vec<String>, String> {
+    with_credentials(&manager, |m| m.list_credentials()).await
 }
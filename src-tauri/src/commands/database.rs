@@ -1,25 +1,68 @@
-use crate::services::database_manager::{BackupInfo, DatabaseCredentials, DatabaseManager};
+use crate::error::{CommandError, CommandResult, ErrorCode};
+use crate::services::database_manager::{
+    BackupInfo, DatabaseCredentials, DatabaseEngine, DatabaseError, DatabaseManager,
+};
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::Mutex;
 
+pub(crate) fn database_error(err: DatabaseError) -> CommandError {
+    let message = err.to_string();
+    match &err {
+        DatabaseError::NotConfigured => CommandError::new(ErrorCode::DatabaseConnectionFailed, message),
+        DatabaseError::Connection { code, .. } => {
+            CommandError::new(ErrorCode::DatabaseConnectionFailed, message)
+                .with_data(serde_json::json!({ "code": code }))
+                .retryable()
+        }
+        DatabaseError::Query { code, state, .. } => {
+            let error_code = if err.is_unknown_database() {
+                ErrorCode::DatabaseNotFound
+            } else {
+                ErrorCode::DatabaseQueryFailed
+            };
+            CommandError::new(error_code, message)
+                .with_data(serde_json::json!({ "code": code, "sqlState": state }))
+        }
+        DatabaseError::Other(_) => CommandError::new(ErrorCode::Unknown, message),
+    }
+}
+
+/// Set the admin login used for administrative statements of an engine (MySQL unless
+/// given). Defaults to the engine's port on localhost; a container or test instance
+/// can be targeted with `host` and `port`.
 #[tauri::command]
 pub async fn set_database_credentials(
     db_manager: State<'_, Arc<Mutex<DatabaseManager>>>,
     username: String,
     password: String,
-) -> Result<(), String> {
+    host: Option<String>,
+    port: Option<u16>,
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<()> {
+    let engine = engine.unwrap_or_default();
+    let defaults = engine.default_admin();
     let mut manager = db_manager.lock().await;
-    manager.set_root_credentials(username, password);
+    manager.set_root_connection(
+        engine,
+        DatabaseCredentials {
+            host: host.unwrap_or(defaults.host),
+            port: port.unwrap_or(defaults.port),
+            username,
+            password,
+            database: defaults.database,
+        },
+    );
     Ok(())
 }
 
 #[tauri::command]
 pub async fn test_database_connection(
     db_manager: State<'_, Arc<Mutex<DatabaseManager>>>,
-) -> Result<bool, String> {
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<bool> {
     let manager = db_manager.lock().await;
-    manager.test_connection()
+    manager.test_connection(engine.unwrap_or_default()).await.map_err(database_error)
 }
 
 #[tauri::command]
@@ -28,10 +71,14 @@ pub async fn create_project_database(
     db_name: String,
     username: String,
     password: Option<String>,
-) -> Result<DatabaseCredentials, String> {
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<DatabaseCredentials> {
     let manager = db_manager.lock().await;
     let password = password.unwrap_or_else(|| DatabaseManager::generate_password(16));
-    manager.create_database_with_user(&db_name, &username, &password)
+    manager
+        .create_database_with_user(engine.unwrap_or_default(), &db_name, &username, &password)
+        .await
+        .map_err(database_error)
 }
 
 #[tauri::command]
@@ -39,19 +86,22 @@ pub async fn drop_project_database(
     db_manager: State<'_, Arc<Mutex<DatabaseManager>>>,
     db_name: String,
     username: String,
-) -> Result<(), String> {
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<()> {
+    let engine = engine.unwrap_or_default();
     let manager = db_manager.lock().await;
-    manager.drop_database(&db_name)?;
-    manager.drop_user(&username)?;
+    manager.drop_database(engine, &db_name).await.map_err(database_error)?;
+    manager.drop_user(engine, &username).await.map_err(database_error)?;
     Ok(())
 }
 
 #[tauri::command]
 pub async fn list_databases(
     db_manager: State<'_, Arc<Mutex<DatabaseManager>>>,
-) -> Result<Vec<String>, String> {
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<Vec<String>> {
     let manager = db_manager.lock().await;
-    manager.list_databases()
+    manager.list_databases(engine.unwrap_or_default()).await.map_err(database_error)
 }
 
 #[tauri::command]
@@ -59,9 +109,13 @@ pub async fn dump_database(
     db_manager: State<'_, Arc<Mutex<DatabaseManager>>>,
     db_name: String,
     project_name: String,
-) -> Result<BackupInfo, String> {
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<BackupInfo> {
     let manager = db_manager.lock().await;
-    manager.dump_database(&db_name, &project_name)
+    manager
+        .dump_database(engine.unwrap_or_default(), &db_name, &project_name)
+        .await
+        .map_err(database_error)
 }
 
 #[tauri::command]
@@ -69,9 +123,13 @@ pub async fn restore_database(
     db_manager: State<'_, Arc<Mutex<DatabaseManager>>>,
     db_name: String,
     backup_path: String,
-) -> Result<(), String> {
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<()> {
     let manager = db_manager.lock().await;
-    manager.restore_database(&db_name, &backup_path)
+    manager
+        .restore_database(engine.unwrap_or_default(), &db_name, &backup_path)
+        .await
+        .map_err(database_error)
 }
 
 #[tauri::command]
@@ -88,10 +146,14 @@ pub async fn reset_database_password(
     db_manager: State<'_, Arc<Mutex<DatabaseManager>>>,
     username: String,
     new_password: Option<String>,
-) -> Result<String, String> {
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<String> {
     let manager = db_manager.lock().await;
     let password = new_password.unwrap_or_else(|| DatabaseManager::generate_password(16));
-    manager.reset_password(&username, &password)?;
+    manager
+        .reset_password(engine.unwrap_or_default(), &username, &password)
+        .await
+        .map_err(database_error)?;
     Ok(password)
 }
 
@@ -103,7 +165,8 @@ pub async fn test_database_credentials(
     username: String,
     password: String,
     database: String,
-) -> Result<bool, String> {
+    engine: Option<DatabaseEngine>,
+) -> CommandResult<bool> {
     let manager = db_manager.lock().await;
     let creds = DatabaseCredentials {
         host,
@@ -112,7 +175,9 @@ pub async fn test_database_credentials(
         password,
         database,
     };
-    manager.test_credentials(&creds)
+    manager
+        .test_credentials(engine.unwrap_or_default(), &creds)
+        .await.map_err(database_error)
 }
 
 #[tauri::command]
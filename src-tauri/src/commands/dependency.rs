@@ -0,0 +1,279 @@
+use crate::commands::process::{halt_project, launch_project};
+use crate::error::{CommandError, CommandResult, ErrorCode};
+use crate::models::ServiceStatus;
+use crate::services::dependency_graph::{
+    DependencyError, DependencyGraph, DependencyKind, DependencyNode,
+};
+use crate::services::health_checker::HealthChecker;
+use crate::services::storage::Storage;
+use crate::services::{ServiceManager, SharedProcessManager};
+use crate::state::AppState;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+
+/// How long an ordered start waits for a dependency to become healthy
+const DEPENDENCY_READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Interval between readiness checks while waiting for a dependency
+const DEPENDENCY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(crate) fn dependency_error(err: DependencyError) -> CommandError {
+    let message = err.to_string();
+    match err {
+        DependencyError::Cycle(cycle) => CommandError::new(ErrorCode::DependencyCycle, message)
+            .with_data(serde_json::json!({ "cycle": cycle })),
+        DependencyError::UnknownDependency(node, dependency) => {
+            CommandError::new(ErrorCode::DependencyNotFound, message)
+                .with_data(serde_json::json!({ "node": node, "dependency": dependency }))
+        }
+        DependencyError::NotFound(_) => CommandError::new(ErrorCode::NotFound, message),
+    }
+}
+
+/// Build the dependency graph from stored projects and the known services
+pub(crate) async fn build_dependency_graph(
+    service_manager: &Arc<Mutex<ServiceManager>>,
+) -> CommandResult<DependencyGraph> {
+    let projects = Storage::new()
+        .and_then(|s| s.load_projects())
+        .map_err(CommandError::from_error)?;
+    let manager = service_manager.lock().await;
+    Ok(DependencyGraph::new(&projects, manager.services.values()))
+}
+
+fn emit_progress(app_handle: &AppHandle, node: &DependencyNode, status: &str, message: Option<String>) {
+    let _ = app_handle.emit(
+        "dependency-progress",
+        serde_json::json!({
+            "id": node.id,
+            "name": node.name,
+            "kind": node.kind,
+            "status": status,
+            "message": message
+        }),
+    );
+}
+
+/// Poll a node's health check until it passes or the timeout elapses
+async fn wait_until_ready(
+    node: &DependencyNode,
+    state: &Arc<Mutex<AppState>>,
+    service_manager: &Arc<Mutex<ServiceManager>>,
+) -> CommandResult<()> {
+    let deadline = Instant::now() + DEPENDENCY_READY_TIMEOUT;
+    let mut last_error = None;
+
+    while Instant::now() < deadline {
+        match node.kind {
+            DependencyKind::Service => {
+                // Probe a snapshot so the service manager stays unlocked during the check
+                let service = service_manager.lock().await.get_service(&node.id).cloned();
+                let service = service.ok_or_else(|| {
+                    CommandError::new(ErrorCode::NotFound, format!("Service '{}' not found", node.name))
+                })?;
+                if matches!(service.status, ServiceStatus::Error | ServiceStatus::NotInstalled) {
+                    return Err(CommandError::new(
+                        ErrorCode::DependencyFailed,
+                        format!("Service '{}' is not running ({:?})", node.name, service.status),
+                    ));
+                }
+                // A service without a process yet is still starting
+                if service.pid.is_some() {
+                    let port = Some(service.port).filter(|p| *p > 0);
+                    let health = HealthChecker::run_check(
+                        &service.id,
+                        &service.health_check,
+                        port,
+                        service.pid,
+                        Some(&service.work_dir),
+                    )
+                    .await;
+                    if health.is_healthy {
+                        return Ok(());
+                    }
+                    last_error = health.error;
+                } else {
+                    last_error = Some(format!("Service '{}' is not running yet", node.name));
+                }
+            }
+            DependencyKind::Project => {
+                let (pid, port) = match state.lock().await.running_processes.get(&node.id) {
+                    Some(info) => (info.pid, info.port),
+                    None => {
+                        return Err(CommandError::new(
+                            ErrorCode::DependencyFailed,
+                            format!("Project '{}' exited before becoming healthy", node.name),
+                        ));
+                    }
+                };
+                let mut project = Storage::new()
+                    .and_then(|s| s.get_project(&node.id))
+                    .map_err(CommandError::from_error)?;
+                // The dev server may have moved to another port
+                if port > 0 {
+                    project.port = port;
+                }
+                let health = HealthChecker::check_project(&project, Some(pid), 2).await;
+                if health.is_healthy {
+                    return Ok(());
+                }
+                last_error = health.error;
+            }
+        }
+
+        tokio::time::sleep(DEPENDENCY_POLL_INTERVAL).await;
+    }
+
+    Err(CommandError::with_details(
+        ErrorCode::Timeout,
+        format!(
+            "'{}' did not become healthy within {}s",
+            node.name,
+            DEPENDENCY_READY_TIMEOUT.as_secs()
+        ),
+        last_error.unwrap_or_default(),
+    )
+    .retryable())
+}
+
+/// Start nodes in the given order, waiting for each dependency's health check
+/// before starting the nodes that depend on it.
+pub(crate) async fn start_in_order(
+    order: &[DependencyNode],
+    state: &Arc<Mutex<AppState>>,
+    process_manager: &SharedProcessManager,
+    service_manager: &Arc<Mutex<ServiceManager>>,
+    app_handle: &AppHandle,
+) -> CommandResult<()> {
+    // Only nodes something else depends on need to be healthy before moving on
+    let required: HashSet<&str> = order
+        .iter()
+        .flat_map(|n| n.depends_on.iter().map(String::as_str))
+        .collect();
+
+    for node in order {
+        emit_progress(app_handle, node, "starting", None);
+
+        let started = match node.kind {
+            DependencyKind::Service => service_manager
+                .lock()
+                .await
+                .start_service(&node.id, Some(app_handle.clone()))
+                .await
+                .map_err(|e| CommandError::new(ErrorCode::ServiceStartFailed, e)),
+            DependencyKind::Project => {
+                launch_project(&node.id, state, process_manager, app_handle.clone())
+                    .await
+                    .map(|_| ())
+            }
+        };
+
+        let ready = match started {
+            Ok(()) if required.contains(node.id.as_str()) => {
+                wait_until_ready(node, state, service_manager).await
+            }
+            other => other,
+        };
+
+        if let Err(e) = ready {
+            emit_progress(app_handle, node, "failed", Some(e.message.clone()));
+            return Err(e);
+        }
+        emit_progress(app_handle, node, "ready", None);
+    }
+
+    Ok(())
+}
+
+/// Stop nodes in the given order. Nodes still needed by a running project
+/// outside of `order` are left alone.
+pub(crate) async fn stop_in_order(
+    order: &[DependencyNode],
+    graph: &DependencyGraph,
+    state: &Arc<Mutex<AppState>>,
+    process_manager: &SharedProcessManager,
+    service_manager: &Arc<Mutex<ServiceManager>>,
+    app_handle: &AppHandle,
+) -> CommandResult<Vec<DependencyNode>> {
+    let stopping: HashSet<&str> = order.iter().map(|n| n.id.as_str()).collect();
+    let mut stopped = Vec::new();
+
+    for node in order {
+        let running_projects: HashSet<String> =
+            state.lock().await.running_processes.keys().cloned().collect();
+        let still_needed = graph.dependents(&node.id).iter().any(|d| {
+            !stopping.contains(d.id.as_str()) && running_projects.contains(&d.id)
+        });
+        if still_needed {
+            emit_progress(app_handle, node, "skipped", Some("Still required by a running project".to_string()));
+            continue;
+        }
+
+        match node.kind {
+            DependencyKind::Service => {
+                ServiceManager::stop_service(service_manager, &node.id)
+                    .await
+                    .map_err(|e| CommandError::new(ErrorCode::ServiceStopFailed, e))?;
+            }
+            DependencyKind::Project => {
+                let was_running = running_projects.contains(&node.id);
+                if let Err(e) = halt_project(&node.id, state, process_manager, app_handle.clone()).await {
+                    if was_running {
+                        return Err(CommandError::new(ErrorCode::ProcessKillFailed, e.to_string()));
+                    }
+                    continue;
+                }
+            }
+        }
+
+        emit_progress(app_handle, node, "stopped", None);
+        stopped.push(node.clone());
+    }
+
+    Ok(stopped)
+}
+
+/// Get the dependency-ordered start sequence for the given projects or services
+#[tauri::command]
+pub async fn get_start_order(
+    ids: Vec<String>,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+) -> CommandResult<Vec<DependencyNode>> {
+    let graph = build_dependency_graph(&service_manager).await?;
+    graph.start_order(&ids).map_err(dependency_error)
+}
+
+/// Start the given projects or services together with all their dependencies
+#[tauri::command]
+pub async fn start_project_group(
+    ids: Vec<String>,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    process_manager: State<'_, SharedProcessManager>,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+    app_handle: AppHandle,
+) -> CommandResult<Vec<DependencyNode>> {
+    let graph = build_dependency_graph(&service_manager).await?;
+    let order = graph.start_order(&ids).map_err(dependency_error)?;
+
+    start_in_order(&order, &state, &process_manager, &service_manager, &app_handle).await?;
+
+    Ok(order)
+}
+
+/// Stop the given projects or services, dependents first
+#[tauri::command]
+pub async fn stop_project_group(
+    ids: Vec<String>,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    process_manager: State<'_, SharedProcessManager>,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+    app_handle: AppHandle,
+) -> CommandResult<Vec<DependencyNode>> {
+    let graph = build_dependency_graph(&service_manager).await?;
+    let order = graph.stop_order(&ids).map_err(dependency_error)?;
+
+    stop_in_order(&order, &graph, &state, &process_manager, &service_manager, &app_handle).await
+}
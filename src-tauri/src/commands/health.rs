@@ -1,6 +1,43 @@
 use crate::services::health_checker::{HealthChecker, HealthStatus};
+use crate::services::health_monitor::HealthSummary;
+use crate::services::SharedHealthMonitor;
+use tauri::State;
 
+/// Default timeout of a one-off health check
+const DEFAULT_TIMEOUT_SECS: u64 = 5;
+
+#[tauri::command]
+pub async fn check_health(
+    project_id: String,
+    url: String,
+    timeout_secs: Option<u64>,
+) -> Result<HealthStatus, String> {
+    let timeout = timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+    Ok(HealthChecker::check_health(&project_id, &url, timeout).await)
+}
+
+/// Recent health samples of a running project, oldest first
+#[tauri::command]
+pub async fn get_health_history(
+    project_id: String,
+    health_monitor: State<'_, SharedHealthMonitor>,
+) -> Result<Vec<HealthStatus>, String> {
+    Ok(health_monitor.lock().await.history(&project_id))
+}
+
+/// Uptime and latency summary of a running project, e.g. "healthy for 2h, p95 40ms"
+#[tauri::command]
+pub async fn get_health_summary(
+    project_id: String,
+    health_monitor: State<'_, SharedHealthMonitor>,
+) -> Result<Option<HealthSummary>, String> {
+    Ok(health_monitor.lock().await.summary(&project_id))
+}
+
+/// Uptime and latency summary of every monitored project
 #[tauri::command]
-pub async fn check_health(project_id: String, url: String) -> Result<HealthStatus, String> {
-    Ok(HealthChecker::check_health(&project_id, &url, 5).await)
+pub async fn get_health_summaries(
+    health_monitor: State<'_, SharedHealthMonitor>,
+) -> Result<Vec<HealthSummary>, String> {
+    Ok(health_monitor.lock().await.summaries())
 }
@@ -1,4 +1,11 @@
-use crate::services::log_manager::{LogEntry, LogManager, LogUpdatePayload, SharedLogStreamManager};
+use crate::services::log_manager::{
+    LogAlertRule, LogDiskUsage, LogEntry, LogManager, LogSearchHit, LogSearchQuery, LogSource,
+    LogUpdatePayload, SharedLogAlerts, SharedLogStreamManager,
+};
+use crate::services::log_tail::{tail_files, LogTailBatch, LogTailOptions, MergedTail};
+use crate::services::storage::Storage;
+use crate::services::ServiceManager;
+use crate::tray;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -61,6 +68,129 @@ pub async fn get_project_logs(
     })
 }
 
+/// Search project and service logs by level, time range, regex and source
+#[tauri::command]
+pub async fn search_logs(
+    log_manager: State<'_, Arc<Mutex<LogManager>>>,
+    query: LogSearchQuery,
+) -> Result<Vec<LogSearchHit>, String> {
+    let manager = log_manager.lock().await.clone();
+    tokio::task::spawn_blocking(move || manager.search_logs(&query))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Disk space used by each project's and service's logs, largest first
+#[tauri::command]
+pub async fn get_log_disk_usage(
+    log_manager: State<'_, Arc<Mutex<LogManager>>>,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+) -> Result<Vec<LogDiskUsage>, String> {
+    let manager = log_manager.lock().await.clone();
+    let native_logs = native_service_logs(&service_manager).await;
+
+    tokio::task::spawn_blocking(move || manager.disk_usage(&native_logs))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Log files services write themselves (Apache access/error, MariaDB), by service
+async fn native_service_logs(service_manager: &Arc<Mutex<ServiceManager>>) -> Vec<(LogSource, PathBuf)> {
+    service_manager.lock().await.native_logs()
+}
+
+/// Follow several projects' and services' logs as one time-ordered stream of
+/// `log-tail` events. Returns the tail id for `ack_log_tail` and `stop_log_tail`.
+#[tauri::command]
+pub async fn start_log_tail(
+    app: AppHandle,
+    stream_manager: State<'_, SharedLogStreamManager>,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+    options: LogTailOptions,
+) -> Result<String, String> {
+    for source in &options.sources {
+        source.check_name()?;
+    }
+    let native_logs = native_service_logs(&service_manager).await;
+    let mut manager = stream_manager.write().await;
+
+    let log_manager = manager.log_manager().clone();
+    let files = tail_files(&log_manager, &options.sources, &native_logs);
+    let mut tail = MergedTail::new(files, &options)?;
+    tail.load_backlog(&log_manager, options.backlog);
+
+    let tail_id = format!("tail:{}", uuid::Uuid::new_v4());
+    let event_tail_id = tail_id.clone();
+    manager.start_merged_stream(tail_id.clone(), tail, move |lines, pending| {
+        let batch = LogTailBatch {
+            tail_id: event_tail_id.clone(),
+            lines,
+            pending,
+        };
+        let _ = app.emit("log-tail", batch);
+    });
+
+    Ok(tail_id)
+}
+
+/// Tell a merged tail the last batch was handled, so it sends the next one
+#[tauri::command]
+pub async fn ack_log_tail(
+    stream_manager: State<'_, SharedLogStreamManager>,
+    tail_id: String,
+) -> Result<bool, String> {
+    Ok(stream_manager.read().await.ack_stream(&tail_id))
+}
+
+#[tauri::command]
+pub async fn stop_log_tail(
+    stream_manager: State<'_, SharedLogStreamManager>,
+    tail_id: String,
+) -> Result<bool, String> {
+    Ok(stream_manager.write().await.stop_stream(&tail_id))
+}
+
+#[tauri::command]
+pub async fn get_log_alert_rules(log_alerts: State<'_, SharedLogAlerts>) -> Result<Vec<LogAlertRule>, String> {
+    let engine = log_alerts.lock().map_err(|e| e.to_string())?;
+    Ok(engine.rules())
+}
+
+/// Replace the log alert rules and save them; rules without an id get one
+#[tauri::command]
+pub async fn set_log_alert_rules(
+    log_alerts: State<'_, SharedLogAlerts>,
+    mut rules: Vec<LogAlertRule>,
+) -> Result<Vec<LogAlertRule>, String> {
+    for rule in rules.iter_mut().filter(|rule| rule.id.is_empty()) {
+        rule.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    log_alerts
+        .lock()
+        .map_err(|e| e.to_string())?
+        .set_rules(rules.clone())?;
+    Storage::new()
+        .and_then(|storage| storage.save_log_alert_rules(&rules))
+        .map_err(|e| e.to_string())?;
+    Ok(rules)
+}
+
+/// Alerts raised since they were last cleared
+#[tauri::command]
+pub async fn get_unread_log_alerts(log_alerts: State<'_, SharedLogAlerts>) -> Result<u32, String> {
+    let engine = log_alerts.lock().map_err(|e| e.to_string())?;
+    Ok(engine.unread())
+}
+
+/// Mark log alerts as seen and remove the tray badge
+#[tauri::command]
+pub async fn clear_log_alerts(app: AppHandle, log_alerts: State<'_, SharedLogAlerts>) -> Result<(), String> {
+    log_alerts.lock().map_err(|e| e.to_string())?.clear_unread();
+    tray::set_alert_badge(&app, 0);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn clear_service_logs(
     log_manager: State<'_, Arc<Mutex<LogManager>>>,
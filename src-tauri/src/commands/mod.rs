@@ -1,8 +1,10 @@
 pub mod backup;
 pub mod bundler;
 pub mod config;
+pub mod control_api;
 pub mod credentials;
 pub mod database;
+pub mod dependency;
 pub mod env;
 pub mod env_profile;
 pub mod health;
@@ -16,9 +18,13 @@ pub mod port;
 pub mod process;
 pub mod project;
 pub mod recovery;
+pub mod redis;
+pub mod resource;
 pub mod scaffold;
 pub mod scheduler;
 pub mod service;
 pub mod tray;
 pub mod uninstaller;
 pub mod updater;
+pub mod vault;
+pub mod workspace;
@@ -1,8 +1,15 @@
-use crate::models::PortInfo;
+use crate::commands::config::get_apache_ports;
+use crate::models::{PortInfo, PortOwnerKind, PortReservation};
+use crate::services::port_registry::PortRegistry;
 use crate::services::port_scanner::PortScanner;
 use crate::services::process_manager::kill_process_tree;
+use crate::services::storage::Storage;
+use crate::services::ServiceManager;
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
@@ -38,13 +45,92 @@ pub async fn check_port_available(port: u16) -> Result<bool, String> {
     Ok(result)
 }
 
-/// Suggest available ports based on type and preferred port
+/// Rebuild the port registry from projects, services and Apache's Listen ports
+pub(crate) async fn sync_port_registry(
+    service_manager: &Arc<Mutex<ServiceManager>>,
+) -> Result<PortRegistry, String> {
+    let storage = Storage::new().map_err(|e| e.to_string())?;
+    let projects = storage.load_projects().map_err(|e| e.to_string())?;
+
+    // Apache may not be installed; that just means there are no Listen ports to reserve
+    let apache = get_apache_ports()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| PortReservation::new(entry.port, PortOwnerKind::Apache, entry.id, entry.name))
+        .collect();
+
+    let mut registry = PortRegistry::load(&storage).map_err(|e| e.to_string())?;
+    registry.sync(&projects, service_manager.lock().await.services.values(), apache);
+    registry.save(&storage).map_err(|e| e.to_string())?;
+
+    Ok(registry)
+}
+
+/// Fail if `port` is reserved by anything other than `owner_id`
+pub(crate) async fn check_port_reservation(
+    port: u16,
+    owner_id: Option<&str>,
+    service_manager: &Arc<Mutex<ServiceManager>>,
+) -> Result<(), String> {
+    if port == 0 {
+        return Ok(());
+    }
+
+    let registry = sync_port_registry(service_manager).await?;
+    let conflicts = registry.conflicts(port, owner_id);
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    let owners: Vec<String> = conflicts.iter().map(|r| r.describe()).collect();
+    Err(format!(
+        "Port {} is already reserved by {}",
+        port,
+        owners.join(", ")
+    ))
+}
+
+#[tauri::command]
+pub async fn get_port_reservations(
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+) -> Result<Vec<PortReservation>, String> {
+    let registry = sync_port_registry(&service_manager).await?;
+    Ok(registry.reservations().to_vec())
+}
+
+/// Reserve a port for something DevPort doesn't manage (e.g. a Docker container)
+#[tauri::command]
+pub fn reserve_port(port: u16, name: String) -> Result<PortReservation, String> {
+    let storage = Storage::new().map_err(|e| e.to_string())?;
+    let mut registry = PortRegistry::load(&storage).map_err(|e| e.to_string())?;
+    let reservation = registry.reserve(port, &name);
+    registry.save(&storage).map_err(|e| e.to_string())?;
+    Ok(reservation)
+}
+
+#[tauri::command]
+pub fn release_port(port: u16) -> Result<(), String> {
+    let storage = Storage::new().map_err(|e| e.to_string())?;
+    let mut registry = PortRegistry::load(&storage).map_err(|e| e.to_string())?;
+    if !registry.release(port) {
+        return Err(format!("Port {} has no manual reservation", port));
+    }
+    registry.save(&storage).map_err(|e| e.to_string())
+}
+
+/// Suggest available ports based on type and preferred port.
+/// Reserved ports are skipped even when nothing is listening on them.
 #[tauri::command]
 pub async fn suggest_available_port(
     preferred_port: u16,
     port_type: String,
     exclude_ports: Vec<u16>,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
 ) -> Result<Vec<u16>, String> {
+    let mut exclude_ports = exclude_ports;
+    exclude_ports.extend(sync_port_registry(&service_manager).await?.reserved_ports());
+
     tokio::task::spawn_blocking(move || {
         Ok(PortScanner::suggest_ports(preferred_port, &port_type, &exclude_ports))
     })
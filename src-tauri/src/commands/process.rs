@@ -1,21 +1,91 @@
+use crate::error::{CommandError, CommandResult, ErrorCode};
 use crate::models::process_info::ProcessInfo;
-use crate::models::ProjectType;
-use crate::services::process_manager::{ProcessManager, kill_process_tree_silent};
+use crate::models::{Project, ProjectType, StopOutcome, StopStep};
+use crate::services::port_conflict::{find_port_conflict, next_free_port, port_owner, PortConflictAction};
+use crate::services::port_scanner::PortScanner;
+use crate::services::process_manager::{
+    kill_process_tree, kill_process_tree_silent, ProcessError, SharedProcessManager,
+};
 use crate::services::project_detector::ProjectDetector;
-use crate::services::storage::Storage;
+use crate::services::storage::{Storage, StorageError};
 use crate::state::AppState;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, State};
 use tokio::sync::Mutex;
 
-#[tauri::command]
-pub async fn start_project(
-    project_id: String,
-    state: State<'_, Arc<Mutex<AppState>>>,
+/// How long to wait for a killed port owner to release the port
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn project_error(err: StorageError) -> CommandError {
+    match err {
+        StorageError::NotFound(id) => CommandError::project_not_found(&id),
+        other => CommandError::from_error(other),
+    }
+}
+
+/// Fail with `PORT_IN_USE` and the available resolutions if the project's port is taken
+async fn ensure_port_free(project: &Project) -> CommandResult<()> {
+    let project_id = project.id.clone();
+    let port = project.port;
+    let conflict = tokio::task::spawn_blocking(move || find_port_conflict(&project_id, port))
+        .await
+        .map_err(CommandError::from_error)?;
+
+    match conflict {
+        Some(conflict) => {
+            Err(CommandError::new(ErrorCode::PortInUse, conflict.message()).with_data(conflict))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Kill whatever holds `port` and wait until the port is released
+fn free_port(port: u16) -> CommandResult<()> {
+    let pid = port_owner(port)
+        .and_then(|o| o.pid)
+        .filter(|pid| *pid != 0)
+        .ok_or_else(|| {
+            CommandError::new(
+                ErrorCode::ProcessNotFound,
+                format!("Could not find the process using port {}", port),
+            )
+        })?;
+
+    let result = kill_process_tree(pid);
+    if !result.success {
+        return Err(CommandError::with_details(
+            ErrorCode::ProcessKillFailed,
+            format!("Failed to kill process {} using port {}", pid, port),
+            result.error.unwrap_or_default(),
+        ));
+    }
+
+    let deadline = Instant::now() + PORT_RELEASE_TIMEOUT;
+    while Instant::now() < deadline {
+        if PortScanner::is_port_available(port) {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(250));
+    }
+
+    Err(CommandError::new(
+        ErrorCode::PortInUse,
+        format!("Port {} is still in use after killing process {}", port, pid),
+    )
+    .retryable())
+}
+
+/// Start a project and record it in `AppState`.
+/// Shared by `start_project` and the ordered group/workspace starts.
+pub(crate) async fn launch_project(
+    project_id: &str,
+    state: &Arc<Mutex<AppState>>,
+    process_manager: &SharedProcessManager,
     app_handle: AppHandle,
-) -> Result<ProcessInfo, String> {
-    let storage = Storage::new().map_err(|e| e.to_string())?;
-    let mut project = storage.get_project(&project_id).map_err(|e| e.to_string())?;
+) -> CommandResult<ProcessInfo> {
+    let storage = Storage::new().map_err(CommandError::from_error)?;
+    let mut project = storage.get_project(project_id).map_err(project_error)?;
 
     // Tauri project: sync port from tauri.conf.json devUrl
     if matches!(project.project_type, ProjectType::Tauri) {
@@ -29,60 +99,210 @@ pub async fn start_project(
         }
     }
 
+    // Already running: return the existing process instead of spawning a duplicate
+    {
+        let app_state = state.lock().await;
+        if process_manager.lock().await.is_running(project_id) {
+            if let Some(process_info) = app_state.running_processes.get(project_id) {
+                return Ok(process_info.clone());
+            }
+        }
+    }
+
+    // Catch a taken port before the dev server fails or silently moves elsewhere
+    ensure_port_free(&project).await?;
+
     let mut app_state = state.lock().await;
-    let mut process_manager = ProcessManager::new();
+    let mut process_manager = process_manager.lock().await;
+
+    // Another start (UI, CLI, control API or a dependency start) may have won the race
+    // while the locks were released; spawning again would orphan its child
+    if process_manager.is_running(project_id) {
+        return app_state
+            .running_processes
+            .get(project_id)
+            .cloned()
+            .ok_or_else(|| {
+                CommandError::new(
+                    ErrorCode::ProcessStartFailed,
+                    format!("Project {} is already running", project.name),
+                )
+            });
+    }
+
+    // A manual start begins a fresh restart history
+    process_manager.reset_restarts(project_id);
 
     let process_info = process_manager
-        .start_project(&project, app_handle)
-        .map_err(|e| e.to_string())?;
+        .start_project(&project, Some(app_handle))
+        .map_err(|e| CommandError::new(ErrorCode::ProcessStartFailed, e.to_string()))?;
 
     app_state
         .running_processes
-        .insert(project_id, process_info.clone());
+        .insert(project_id.to_string(), process_info.clone());
 
     Ok(process_info)
 }
 
-#[tauri::command]
-pub async fn stop_project(
-    project_id: String,
-    state: State<'_, Arc<Mutex<AppState>>>,
+/// Stop a project and remove it from `AppState`.
+/// Shared by `stop_project` and the ordered group/workspace stops.
+/// Returns which stop step ended the process, or `None` if nothing was running.
+pub(crate) async fn halt_project(
+    project_id: &str,
+    state: &Arc<Mutex<AppState>>,
+    process_manager: &SharedProcessManager,
     app_handle: AppHandle,
-) -> Result<(), String> {
-    let mut app_state = state.lock().await;
+) -> Result<Option<StopOutcome>, ProcessError> {
+    let (process_info, stopping) = {
+        let mut app_state = state.lock().await;
+        let process_info = app_state.running_processes.remove(project_id);
+        let stopping = process_manager.lock().await.take_for_stop(project_id);
+        (process_info, stopping)
+    };
+
+    // Managed child: run its stop strategy and wait for it to exit. The wait can
+    // take the whole stop timeout, so it runs without holding either lock.
+    if let Ok(mut stopping) = stopping {
+        let (stopping, result) = tokio::task::spawn_blocking(move || {
+            let result = stopping.stop();
+            (stopping, result)
+        })
+        .await
+        .map_err(|e| ProcessError::StopError(e.to_string()))?;
+
+        let mut app_state = state.lock().await;
+        let mut process_manager = process_manager.lock().await;
+        return match process_manager.finish_stop(stopping, result, Some(app_handle)) {
+            Ok(outcome) => Ok(Some(outcome)),
+            Err(e) => {
+                // Still running
+                if let Some(process_info) = process_info {
+                    app_state
+                        .running_processes
+                        .insert(project_id.to_string(), process_info);
+                }
+                Err(e)
+            }
+        };
+    }
 
-    if let Some(process_info) = app_state.running_processes.remove(&project_id) {
-        // Kill the process tree using centralized function
+    let mut process_manager = process_manager.lock().await;
+
+    // Waiting to auto-restart: cancelling the restart is the stop
+    if process_manager.cancel_restart(project_id) {
+        let _ = app_handle.emit(
+            "process-stopped",
+            serde_json::json!({
+                "projectId": project_id
+            }),
+        );
+        return Ok(None);
+    }
+
+    if let Some(process_info) = process_info {
+        // No child handle (e.g. already reaped) - fall back to killing by PID
         kill_process_tree_silent(process_info.pid);
 
         // Emit process stopped event
         let _ = app_handle.emit(
             "process-stopped",
             serde_json::json!({
-                "projectId": project_id
+                "projectId": project_id,
+                "stoppedBy": StopStep::ForceKill
             }),
         );
 
-        Ok(())
+        Ok(Some(StopOutcome {
+            stopped_by: StopStep::ForceKill,
+            exit_code: None,
+        }))
     } else {
-        Err(format!("Process not found for project: {}", project_id))
+        Err(ProcessError::NotFound(project_id.to_string()))
     }
 }
 
 #[tauri::command]
-pub async fn restart_project(
+pub async fn start_project(
+    project_id: String,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    process_manager: State<'_, SharedProcessManager>,
+    app_handle: AppHandle,
+) -> CommandResult<ProcessInfo> {
+    launch_project(&project_id, &state, &process_manager, app_handle).await
+}
+
+#[tauri::command]
+pub async fn stop_project(
     project_id: String,
     state: State<'_, Arc<Mutex<AppState>>>,
+    process_manager: State<'_, SharedProcessManager>,
     app_handle: AppHandle,
-) -> Result<ProcessInfo, String> {
-    // Stop the project first
-    let _ = stop_project(project_id.clone(), state.clone(), app_handle.clone()).await;
+) -> Result<Option<StopOutcome>, String> {
+    halt_project(&project_id, &state, &process_manager, app_handle)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    // Wait a bit for the process to fully stop
-    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+#[tauri::command]
+pub async fn restart_project(
+    project_id: String,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    process_manager: State<'_, SharedProcessManager>,
+    app_handle: AppHandle,
+) -> CommandResult<ProcessInfo> {
+    // Stop the project first; this waits for the process to exit
+    let _ = halt_project(&project_id, &state, &process_manager, app_handle.clone()).await;
 
     // Start the project again
-    start_project(project_id, state, app_handle).await
+    launch_project(&project_id, &state, &process_manager, app_handle).await
+}
+
+/// Resolve a `PORT_IN_USE` error from `start_project`, then start the project.
+/// Returns `None` when the user chose to abort.
+#[tauri::command]
+pub async fn resolve_port_conflict(
+    project_id: String,
+    action: PortConflictAction,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    process_manager: State<'_, SharedProcessManager>,
+    app_handle: AppHandle,
+) -> CommandResult<Option<ProcessInfo>> {
+    let storage = Storage::new().map_err(CommandError::from_error)?;
+    let mut project = storage.get_project(&project_id).map_err(project_error)?;
+    let port = project.port;
+
+    match action {
+        PortConflictAction::Abort => return Ok(None),
+        PortConflictAction::KillOwner => {
+            tokio::task::spawn_blocking(move || free_port(port))
+                .await
+                .map_err(CommandError::from_error)??;
+        }
+        PortConflictAction::UseNextPort => {
+            let new_port = tokio::task::spawn_blocking(move || next_free_port(port))
+                .await
+                .map_err(CommandError::from_error)?
+                .ok_or_else(|| {
+                    CommandError::new(
+                        ErrorCode::PortInUse,
+                        format!("No free port found after {}", port),
+                    )
+                })?;
+
+            // Rewrite tauri.conf.json / vite.config / package.json so the dev server agrees
+            let project_path = std::path::Path::new(&project.path);
+            ProjectDetector::update_configured_port(project_path, &project.project_type, new_port)
+                .map_err(|e| CommandError::new(ErrorCode::ConfigWriteFailed, e))?;
+
+            project.port = new_port;
+            project.updated_at = chrono::Utc::now().to_rfc3339();
+            storage.update_project(project).map_err(CommandError::from_error)?;
+        }
+    }
+
+    launch_project(&project_id, &state, &process_manager, app_handle)
+        .await
+        .map(Some)
 }
 
 #[tauri::command]
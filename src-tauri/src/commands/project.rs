@@ -1,9 +1,16 @@
-use crate::models::{Project, ProjectType};
-use crate::services::database_manager::DatabaseManager;
+use crate::commands::dependency::dependency_error;
+use crate::commands::port::check_port_reservation;
+use crate::error::{CommandError, CommandResult};
+use crate::models::{HealthCheckConfig, LogConfig, Project, ProjectType, RestartPolicy, StopStrategy};
+use crate::services::database_manager::{DatabaseCredentials, DatabaseEngine, DatabaseManager};
+use crate::services::dependency_graph::DependencyGraph;
 use crate::services::hosts_manager::HostsManager;
+use crate::services::env_manager::EnvManager;
 use crate::services::project_detector::ProjectDetector;
+use crate::services::redis_manager::RedisManager;
+use crate::services::secret_vault::SecretVault;
 use crate::services::storage::Storage;
-use crate::services::SharedProjectWatcher;
+use crate::services::{ServiceManager, SharedProjectWatcher};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::State;
@@ -20,13 +27,33 @@ pub struct CreateProjectInput {
     #[serde(default)]
     pub auto_start: bool,
     pub health_check_url: Option<String>,
+    pub health_check: Option<HealthCheckConfig>,
     pub domain: Option<String>,
     pub github_url: Option<String>,
     #[serde(default = "default_launch_mode")]
     pub launch_mode: String,
     #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    #[serde(default)]
+    pub persist_detected_port: bool,
+    pub stop_timeout: Option<u64>,
+    #[serde(default)]
+    pub stop_strategy: StopStrategy,
+    pub log_config: Option<LogConfig>,
+    #[serde(default)]
     pub create_database: bool,
     pub database_name: Option<String>,
+    /// Server the database is created on
+    #[serde(default)]
+    pub database_engine: DatabaseEngine,
+    /// Give the project its own Redis database and write `REDIS_URL` to `.env`
+    #[serde(default)]
+    pub allocate_redis: bool,
+    /// Save even if the port is already reserved by another project or service
+    #[serde(default)]
+    pub allow_port_conflict: bool,
 }
 
 fn default_launch_mode() -> String {
@@ -42,7 +69,17 @@ pub struct UpdateProjectInput {
     pub start_command: Option<String>,
     pub auto_start: Option<bool>,
     pub health_check_url: Option<String>,
+    pub health_check: Option<HealthCheckConfig>,
     pub launch_mode: Option<String>,
+    pub restart_policy: Option<RestartPolicy>,
+    pub depends_on: Option<Vec<String>>,
+    pub persist_detected_port: Option<bool>,
+    pub stop_timeout: Option<u64>,
+    pub stop_strategy: Option<StopStrategy>,
+    pub log_config: Option<LogConfig>,
+    /// Save even if the port is already reserved by another project or service
+    #[serde(default)]
+    pub allow_port_conflict: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -90,9 +127,14 @@ pub async fn create_project(
     input: CreateProjectInput,
     project_watcher: State<'_, SharedProjectWatcher>,
     database_manager: State<'_, Arc<Mutex<DatabaseManager>>>,
-) -> Result<Project, String> {
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+) -> CommandResult<Project> {
     let storage = Storage::new().map_err(|e| e.to_string())?;
 
+    if !input.allow_port_conflict {
+        check_port_reservation(input.port, None, &service_manager).await?;
+    }
+
     let project_path = if input.create_database { Some(input.path.clone()) } else { None };
     let project_type_clone = input.project_type.clone();
 
@@ -107,9 +149,23 @@ pub async fn create_project(
     let mut project = project;
     project.auto_start = input.auto_start;
     project.health_check_url = input.health_check_url;
+    project.health_check = input.health_check;
     project.domain = input.domain.clone();
     project.github_url = input.github_url;
     project.launch_mode = input.launch_mode;
+    project.restart_policy = input.restart_policy;
+    project.depends_on = input.depends_on;
+    project.persist_detected_port = input.persist_detected_port;
+    if let Some(stop_timeout) = input.stop_timeout {
+        project.stop_timeout = stop_timeout;
+    }
+    project.stop_strategy = input.stop_strategy;
+    if let Some(log_config) = input.log_config {
+        project.log_config = log_config;
+    }
+    if !project.depends_on.is_empty() {
+        validate_dependencies(&storage, &project, &service_manager).await?;
+    }
 
     // Add hosts entry if domain is provided
     if let Some(ref domain) = input.domain {
@@ -133,13 +189,22 @@ pub async fn create_project(
                 let db_username = db_name.clone();
                 let db_password = generate_db_password();
 
-                match db_manager.create_database_with_user(db_name, &db_username, &db_password) {
+                match db_manager
+                    .create_database_with_user(
+                        input.database_engine,
+                        db_name,
+                        &db_username,
+                        &db_password,
+                    )
+                    .await
+                {
                     Ok(creds) => {
                         // Inject DB environment variables into .env file
                         if let Some(ref proj_path) = project_path {
                             let _ = inject_db_env_vars(
                                 proj_path,
                                 &project_type_clone,
+                                input.database_engine,
                                 &creds,
                             );
                         }
@@ -155,6 +220,20 @@ pub async fn create_project(
 
     let created_project = storage.create_project(project).map_err(|e| e.to_string())?;
 
+    // Allocated after saving, since the index is keyed by the project id
+    if input.allocate_redis {
+        let allocated = RedisManager::new()
+            .and_then(|redis| redis.allocate(&created_project.id))
+            .and_then(|allocation| {
+                EnvManager::new(std::path::PathBuf::from(&created_project.path))
+                    .set_variable(".env", "REDIS_URL", &allocation.url)
+            });
+        if let Err(e) = allocated {
+            eprintln!("Failed to allocate Redis database: {}", e);
+            // Don't fail project creation if the allocation fails
+        }
+    }
+
     // Start watching the new project
     if let Ok(mut watcher) = project_watcher.lock() {
         let _ = watcher.watch_project(&created_project);
@@ -177,15 +256,13 @@ fn generate_db_password() -> String {
 fn inject_db_env_vars(
     project_path: &str,
     project_type: &ProjectType,
-    creds: &crate::services::database_manager::DatabaseCredentials,
+    engine: DatabaseEngine,
+    creds: &DatabaseCredentials,
 ) -> Result<(), String> {
     let path = std::path::Path::new(project_path);
     let env_path = path.join(".env");
 
-    let connection_url = format!(
-        "mysql://{}:{}@{}:{}/{}",
-        creds.username, creds.password, creds.host, creds.port, creds.database
-    );
+    let connection_url = engine.connection_url(creds);
 
     // Build env vars based on framework type
     let mut new_vars: Vec<(String, String)> = Vec::new();
@@ -199,6 +276,12 @@ fn inject_db_env_vars(
         _ => {
             // Generic: provide both DATABASE_URL and individual vars
             new_vars.push(("DATABASE_URL".to_string(), connection_url));
+            // Laravel's driver names
+            let connection = match engine {
+                DatabaseEngine::Mysql => "mysql",
+                DatabaseEngine::Postgres => "pgsql",
+            };
+            new_vars.push(("DB_CONNECTION".to_string(), connection.to_string()));
             new_vars.push(("DB_HOST".to_string(), creds.host.clone()));
             new_vars.push(("DB_PORT".to_string(), creds.port.to_string()));
             new_vars.push(("DB_DATABASE".to_string(), creds.database.clone()));
@@ -227,7 +310,10 @@ fn inject_db_env_vars(
 }
 
 #[tauri::command]
-pub fn update_project(input: UpdateProjectInput) -> Result<Project, String> {
+pub async fn update_project(
+    input: UpdateProjectInput,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+) -> CommandResult<Project> {
     let storage = Storage::new().map_err(|e| e.to_string())?;
 
     let mut project = storage.get_project(&input.id).map_err(|e| e.to_string())?;
@@ -236,6 +322,10 @@ pub fn update_project(input: UpdateProjectInput) -> Result<Project, String> {
         project.name = name;
     }
     if let Some(port) = input.port {
+        if port != project.port && !input.allow_port_conflict {
+            check_port_reservation(port, Some(&project.id), &service_manager).await?;
+        }
+
         // Tauri: sync port to config files (tauri.conf.json + vite.config.ts)
         if matches!(project.project_type, ProjectType::Tauri) && port != project.port {
             let project_path = std::path::Path::new(&project.path);
@@ -253,13 +343,52 @@ pub fn update_project(input: UpdateProjectInput) -> Result<Project, String> {
     if let Some(health_check_url) = input.health_check_url {
         project.health_check_url = Some(health_check_url);
     }
+    if let Some(health_check) = input.health_check {
+        project.health_check = Some(health_check);
+    }
     if let Some(launch_mode) = input.launch_mode {
         project.launch_mode = launch_mode;
     }
+    if let Some(restart_policy) = input.restart_policy {
+        project.restart_policy = restart_policy;
+    }
+    if let Some(persist_detected_port) = input.persist_detected_port {
+        project.persist_detected_port = persist_detected_port;
+    }
+    if let Some(stop_timeout) = input.stop_timeout {
+        project.stop_timeout = stop_timeout;
+    }
+    if let Some(stop_strategy) = input.stop_strategy {
+        project.stop_strategy = stop_strategy;
+    }
+    if let Some(log_config) = input.log_config {
+        project.log_config = log_config;
+    }
+    if let Some(depends_on) = input.depends_on {
+        project.depends_on = depends_on;
+        validate_dependencies(&storage, &project, &service_manager).await?;
+    }
 
     project.updated_at = chrono::Utc::now().to_rfc3339();
 
-    storage.update_project(project).map_err(|e| e.to_string())
+    storage.update_project(project).map_err(CommandError::from_error)
+}
+
+/// Reject unknown dependencies, self-references and cycles before `project` is saved
+async fn validate_dependencies(
+    storage: &Storage,
+    project: &Project,
+    service_manager: &Arc<Mutex<ServiceManager>>,
+) -> CommandResult<()> {
+    let mut projects = storage.load_projects().map_err(CommandError::from_error)?;
+    match projects.iter_mut().find(|p| p.id == project.id) {
+        Some(existing) => *existing = project.clone(),
+        None => projects.push(project.clone()),
+    }
+    let manager = service_manager.lock().await;
+    DependencyGraph::new(&projects, manager.services.values())
+        .validate()
+        .map_err(dependency_error)
 }
 
 #[tauri::command]
@@ -282,6 +411,14 @@ pub fn delete_project(
         }
     }
 
+    // Drop the project's vault secrets
+    if let Err(e) = SecretVault::new().and_then(|vault| vault.delete_project(&id)) {
+        eprintln!("Failed to remove vault entries of {}: {}", project.name, e);
+    }
+    if let Err(e) = RedisManager::new().and_then(|redis| redis.release(&id)) {
+        eprintln!("Failed to release Redis database of {}: {}", project.name, e);
+    }
+
     // Stop watching the project
     if let Ok(mut watcher) = project_watcher.lock() {
         watcher.unwatch_project(&id);
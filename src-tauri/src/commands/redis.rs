@@ -0,0 +1,77 @@
+//! Tauri commands for the Redis service and its per-project databases
+
+use crate::models::Service;
+use crate::services::env_manager::EnvManager;
+use crate::services::redis_manager::{RedisAllocation, RedisConfig, RedisManager};
+use crate::services::storage::Storage;
+use crate::services::ServiceManager;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[tauri::command]
+pub fn get_redis_config() -> Result<RedisConfig, String> {
+    Ok(RedisConfig::load())
+}
+
+/// Writes the settings to `redis.conf`; a running server picks them up on restart.
+/// A new port is also written to the `REDIS_URL` of every project with a database.
+#[tauri::command]
+pub async fn update_redis_config(
+    config: RedisConfig,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+) -> Result<RedisConfig, String> {
+    let redis = RedisManager::new()?;
+    redis.check_config(&config)?;
+    let previous_port = RedisConfig::load().port;
+    config.save(&Service::redis_config_path())?;
+
+    if config.port != previous_port {
+        let storage = Storage::new().map_err(|e| e.to_string())?;
+        for allocation in redis.list()? {
+            // Allocations of deleted projects have no .env to update
+            let Ok(project) = storage.get_project(&allocation.project_id) else {
+                continue;
+            };
+            let env = EnvManager::new(PathBuf::from(&project.path));
+            if let Err(e) = env.set_variable(".env", "REDIS_URL", &allocation.url) {
+                eprintln!("Failed to update REDIS_URL of {}: {}", project.name, e);
+            }
+        }
+    }
+
+    let mut manager = service_manager.lock().await;
+    if let Some(service) = manager.get_service_mut("redis") {
+        let updated = Service::redis(config.port);
+        service.port = updated.port;
+        service.health_check = updated.health_check;
+        // The generated shutdown command names the port; one the user set is kept
+        if service.stop_strategy.command == Service::redis(previous_port).stop_strategy.command {
+            service.stop_strategy.command = updated.stop_strategy.command;
+        }
+    }
+    Ok(config)
+}
+
+#[tauri::command]
+pub fn list_redis_allocations() -> Result<Vec<RedisAllocation>, String> {
+    RedisManager::new()?.list()
+}
+
+/// Gives a project its own Redis database and writes `REDIS_URL` to its `.env`.
+/// A project that already has one keeps its index.
+#[tauri::command]
+pub fn allocate_redis_database(project_id: String) -> Result<RedisAllocation, String> {
+    let storage = Storage::new().map_err(|e| e.to_string())?;
+    let project = storage.get_project(&project_id).map_err(|e| e.to_string())?;
+    let allocation = RedisManager::new()?.allocate(&project.id)?;
+    EnvManager::new(PathBuf::from(&project.path)).set_variable(".env", "REDIS_URL", &allocation.url)?;
+    Ok(allocation)
+}
+
+/// Frees the project's database and flushes its keys. `REDIS_URL` stays in `.env`.
+#[tauri::command]
+pub fn release_redis_database(project_id: String) -> Result<(), String> {
+    RedisManager::new()?.release(&project_id)
+}
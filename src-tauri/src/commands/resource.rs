@@ -0,0 +1,21 @@
+use crate::services::resource_monitor::{ResourceOwner, ResourceSample, ResourceUsage};
+use crate::services::SharedResourceMonitor;
+use tauri::State;
+
+/// Latest CPU/memory sample of every running project and service
+#[tauri::command]
+pub async fn get_resource_usage(
+    resource_monitor: State<'_, SharedResourceMonitor>,
+) -> Result<Vec<ResourceUsage>, String> {
+    Ok(resource_monitor.lock().await.latest())
+}
+
+/// Recent CPU/memory samples of one project or service, oldest first
+#[tauri::command]
+pub async fn get_resource_history(
+    owner: ResourceOwner,
+    id: String,
+    resource_monitor: State<'_, SharedResourceMonitor>,
+) -> Result<Vec<ResourceSample>, String> {
+    Ok(resource_monitor.lock().await.history(owner, &id))
+}
@@ -1,4 +1,4 @@
-use crate::models::{ConfigFile, Service};
+use crate::models::{ConfigFile, Service, StopStep, StopStrategy};
 use crate::services::ServiceManager;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -40,6 +40,11 @@ pub struct ServiceInfo {
     pub error_message: Option<String>,
     pub installed: bool,
     pub config_files: Vec<ConfigFileInfo>,
+    pub stop_strategy: StopStrategy,
+    pub stop_timeout: u64,
+    pub stopped_by: Option<StopStep>,
+    pub cpu_usage: Option<f32>,
+    pub memory_usage: Option<u64>,
 }
 
 impl From<&Service> for ServiceInfo {
@@ -58,6 +63,11 @@ impl From<&Service> for ServiceInfo {
             error_message: service.error_message.clone(),
             installed: service.installed,
             config_files: service.config_files.iter().map(ConfigFileInfo::from).collect(),
+            stop_strategy: service.stop_strategy.clone(),
+            stop_timeout: service.stop_timeout,
+            stopped_by: service.stopped_by.clone(),
+            cpu_usage: service.cpu_usage,
+            memory_usage: service.memory_usage,
         }
     }
 }
@@ -102,8 +112,8 @@ pub async fn stop_service(
     service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
     id: String,
 ) -> Result<ServiceInfo, String> {
-    let mut manager = service_manager.lock().await;
-    manager.stop_service(&id).await?;
+    ServiceManager::stop_service(&service_manager, &id).await?;
+    let manager = service_manager.lock().await;
     manager
         .get_service(&id)
         .map(|s| ServiceInfo::from(s))
@@ -116,8 +126,8 @@ pub async fn restart_service(
     service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
     id: String,
 ) -> Result<ServiceInfo, String> {
-    let mut manager = service_manager.lock().await;
-    manager.restart_service(&id, Some(app)).await?;
+    ServiceManager::restart_service(&service_manager, &id, Some(app)).await?;
+    let manager = service_manager.lock().await;
     manager
         .get_service(&id)
         .map(|s| ServiceInfo::from(s))
@@ -177,6 +187,25 @@ pub async fn set_service_auto_restart(
     }
 }
 
+#[tauri::command]
+pub async fn set_service_stop_strategy(
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+    id: String,
+    stop_strategy: StopStrategy,
+    stop_timeout: Option<u64>,
+) -> Result<ServiceInfo, String> {
+    let mut manager = service_manager.lock().await;
+    if let Some(service) = manager.get_service_mut(&id) {
+        service.stop_strategy = stop_strategy;
+        if let Some(stop_timeout) = stop_timeout {
+            service.stop_timeout = stop_timeout;
+        }
+        Ok(ServiceInfo::from(&service.clone()))
+    } else {
+        Err("Service not found".to_string())
+    }
+}
+
 #[tauri::command]
 pub async fn get_service_config(
     service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
@@ -20,11 +20,8 @@ pub async fn perform_uninstall(
     let manager = UninstallManager::new();
 
     // Stop all services first
-    {
-        let mut sm = service_manager.lock().await;
-        let _ = sm.stop_service("apache").await;
-        let _ = sm.stop_service("mariadb").await;
-    }
+    let _ = ServiceManager::stop_service(&service_manager, "apache").await;
+    let _ = ServiceManager::stop_service(&service_manager, "mariadb").await;
 
     // Also stop via system commands to ensure cleanup
     let _ = manager.stop_all_services();
@@ -48,11 +45,8 @@ pub async fn stop_all_for_uninstall(
     let manager = UninstallManager::new();
 
     // Stop services via service manager
-    {
-        let mut sm = service_manager.lock().await;
-        let _ = sm.stop_service("apache").await;
-        let _ = sm.stop_service("mariadb").await;
-    }
+    let _ = ServiceManager::stop_service(&service_manager, "apache").await;
+    let _ = ServiceManager::stop_service(&service_manager, "mariadb").await;
 
     // Also stop via system commands
     manager.stop_all_services().map_err(|e| e.to_string())?;
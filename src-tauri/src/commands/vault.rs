@@ -0,0 +1,37 @@
+//! Tauri commands for the project secret vault
+//!
+//! Values are write-only from the UI's point of view: they can be set and rotated,
+//! and are only ever read back when a project starts.
+
+use crate::services::secret_vault::{SecretVault, VaultEntry};
+use crate::services::storage::Storage;
+
+/// Lists a project's vault entries and the `.env` variables referencing each.
+#[tauri::command]
+pub fn list_vault_entries(project_id: String) -> Result<Vec<VaultEntry>, String> {
+    let storage = Storage::new().map_err(|e| e.to_string())?;
+    let project = storage.get_project(&project_id).map_err(|e| e.to_string())?;
+    SecretVault::new()?.list(&project.id, &project.path)
+}
+
+/// Stores a secret for a project, to be referenced as `${vault:<name>}`.
+#[tauri::command]
+pub fn set_vault_entry(project_id: String, name: String, value: String) -> Result<VaultEntry, String> {
+    SecretVault::new()?.set(&project_id, &name, &value)
+}
+
+/// Replaces the value of an existing entry; a random secret is generated without `value`.
+/// Running projects pick up the new value on their next start.
+#[tauri::command]
+pub fn rotate_vault_entry(
+    project_id: String,
+    name: String,
+    value: Option<String>,
+) -> Result<VaultEntry, String> {
+    SecretVault::new()?.rotate(&project_id, &name, value.as_deref())
+}
+
+#[tauri::command]
+pub fn delete_vault_entry(project_id: String, name: String) -> Result<(), String> {
+    SecretVault::new()?.delete(&project_id, &name)
+}
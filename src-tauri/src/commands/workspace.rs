@@ -0,0 +1,191 @@
+use crate::commands::dependency::{
+    build_dependency_graph, dependency_error, start_in_order, stop_in_order,
+};
+use crate::error::{CommandError, CommandResult, ErrorCode};
+use crate::models::Workspace;
+use crate::services::dependency_graph::DependencyNode;
+use crate::services::storage::{Storage, StorageError};
+use crate::services::{ServiceManager, SharedProcessManager};
+use crate::state::AppState;
+use crate::tray;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWorkspaceInput {
+    pub name: String,
+    #[serde(default)]
+    pub project_ids: Vec<String>,
+    #[serde(default)]
+    pub service_ids: Vec<String>,
+    #[serde(default)]
+    pub start_order: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWorkspaceInput {
+    pub id: String,
+    pub name: Option<String>,
+    pub project_ids: Option<Vec<String>>,
+    pub service_ids: Option<Vec<String>>,
+    pub start_order: Option<Vec<String>>,
+}
+
+fn storage_error(err: StorageError) -> CommandError {
+    match err {
+        StorageError::WorkspaceNotFound(id) => CommandError::not_found("Workspace", &id),
+        other => CommandError::from_error(other),
+    }
+}
+
+/// Make sure every member exists and the members' dependencies are acyclic
+async fn validate_workspace(
+    workspace: &Workspace,
+    service_manager: &Arc<Mutex<ServiceManager>>,
+) -> CommandResult<()> {
+    if workspace.name.trim().is_empty() {
+        return Err(CommandError::new(ErrorCode::InvalidInput, "Workspace name is required"));
+    }
+
+    let graph = build_dependency_graph(service_manager).await?;
+    graph
+        .start_order(&workspace.ordered_members())
+        .map(|_| ())
+        .map_err(dependency_error)
+}
+
+/// Start every member of a workspace, dependencies first
+pub(crate) async fn start_workspace_by_id(
+    workspace_id: &str,
+    state: &Arc<Mutex<AppState>>,
+    process_manager: &SharedProcessManager,
+    service_manager: &Arc<Mutex<ServiceManager>>,
+    app_handle: &AppHandle,
+) -> CommandResult<Vec<DependencyNode>> {
+    let workspace = Storage::new()
+        .and_then(|s| s.get_workspace(workspace_id))
+        .map_err(storage_error)?;
+
+    let graph = build_dependency_graph(service_manager).await?;
+    let order = graph
+        .start_order(&workspace.ordered_members())
+        .map_err(dependency_error)?;
+
+    start_in_order(&order, state, process_manager, service_manager, app_handle).await?;
+
+    Ok(order)
+}
+
+/// Stop every member of a workspace, dependents first
+pub(crate) async fn stop_workspace_by_id(
+    workspace_id: &str,
+    state: &Arc<Mutex<AppState>>,
+    process_manager: &SharedProcessManager,
+    service_manager: &Arc<Mutex<ServiceManager>>,
+    app_handle: &AppHandle,
+) -> CommandResult<Vec<DependencyNode>> {
+    let workspace = Storage::new()
+        .and_then(|s| s.get_workspace(workspace_id))
+        .map_err(storage_error)?;
+
+    let graph = build_dependency_graph(service_manager).await?;
+    let order = graph
+        .stop_order(&workspace.ordered_members())
+        .map_err(dependency_error)?;
+
+    stop_in_order(&order, &graph, state, process_manager, service_manager, app_handle).await
+}
+
+#[tauri::command]
+pub fn get_workspaces() -> CommandResult<Vec<Workspace>> {
+    Storage::new()
+        .and_then(|s| s.load_workspaces())
+        .map_err(storage_error)
+}
+
+#[tauri::command]
+pub async fn create_workspace(
+    input: CreateWorkspaceInput,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+    app_handle: AppHandle,
+) -> CommandResult<Workspace> {
+    let mut workspace = Workspace::new(input.name, input.project_ids, input.service_ids);
+    workspace.start_order = input.start_order;
+
+    validate_workspace(&workspace, &service_manager).await?;
+
+    let storage = Storage::new().map_err(storage_error)?;
+    let created = storage.create_workspace(workspace).map_err(storage_error)?;
+
+    tray::refresh_tray_menu(&app_handle);
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_workspace(
+    input: UpdateWorkspaceInput,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+    app_handle: AppHandle,
+) -> CommandResult<Workspace> {
+    let storage = Storage::new().map_err(storage_error)?;
+    let mut workspace = storage.get_workspace(&input.id).map_err(storage_error)?;
+
+    if let Some(name) = input.name {
+        workspace.name = name;
+    }
+    if let Some(project_ids) = input.project_ids {
+        workspace.project_ids = project_ids;
+    }
+    if let Some(service_ids) = input.service_ids {
+        workspace.service_ids = service_ids;
+    }
+    if let Some(start_order) = input.start_order {
+        workspace.start_order = start_order;
+    }
+
+    validate_workspace(&workspace, &service_manager).await?;
+
+    workspace.updated_at = chrono::Utc::now().to_rfc3339();
+    let updated = storage.update_workspace(workspace).map_err(storage_error)?;
+
+    tray::refresh_tray_menu(&app_handle);
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn delete_workspace(id: String, app_handle: AppHandle) -> CommandResult<()> {
+    let storage = Storage::new().map_err(storage_error)?;
+    storage.delete_workspace(&id).map_err(storage_error)?;
+
+    tray::refresh_tray_menu(&app_handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn start_workspace(
+    id: String,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    process_manager: State<'_, SharedProcessManager>,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+    app_handle: AppHandle,
+) -> CommandResult<Vec<DependencyNode>> {
+    start_workspace_by_id(&id, &state, &process_manager, &service_manager, &app_handle).await
+}
+
+#[tauri::command]
+pub async fn stop_workspace(
+    id: String,
+    state: State<'_, Arc<Mutex<AppState>>>,
+    process_manager: State<'_, SharedProcessManager>,
+    service_manager: State<'_, Arc<Mutex<ServiceManager>>>,
+    app_handle: AppHandle,
+) -> CommandResult<Vec<DependencyNode>> {
+    stop_workspace_by_id(&id, &state, &process_manager, &service_manager, &app_handle).await
+}
@@ -0,0 +1,402 @@
+//! Opt-in local control API.
+//!
+//! Serves JSON over HTTP on 127.0.0.1 or a Unix domain socket so editors, git
+//! hooks and scripts can drive the running app. Handlers read the same managed
+//! `AppState`, `ProcessManager`, `ServiceManager` and `LogManager` as the Tauri
+//! commands, so the API and the GUI never disagree. Every request must carry
+//! `Authorization: Bearer <token>`, where the token is stored in the app data
+//! directory.
+
+use crate::commands::process::{halt_project, launch_project};
+use crate::error::{CommandError, ErrorCode};
+use crate::models::{ControlApiConfig, ControlApiTransport, PortInfo, ProcessInfo, Project, Service};
+use crate::services::log_manager::{LogEntry, LogManager};
+use crate::services::port_scanner::PortScanner;
+use crate::services::process_manager::ProcessError;
+use crate::services::storage::{Storage, StorageError};
+use crate::services::{ServiceManager, SharedProcessManager};
+use crate::state::AppState;
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{oneshot, Mutex};
+
+/// Error response: the HTTP status plus the same structured error the commands return
+struct ApiError(StatusCode, CommandError);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(self.1)).into_response()
+    }
+}
+
+impl From<StorageError> for ApiError {
+    fn from(err: StorageError) -> Self {
+        match err {
+            StorageError::NotFound(id) => {
+                ApiError(StatusCode::NOT_FOUND, CommandError::project_not_found(&id))
+            }
+            other => ApiError(StatusCode::INTERNAL_SERVER_ERROR, CommandError::from_error(other)),
+        }
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+#[derive(Clone)]
+struct ApiState {
+    app: AppHandle,
+    token: Arc<str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectStatus {
+    #[serde(flatten)]
+    project: Project,
+    running: bool,
+    process: Option<ProcessInfo>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogQuery {
+    log_type: Option<String>,
+    lines: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogTail {
+    path: String,
+    entries: Vec<LogEntry>,
+}
+
+/// Handle to the running control API server, if any
+pub struct ControlApi {
+    shutdown: Option<oneshot::Sender<()>>,
+    address: Option<String>,
+    socket_path: Option<PathBuf>,
+}
+
+pub type SharedControlApi = Arc<Mutex<ControlApi>>;
+
+pub fn init_control_api() -> SharedControlApi {
+    Arc::new(Mutex::new(ControlApi::new()))
+}
+
+impl ControlApi {
+    pub fn new() -> Self {
+        Self {
+            shutdown: None,
+            address: None,
+            socket_path: None,
+        }
+    }
+
+    /// Address the server is listening on (`http://127.0.0.1:<port>` or `unix:<path>`)
+    pub fn address(&self) -> Option<&str> {
+        self.address.as_deref()
+    }
+
+    /// Start (or restart) the server with the given settings
+    pub async fn start(&mut self, app: AppHandle, config: &ControlApiConfig) -> Result<String, String> {
+        self.stop();
+
+        let state = ApiState {
+            app,
+            token: read_or_create_token()?.into(),
+        };
+        let router = build_router(state);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let shutdown = async {
+            let _ = shutdown_rx.await;
+        };
+
+        let address = match config.transport {
+            ControlApiTransport::Tcp => {
+                let listener = tokio::net::TcpListener::bind(("127.0.0.1", config.port))
+                    .await
+                    .map_err(|e| format!("Failed to bind 127.0.0.1:{}: {}", config.port, e))?;
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = axum::serve(listener, router).with_graceful_shutdown(shutdown).await {
+                        eprintln!("Control API server error: {}", e);
+                    }
+                });
+                format!("http://127.0.0.1:{}", config.port)
+            }
+            #[cfg(unix)]
+            ControlApiTransport::Unix => {
+                use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+                let path = match &config.socket_path {
+                    Some(path) => PathBuf::from(path),
+                    None => Storage::new()
+                        .map_err(|e| e.to_string())?
+                        .control_api_socket_file(),
+                };
+                // A socket left behind by a previous run would make bind fail. Anything
+                // else at the path is likely a mistyped setting, so leave it alone.
+                if let Ok(metadata) = std::fs::symlink_metadata(&path) {
+                    if !metadata.file_type().is_socket() {
+                        return Err(format!(
+                            "{} already exists and is not a socket",
+                            path.display()
+                        ));
+                    }
+                    std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+                }
+                let listener = tokio::net::UnixListener::bind(&path)
+                    .map_err(|e| format!("Failed to bind {}: {}", path.display(), e))?;
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+                    .map_err(|e| e.to_string())?;
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = axum::serve(listener, router).with_graceful_shutdown(shutdown).await {
+                        eprintln!("Control API server error: {}", e);
+                    }
+                });
+                let address = format!("unix:{}", path.display());
+                self.socket_path = Some(path);
+                address
+            }
+            #[cfg(not(unix))]
+            ControlApiTransport::Unix => {
+                return Err("Unix socket transport is not supported on this platform".to_string());
+            }
+        };
+
+        self.shutdown = Some(shutdown_tx);
+        self.address = Some(address.clone());
+        Ok(address)
+    }
+
+    /// Stop the server if it is running
+    pub fn stop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(path) = self.socket_path.take() {
+            let _ = std::fs::remove_file(path);
+        }
+        self.address = None;
+    }
+}
+
+impl Default for ControlApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read the API token, generating one on first use
+pub fn read_or_create_token() -> Result<String, String> {
+    let path = Storage::new().map_err(|e| e.to_string())?.control_api_token_file();
+    if let Ok(token) = std::fs::read_to_string(&path) {
+        let token = token.trim();
+        if !token.is_empty() {
+            return Ok(token.to_string());
+        }
+    }
+    rotate_token()
+}
+
+/// Replace the API token with a fresh random one
+pub fn rotate_token() -> Result<String, String> {
+    let path = Storage::new().map_err(|e| e.to_string())?.control_api_token_file();
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    std::fs::write(&path, &token).map_err(|e| format!("Failed to write API token: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(token)
+}
+
+fn build_router(state: ApiState) -> Router {
+    Router::new()
+        .route("/v1/projects", get(list_projects))
+        .route("/v1/projects/{id}", get(get_project))
+        .route("/v1/projects/{id}/start", post(start_project))
+        .route("/v1/projects/{id}/stop", post(stop_project))
+        .route("/v1/projects/{id}/logs", get(project_logs))
+        .route("/v1/services", get(list_services))
+        .route("/v1/services/{id}/logs", get(service_logs))
+        .route("/v1/ports", get(list_ports))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+/// Reject requests without the right bearer token
+async fn require_token(State(api): State<ApiState>, request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), api.token.as_bytes()) => {
+            next.run(request).await
+        }
+        _ => ApiError(
+            StatusCode::UNAUTHORIZED,
+            CommandError::new(ErrorCode::PermissionDenied, "Missing or invalid API token"),
+        )
+        .into_response(),
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn project_status(api: &ApiState, project: Project) -> ProjectStatus {
+    let app_state = api.app.state::<Arc<Mutex<AppState>>>();
+    let process = app_state.lock().await.running_processes.get(&project.id).cloned();
+    ProjectStatus {
+        project,
+        running: process.is_some(),
+        process,
+    }
+}
+
+async fn list_projects(State(api): State<ApiState>) -> ApiResult<Vec<ProjectStatus>> {
+    let projects = Storage::new()?.load_projects()?;
+    let mut statuses = Vec::with_capacity(projects.len());
+    for project in projects {
+        statuses.push(project_status(&api, project).await);
+    }
+    Ok(Json(statuses))
+}
+
+async fn get_project(State(api): State<ApiState>, Path(id): Path<String>) -> ApiResult<ProjectStatus> {
+    let project = Storage::new()?.get_project(&id)?;
+    Ok(Json(project_status(&api, project).await))
+}
+
+async fn start_project(State(api): State<ApiState>, Path(id): Path<String>) -> ApiResult<ProcessInfo> {
+    // Surface a missing project as 404 rather than a start failure
+    Storage::new()?.get_project(&id)?;
+
+    let state = api.app.state::<Arc<Mutex<AppState>>>();
+    let process_manager = api.app.state::<SharedProcessManager>();
+    launch_project(&id, &state, &process_manager, api.app.clone())
+        .await
+        .map(Json)
+        .map_err(|e| {
+            let status = match e.code {
+                ErrorCode::PortInUse => StatusCode::CONFLICT,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            ApiError(status, e)
+        })
+}
+
+async fn stop_project(State(api): State<ApiState>, Path(id): Path<String>) -> ApiResult<ProjectStatus> {
+    let project = Storage::new()?.get_project(&id)?;
+
+    let state = api.app.state::<Arc<Mutex<AppState>>>();
+    let process_manager = api.app.state::<SharedProcessManager>();
+    halt_project(&id, &state, &process_manager, api.app.clone())
+        .await
+        .map_err(|e| match e {
+            ProcessError::NotFound(_) => ApiError(
+                StatusCode::NOT_FOUND,
+                CommandError::new(ErrorCode::ProcessNotFound, e.to_string()),
+            ),
+            _ => ApiError(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                CommandError::new(ErrorCode::ProcessKillFailed, e.to_string()),
+            ),
+        })?;
+
+    Ok(Json(project_status(&api, project).await))
+}
+
+async fn list_services(State(api): State<ApiState>) -> ApiResult<Vec<Service>> {
+    let service_manager = api.app.state::<Arc<Mutex<ServiceManager>>>();
+    let manager = service_manager.lock().await;
+    let mut services: Vec<Service> = manager.get_services().into_iter().cloned().collect();
+    services.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(Json(services))
+}
+
+async fn list_ports() -> ApiResult<Vec<PortInfo>> {
+    tokio::task::spawn_blocking(PortScanner::scan_ports)
+        .await
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, CommandError::from_error(e)))?
+        .map(Json)
+        .map_err(|e| {
+            ApiError(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                CommandError::new(ErrorCode::PortScanFailed, e.to_string()),
+            )
+        })
+}
+
+/// Log sources and types become path segments, so keep them to plain names
+fn check_segment(value: &str) -> Result<(), ApiError> {
+    if value.is_empty() || value.contains(['/', '\\']) || value.contains("..") {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            CommandError::new(ErrorCode::InvalidInput, format!("Invalid log name: {}", value)),
+        ));
+    }
+    Ok(())
+}
+
+fn read_tail(manager: &LogManager, path: PathBuf, lines: Option<usize>) -> ApiResult<LogTail> {
+    let entries = manager
+        .read_log_entries(&path, lines.unwrap_or(100))
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, CommandError::from(e)))?;
+    Ok(Json(LogTail {
+        path: path.to_string_lossy().to_string(),
+        entries,
+    }))
+}
+
+async fn project_logs(
+    State(api): State<ApiState>,
+    Path(id): Path<String>,
+    Query(query): Query<LogQuery>,
+) -> ApiResult<LogTail> {
+    let project = Storage::new()?.get_project(&id)?;
+    let log_type = query.log_type.as_deref().unwrap_or("stdout");
+    check_segment(&project.name)?;
+    check_segment(log_type)?;
+
+    let log_manager = api.app.state::<Arc<Mutex<LogManager>>>();
+    let manager = log_manager.lock().await;
+    let path = manager.get_project_log_path(&project.name, log_type);
+    read_tail(&manager, path, query.lines)
+}
+
+async fn service_logs(
+    State(api): State<ApiState>,
+    Path(id): Path<String>,
+    Query(query): Query<LogQuery>,
+) -> ApiResult<LogTail> {
+    let log_type = query.log_type.as_deref().unwrap_or("error");
+    check_segment(&id)?;
+    check_segment(log_type)?;
+
+    let log_manager = api.app.state::<Arc<Mutex<LogManager>>>();
+    let manager = log_manager.lock().await;
+    let path = manager.get_log_path(&id, log_type);
+    read_tail(&manager, path, query.lines)
+}
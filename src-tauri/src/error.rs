@@ -55,6 +55,11 @@ pub enum ErrorCode {
     ProjectDetectionFailed,
     ProjectAlreadyExists,
 
+    // Dependency errors
+    DependencyCycle,
+    DependencyNotFound,
+    DependencyFailed,
+
     // Domain/Hosts errors
     DomainInvalid,
     DomainConflict,
@@ -84,6 +89,9 @@ pub struct CommandError {
     pub details: Option<String>,
     /// Whether this error is potentially recoverable by retrying
     pub is_retryable: bool,
+    /// Optional structured payload (e.g. the nodes of a dependency cycle)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
 impl CommandError {
@@ -94,6 +102,7 @@ impl CommandError {
             message: message.into(),
             details: None,
             is_retryable: false,
+            data: None,
         }
     }
 
@@ -104,6 +113,7 @@ impl CommandError {
             message: message.into(),
             details: Some(details.into()),
             is_retryable: false,
+            data: None,
         }
     }
 
@@ -113,6 +123,12 @@ impl CommandError {
         self
     }
 
+    /// Attach a structured payload for the frontend
+    pub fn with_data(mut self, data: impl Serialize) -> Self {
+        self.data = serde_json::to_value(data).ok();
+        self
+    }
+
     /// Create a not found error
     pub fn not_found(resource: &str, id: &str) -> Self {
         Self::new(
@@ -237,4 +253,15 @@ mod tests {
         let err = CommandError::project_not_found("my-project");
         assert!(err.message.contains("my-project"));
     }
+
+    #[test]
+    fn test_error_data() {
+        let err = CommandError::new(ErrorCode::PortInUse, "Port 5173 is in use");
+        let json = serde_json::to_value(&err).unwrap();
+        assert!(json.get("data").is_none());
+
+        let err = err.with_data(serde_json::json!({ "port": 5173 }));
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["data"]["port"], 5173);
+    }
 }
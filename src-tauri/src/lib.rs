@@ -1,4 +1,6 @@
+pub mod cli;
 mod commands;
+mod control_api;
 pub mod error;
 mod models;
 mod services;
@@ -7,8 +9,13 @@ mod tray;
 
 use services::{
     DatabaseManager, LogManager, LogStreamManager, ServiceManager, init_project_watcher,
-    init_bundle_installer, init_download_manager,
+    init_bundle_installer, init_download_manager, init_health_monitor, init_log_alerts, init_process_manager,
+    init_resource_monitor, spawn_exit_monitor, spawn_health_monitor, spawn_native_log_alerts,
+    spawn_native_log_rotation, spawn_resource_monitor,
 };
+use control_api::init_control_api;
+use services::credential_manager::CredentialManager;
+use services::storage::Storage;
 use state::AppState;
 use std::sync::Arc;
 use tauri::Manager;
@@ -21,28 +28,90 @@ pub fn run() {
     let log_manager_instance = LogManager::new();
     let log_stream_manager = Arc::new(RwLock::new(LogStreamManager::new(log_manager_instance.clone())));
     let log_manager = Arc::new(Mutex::new(log_manager_instance));
+    let log_alerts = init_log_alerts(
+        Storage::new()
+            .and_then(|s| s.load_log_alert_rules())
+            .unwrap_or_default(),
+    );
     let database_manager = Arc::new(Mutex::new(DatabaseManager::new()));
     let bundle_installer = init_bundle_installer();
     let download_manager = init_download_manager();
+    let process_manager = init_process_manager();
+    let control_api = init_control_api();
+    let health_monitor = init_health_monitor();
+    let resource_monitor = init_resource_monitor();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_window_state::Builder::new().build())
-        .manage(app_state)
-        .manage(service_manager)
+        .plugin(tauri_plugin_notification::init())
+        .manage(app_state.clone())
+        .manage(service_manager.clone())
         .manage(log_manager)
         .manage(log_stream_manager)
+        .manage(log_alerts.clone())
         .manage(database_manager)
         .manage(bundle_installer)
         .manage(download_manager)
-        .setup(|app| {
+        .manage(process_manager.clone())
+        .manage(control_api.clone())
+        .manage(health_monitor.clone())
+        .manage(resource_monitor.clone())
+        .setup(move |app| {
             tray::setup_tray(app)?;
 
+            // Connect to the keyring and migrate credential files once
+            match CredentialManager::shared() {
+                Ok(credential_manager) => {
+                    app.manage(credential_manager);
+                }
+                Err(e) => eprintln!("Failed to open credential storage: {}", e),
+            }
+
+            // Log alerts raise an event, a notification and a tray badge
+            let alert_app = app.handle().clone();
+            if let Ok(mut engine) = log_alerts.lock() {
+                engine.set_handler(Arc::new(move |alert| tray::notify_log_alert(&alert_app, alert)));
+            }
+
+            // Logs services write themselves are checked as they grow
+            spawn_native_log_alerts(service_manager.clone(), log_alerts.clone());
+
+            // Reap exited project processes and notify the frontend
+            spawn_exit_monitor(app.handle().clone(), process_manager, app_state.clone());
+
+            // Probe running projects and keep their health history
+            spawn_health_monitor(app.handle().clone(), health_monitor, app_state.clone());
+
+            // Keep logs of long-running services within their size limit
+            spawn_native_log_rotation(service_manager.clone());
+
+            // Sample CPU and memory of running projects and services
+            spawn_resource_monitor(
+                app.handle().clone(),
+                resource_monitor,
+                app_state,
+                service_manager,
+            );
+
             // Initialize project watcher for auto-detection
             let project_watcher = init_project_watcher(app.handle());
             app.manage(project_watcher);
 
+            // Start the local control API if the user opted in
+            let config = Storage::new()
+                .and_then(|s| s.load_control_api_config())
+                .unwrap_or_default();
+            if config.enabled {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = control_api.lock().await.start(app_handle, &config).await {
+                        eprintln!("Failed to start control API: {}", e);
+                    }
+                });
+            }
+
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -64,14 +133,38 @@ pub fn run() {
             commands::process::stop_project,
             commands::process::restart_project,
             commands::process::get_running_processes,
+            commands::process::resolve_port_conflict,
+            // Dependency commands
+            commands::dependency::get_start_order,
+            commands::dependency::start_project_group,
+            commands::dependency::stop_project_group,
+            commands::workspace::get_workspaces,
+            commands::workspace::create_workspace,
+            commands::workspace::update_workspace,
+            commands::workspace::delete_workspace,
+            commands::workspace::start_workspace,
+            commands::workspace::stop_workspace,
+            // Control API commands
+            commands::control_api::get_control_api_status,
+            commands::control_api::set_control_api_config,
+            commands::control_api::get_control_api_token,
+            commands::control_api::rotate_control_api_token,
             // Port commands
             commands::port::scan_ports,
             commands::port::check_port_available,
             commands::port::suggest_available_port,
+            commands::port::get_port_reservations,
+            commands::port::reserve_port,
+            commands::port::release_port,
             commands::port::get_process_details,
             commands::port::kill_process_by_pid,
             // Health commands
             commands::health::check_health,
+            commands::health::get_health_history,
+            commands::health::get_health_summary,
+            commands::health::get_health_summaries,
+            commands::resource::get_resource_usage,
+            commands::resource::get_resource_history,
             // Service commands
             commands::service::get_services,
             commands::service::get_service,
@@ -82,12 +175,22 @@ pub fn run() {
             commands::service::get_all_service_statuses,
             commands::service::set_service_auto_start,
             commands::service::set_service_auto_restart,
+            commands::service::set_service_stop_strategy,
             commands::service::get_service_config,
             commands::service::save_service_config,
             commands::service::get_service_config_list,
             // Log commands
             commands::log::get_service_logs,
             commands::log::get_project_logs,
+            commands::log::search_logs,
+            commands::log::get_log_disk_usage,
+            commands::log::start_log_tail,
+            commands::log::ack_log_tail,
+            commands::log::stop_log_tail,
+            commands::log::get_log_alert_rules,
+            commands::log::set_log_alert_rules,
+            commands::log::get_unread_log_alerts,
+            commands::log::clear_log_alerts,
             commands::log::clear_service_logs,
             commands::log::clear_project_logs,
             commands::log::cleanup_old_logs,
@@ -203,6 +306,15 @@ pub fn run() {
             commands::credentials::delete_db_credential,
             commands::credentials::has_db_credential,
             commands::credentials::list_db_credentials,
+            commands::vault::list_vault_entries,
+            commands::vault::set_vault_entry,
+            commands::vault::rotate_vault_entry,
+            commands::vault::delete_vault_entry,
+            commands::redis::get_redis_config,
+            commands::redis::update_redis_config,
+            commands::redis::list_redis_allocations,
+            commands::redis::allocate_redis_database,
+            commands::redis::release_redis_database,
             // Updater commands
             commands::updater::check_for_updates,
             commands::updater::get_current_version,
@@ -158,6 +158,24 @@ impl BundleManifest {
             resolve_strategy: Some(ResolveStrategy::MariaDbArchive),
         });
 
+        components.insert("postgresql".to_string(), BundleComponent {
+            id: "postgresql".to_string(),
+            name: "PostgreSQL".to_string(),
+            category: ComponentCategory::Database,
+            version: "17.2".to_string(),
+            file_name: Some("postgresql-17.2-1-windows-x64-binaries.zip".to_string()),
+            download_url: Some("https://get.enterprisedb.com/postgresql/postgresql-17.2-1-windows-x64-binaries.zip".to_string()),
+            size_bytes: 330_000_000, // ~330MB
+            sha256: None,
+            install_path: "runtime/postgresql".to_string(),
+            executable_path: Some("pgsql/bin/postgres.exe".to_string()),
+            post_install: vec![PostInstallAction::InitDatabase, PostInstallAction::SetupService],
+            dependencies: vec![],
+            description: "확장성 높은 오픈소스 관계형 데이터베이스".to_string(),
+            icon: Some("database".to_string()),
+            resolve_strategy: None,
+        });
+
         // Runtimes
         // ID를 inventory_scanner와 일치시킴: "node"
         components.insert("node".to_string(), BundleComponent {
@@ -321,6 +339,7 @@ impl BundleManifest {
                 "apache".to_string(),
                 "php".to_string(),
                 "mariadb".to_string(),
+                "postgresql".to_string(),
                 "phpmyadmin".to_string(),
                 "composer".to_string(),
                 "git".to_string(),
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Default loopback port of the local control API
+pub const DEFAULT_CONTROL_API_PORT: u16 = 47800;
+
+/// Where the local control API listens
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ControlApiTransport {
+    /// HTTP on 127.0.0.1
+    #[default]
+    Tcp,
+    /// HTTP over a Unix domain socket (not available on Windows)
+    Unix,
+}
+
+/// Settings of the opt-in local control API used by editors, git hooks and scripts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ControlApiConfig {
+    pub enabled: bool,
+    pub transport: ControlApiTransport,
+    pub port: u16,
+    /// Socket path for the Unix transport; defaults to `control.sock` in the app data directory
+    pub socket_path: Option<String>,
+}
+
+impl Default for ControlApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            transport: ControlApiTransport::Tcp,
+            port: DEFAULT_CONTROL_API_PORT,
+            socket_path: None,
+        }
+    }
+}
@@ -1,18 +1,26 @@
 pub mod bundle;
+pub mod control_api;
 pub mod inventory;
 pub mod port_info;
+pub mod port_reservation;
 pub mod process_info;
 pub mod project;
 pub mod service;
+pub mod stop_strategy;
+pub mod workspace;
 
 pub use bundle::{
     BundleComponent, BundleManifest, ComponentCategory, InstallOptions, InstallPhase,
     InstallPreset, InstallProgress, InstallationState, InstalledComponent, PostInstallAction,
     ResolveStrategy,
 };
+pub use control_api::{ControlApiConfig, ControlApiTransport};
 pub use port_info::PortInfo;
+pub use port_reservation::{PortOwnerKind, PortReservation};
 pub use process_info::ProcessInfo;
-pub use project::{Project, ProjectType};
+pub use project::{Project, ProjectType, RestartPolicy};
 pub use service::{
     ConfigFile, HealthCheckConfig, HealthCheckType, LogConfig, Service, ServiceStatus, ServiceType,
 };
+pub use stop_strategy::{StopOutcome, StopSignal, StopStep, StopStrategy};
+pub use workspace::Workspace;
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// What a reserved port belongs to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PortOwnerKind {
+    Project,
+    Service,
+    /// An Apache `Listen` port or VirtualHost
+    Apache,
+    /// Reserved by hand for something DevPort doesn't manage
+    Manual,
+}
+
+/// A port claimed by a project, service or Apache config, whether or not
+/// anything is listening on it right now
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortReservation {
+    pub port: u16,
+    pub kind: PortOwnerKind,
+    pub owner_id: String,
+    pub owner_name: String,
+}
+
+impl PortReservation {
+    pub fn new(port: u16, kind: PortOwnerKind, owner_id: impl Into<String>, owner_name: impl Into<String>) -> Self {
+        Self {
+            port,
+            kind,
+            owner_id: owner_id.into(),
+            owner_name: owner_name.into(),
+        }
+    }
+
+    /// Human-readable owner, e.g. "project 'web'"
+    pub fn describe(&self) -> String {
+        let kind = match self.kind {
+            PortOwnerKind::Project => "project",
+            PortOwnerKind::Service => "service",
+            PortOwnerKind::Apache => "Apache",
+            PortOwnerKind::Manual => "manual reservation",
+        };
+        format!("{} '{}'", kind, self.owner_name)
+    }
+}
@@ -1,3 +1,5 @@
+use super::service::{HealthCheckConfig, LogConfig};
+use super::stop_strategy::StopStrategy;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -35,6 +37,65 @@ fn default_launch_mode() -> String {
     "web".to_string()
 }
 
+fn default_stop_timeout() -> u64 {
+    5000
+}
+
+/// When a project process should be restarted after it exits on its own
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartMode {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+/// Per-project auto-restart policy with exponential backoff and a crash-loop cutoff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RestartPolicy {
+    pub mode: RestartMode,
+    /// Delay before the first restart (ms); doubled on each consecutive restart
+    pub restart_delay: u64,
+    /// Upper bound for the backoff delay (ms)
+    pub max_delay: u64,
+    /// Consecutive restarts allowed before the project is marked as errored
+    pub max_restarts: u32,
+    /// Uptime (ms) after which the process is considered stable and the counter resets
+    pub reset_after: u64,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            mode: RestartMode::Never,
+            restart_delay: 1000,
+            max_delay: 30000,
+            max_restarts: 5,
+            reset_after: 60000,
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Whether an exit with the given success flag should trigger a restart
+    pub fn should_restart(&self, success: bool) -> bool {
+        match self.mode {
+            RestartMode::Never => false,
+            RestartMode::OnFailure => !success,
+            RestartMode::Always => true,
+        }
+    }
+
+    /// Backoff delay (ms) before the given restart attempt (0-based)
+    pub fn backoff_delay(&self, attempt: u32) -> u64 {
+        self.restart_delay
+            .saturating_mul(2u64.saturating_pow(attempt))
+            .min(self.max_delay)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Project {
@@ -48,11 +109,25 @@ pub struct Project {
     pub auto_start: bool,
     pub health_check_url: Option<String>,
     #[serde(default)]
+    pub health_check: Option<HealthCheckConfig>,  // Overrides health_check_url when set
+    #[serde(default)]
     pub domain: Option<String>,  // Custom domain for hosts file (e.g., "my-app.test")
     #[serde(default)]
     pub github_url: Option<String>,  // GitHub repository URL
     #[serde(default = "default_launch_mode")]
     pub launch_mode: String,  // "web" or "app"
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+    #[serde(default)]
+    pub depends_on: Vec<String>,  // IDs of projects or services that must be up first
+    #[serde(default)]
+    pub persist_detected_port: bool,  // Save the port the dev server reports when it differs from `port`
+    #[serde(default = "default_stop_timeout")]
+    pub stop_timeout: u64,  // Time (ms) each graceful stop step gets before the next
+    #[serde(default)]
+    pub stop_strategy: StopStrategy,  // Stop command / signal / force kill
+    #[serde(default)]
+    pub log_config: LogConfig,  // Size, count and retention of the project's stdout/stderr logs
     pub created_at: String,
     pub updated_at: String,
 }
@@ -76,11 +151,46 @@ impl Project {
             env_vars: std::collections::HashMap::new(),
             auto_start: false,
             health_check_url: None,
+            health_check: None,
             domain: None,
             github_url: None,
             launch_mode: "web".to_string(),
+            restart_policy: RestartPolicy::default(),
+            depends_on: Vec::new(),
+            persist_detected_port: false,
+            stop_timeout: default_stop_timeout(),
+            stop_strategy: StopStrategy::default(),
+            log_config: LogConfig::default(),
             created_at: now.clone(),
             updated_at: now,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_policy_modes() {
+        let mut policy = RestartPolicy::default();
+        assert!(!policy.should_restart(false));
+
+        policy.mode = RestartMode::OnFailure;
+        assert!(policy.should_restart(false));
+        assert!(!policy.should_restart(true));
+
+        policy.mode = RestartMode::Always;
+        assert!(policy.should_restart(true));
+    }
+
+    #[test]
+    fn test_restart_policy_backoff() {
+        let policy = RestartPolicy::default();
+        assert_eq!(policy.backoff_delay(0), 1000);
+        assert_eq!(policy.backoff_delay(1), 2000);
+        assert_eq!(policy.backoff_delay(3), 8000);
+        assert_eq!(policy.backoff_delay(10), 30000);
+        assert_eq!(policy.backoff_delay(100), 30000);
+    }
+}
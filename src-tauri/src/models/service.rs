@@ -1,6 +1,7 @@
+use super::stop_strategy::{StopStep, StopStrategy};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -41,8 +42,11 @@ pub enum HealthCheckType {
     Http,
     Tcp,
     Process,
+    /// Run `command`; exit code 0 means healthy
+    Command,
 }
 
+/// Health probe shared by services and projects
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HealthCheckConfig {
@@ -51,6 +55,36 @@ pub struct HealthCheckConfig {
     pub interval: u64,
     pub timeout: u64,
     pub retries: u32,
+    /// Accepted HTTP status codes, e.g. "200-299,302" (default: any 2xx or 3xx)
+    #[serde(default)]
+    pub expected_status: Option<String>,
+    /// The response body must contain this text
+    #[serde(default)]
+    pub body_contains: Option<String>,
+    /// The response body must match this regex
+    #[serde(default)]
+    pub body_regex: Option<String>,
+    /// JSON pointer into the response body, e.g. "/status"
+    #[serde(default)]
+    pub json_pointer: Option<String>,
+    /// Value expected at `json_pointer`; if unset the pointer only has to exist
+    #[serde(default)]
+    pub json_value: Option<serde_json::Value>,
+    /// Extra request headers, e.g. an auth token
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Accept self-signed certificates on local HTTPS endpoints
+    #[serde(default)]
+    pub insecure_tls: bool,
+    /// Shell command for `HealthCheckType::Command`
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Sent by a TCP probe once connected, e.g. a Redis `PING`
+    #[serde(default)]
+    pub tcp_send: Option<String>,
+    /// Required start of the TCP probe's reply line, e.g. `+PONG`
+    #[serde(default)]
+    pub tcp_expect: Option<String>,
 }
 
 impl Default for HealthCheckConfig {
@@ -61,19 +95,32 @@ impl Default for HealthCheckConfig {
             interval: 5000,
             timeout: 2000,
             retries: 2,
+            expected_status: None,
+            body_contains: None,
+            body_regex: None,
+            json_pointer: None,
+            json_value: None,
+            headers: HashMap::new(),
+            insecure_tls: false,
+            command: None,
+            tcp_send: None,
+            tcp_expect: None,
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct LogConfig {
     pub stdout_path: String,
     pub stderr_path: String,
     pub max_size: String,
     pub max_files: u32,
+    /// "size", "daily" or "hourly"; the size limit applies with every policy
     pub rotation_policy: String,
     pub retention_days: u32,
+    /// Gzip rotated files
+    pub compress: bool,
 }
 
 impl Default for LogConfig {
@@ -85,10 +132,22 @@ impl Default for LogConfig {
             max_files: 5,
             rotation_policy: "size".to_string(),
             retention_days: 30,
+            compress: true,
         }
     }
 }
 
+impl LogConfig {
+    /// Log files the service writes itself, e.g. Apache's access and error logs
+    pub fn native_paths(&self) -> Vec<PathBuf> {
+        [&self.stdout_path, &self.stderr_path]
+            .into_iter()
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Service {
@@ -116,6 +175,24 @@ pub struct Service {
     pub error_message: Option<String>,
     pub installed: bool,
     pub config_files: Vec<ConfigFile>,
+    #[serde(default)]
+    pub stop_strategy: StopStrategy,
+    /// Time (ms) each graceful stop step gets before the next
+    #[serde(default = "default_stop_timeout")]
+    pub stop_timeout: u64,
+    /// Which stop step ended the last run
+    #[serde(default)]
+    pub stopped_by: Option<StopStep>,
+    /// CPU percent of the process tree, filled by the resource monitor
+    #[serde(default)]
+    pub cpu_usage: Option<f32>,
+    /// Resident memory of the process tree in bytes
+    #[serde(default)]
+    pub memory_usage: Option<u64>,
+}
+
+fn default_stop_timeout() -> u64 {
+    5000
 }
 
 impl Service {
@@ -145,6 +222,11 @@ impl Service {
             error_message: None,
             installed: false,
             config_files: Vec::new(),
+            stop_strategy: StopStrategy::default(),
+            stop_timeout: default_stop_timeout(),
+            stopped_by: None,
+            cpu_usage: None,
+            memory_usage: None,
         }
     }
 
@@ -237,6 +319,7 @@ impl Service {
                 interval: 5000,
                 timeout: 2000,
                 retries: 2,
+                ..Default::default()
             };
             service.log_config = LogConfig {
                 stdout_path: format!("{}\\logs\\access.log", base_path),
@@ -266,6 +349,7 @@ impl Service {
                 interval: 5000,
                 timeout: 2000,
                 retries: 2,
+                ..Default::default()
             };
         }
 
@@ -295,12 +379,26 @@ impl Service {
 
             service.work_dir = base_path.clone();
             service.port = 3306;
+
+            // Let mysqld flush and close its tables instead of being killed mid-write
+            // The root login comes from an option file written just before stopping
+            let mysqladmin = format!("{}\\bin\\mysqladmin.exe", base_path);
+            if Path::new(&mysqladmin).exists() {
+                service.stop_strategy.command = Some(format!(
+                    "\"{}\" --defaults-extra-file=\"{}\" --port={} shutdown",
+                    mysqladmin,
+                    Self::mariadb_client_config_path().display(),
+                    service.port
+                ));
+                service.stop_timeout = 15000;
+            }
             service.health_check = HealthCheckConfig {
                 check_type: HealthCheckType::Tcp,
                 endpoint: Some("localhost:3306".to_string()),
                 interval: 5000,
                 timeout: 2000,
                 retries: 2,
+                ..Default::default()
             };
             service.log_config = LogConfig {
                 stdout_path: format!("{}\\data\\mysql.log", base_path),
@@ -337,6 +435,7 @@ impl Service {
                 interval: 5000,
                 timeout: 2000,
                 retries: 2,
+                ..Default::default()
             };
         }
 
@@ -344,6 +443,235 @@ impl Service {
         service
     }
 
+    /// Option file with the root login `mysqladmin shutdown` reads; it only
+    /// exists while MariaDB is being stopped
+    pub fn mariadb_client_config_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("clickdevport")
+            .join("mariadb-client.cnf")
+    }
+
+    /// Directory holding the MySQL/MariaDB client tools (mysqldump, mysql):
+    /// the detected server install or a system package
+    pub fn find_mariadb_bin_dir() -> Option<PathBuf> {
+        let mut candidates: Vec<PathBuf> = Self::find_mysql_path()
+            .map(|(base_path, _, _)| PathBuf::from(base_path).join("bin"))
+            .into_iter()
+            .collect();
+        candidates.extend(
+            ["/opt/homebrew/bin", "/usr/local/mysql/bin", "/usr/local/bin", "/usr/bin"].map(PathBuf::from),
+        );
+        candidates
+            .into_iter()
+            .find(|dir| dir.join(format!("mysqldump{}", std::env::consts::EXE_SUFFIX)).exists())
+    }
+
+    /// Directory holding the PostgreSQL server and client binaries:
+    /// DevPort's runtime, the EDB installer or a system package
+    pub fn find_postgresql_bin_dir() -> Option<PathBuf> {
+        let mut candidates = vec![PathBuf::from("C:\\DevPort\\runtime\\postgresql\\pgsql\\bin")];
+        for version in ["17", "16", "15", "14", "13"] {
+            candidates.push(PathBuf::from(format!("C:\\Program Files\\PostgreSQL\\{}\\bin", version)));
+            candidates.push(PathBuf::from(format!("/usr/lib/postgresql/{}/bin", version)));
+        }
+        candidates.extend(
+            ["/opt/homebrew/bin", "/usr/local/pgsql/bin", "/usr/local/bin", "/usr/bin"]
+                .map(PathBuf::from),
+        );
+        candidates
+            .into_iter()
+            .find(|dir| dir.join(format!("postgres{}", std::env::consts::EXE_SUFFIX)).exists())
+    }
+
+    /// Cluster DevPort initializes and runs, kept with the app data so it
+    /// survives reinstalling the binaries
+    pub fn postgresql_data_dir() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("clickdevport")
+            .join("postgresql")
+            .join("data")
+    }
+
+    pub fn postgresql() -> Self {
+        let mut service = Self::new(
+            "postgresql".to_string(),
+            "PostgreSQL".to_string(),
+            ServiceType::Database,
+        );
+
+        let bin_dir = Self::find_postgresql_bin_dir()
+            .unwrap_or_else(|| PathBuf::from("C:\\DevPort\\runtime\\postgresql\\pgsql\\bin"));
+        let tool = |name: &str| {
+            bin_dir
+                .join(format!("{}{}", name, std::env::consts::EXE_SUFFIX))
+                .to_string_lossy()
+                .to_string()
+        };
+        let data_dir = Self::postgresql_data_dir().to_string_lossy().to_string();
+
+        service.executable = tool("postgres");
+        service.port = 5432;
+        // Local connections only
+        service.args = vec![
+            "-D".to_string(),
+            data_dir.clone(),
+            "-p".to_string(),
+            service.port.to_string(),
+            "-h".to_string(),
+            "127.0.0.1".to_string(),
+        ];
+        // The default socket directory usually belongs to the system's postgres user
+        #[cfg(unix)]
+        service.args.extend(["-k".to_string(), data_dir.clone()]);
+        service.work_dir = bin_dir
+            .parent()
+            .unwrap_or(&bin_dir)
+            .to_string_lossy()
+            .to_string();
+
+        // A fast shutdown rolls back open transactions and checkpoints cleanly
+        service.stop_strategy.command = Some(format!(
+            "\"{}\" stop -D \"{}\" -m fast",
+            tool("pg_ctl"),
+            data_dir
+        ));
+        service.stop_timeout = 15000;
+
+        // pg_isready also tells a server still starting up or shutting down from a
+        // ready one; without it an open port has to do
+        let pg_isready = tool("pg_isready");
+        service.health_check = if Path::new(&pg_isready).exists() {
+            HealthCheckConfig {
+                check_type: HealthCheckType::Command,
+                command: Some(format!(
+                    "\"{}\" -h 127.0.0.1 -p {} -t 2",
+                    pg_isready, service.port
+                )),
+                interval: 5000,
+                timeout: 3000,
+                retries: 2,
+                ..Default::default()
+            }
+        } else {
+            HealthCheckConfig {
+                check_type: HealthCheckType::Tcp,
+                endpoint: Some(format!("localhost:{}", service.port)),
+                interval: 5000,
+                timeout: 2000,
+                retries: 2,
+                ..Default::default()
+            }
+        };
+
+        let data_path = Self::postgresql_data_dir();
+        service.config_files = vec![
+            ConfigFile {
+                name: "postgresql.conf".to_string(),
+                path: data_path.join("postgresql.conf").to_string_lossy().to_string(),
+                description: "PostgreSQL 메인 설정".to_string(),
+            },
+            ConfigFile {
+                name: "pg_hba.conf".to_string(),
+                path: data_path.join("pg_hba.conf").to_string_lossy().to_string(),
+                description: "클라이언트 인증 설정".to_string(),
+            },
+        ];
+
+        service.check_installed();
+        service
+    }
+
+    /// A Redis-compatible server: Redis, Valkey or Memurai
+    fn find_redis_path() -> Option<String> {
+        let possible_paths = [
+            // DevPort 커스텀 경로
+            "C:\\DevPort\\runtime\\redis\\redis-server.exe",
+            // Redis for Windows (tporadowski) MSI
+            "C:\\Program Files\\Redis\\redis-server.exe",
+            // Memurai
+            "C:\\Program Files\\Memurai\\memurai.exe",
+            // Laragon
+            "C:\\laragon\\bin\\redis\\redis-x64-5.0.14.1\\redis-server.exe",
+            "/opt/homebrew/bin/redis-server",
+            "/opt/homebrew/bin/valkey-server",
+            "/usr/local/bin/redis-server",
+            "/usr/local/bin/valkey-server",
+            "/usr/bin/redis-server",
+            "/usr/bin/valkey-server",
+        ];
+        possible_paths
+            .into_iter()
+            .find(|path| Path::new(path).exists())
+            .map(str::to_string)
+    }
+
+    /// Snapshots and the generated config, kept with the app data
+    pub fn redis_data_dir() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("clickdevport")
+            .join("redis")
+    }
+
+    pub fn redis_config_path() -> PathBuf {
+        Self::redis_data_dir().join("redis.conf")
+    }
+
+    /// Redis on `port`, as set in the generated `redis.conf`
+    pub fn redis(port: u16) -> Self {
+        let mut service = Self::new(
+            "redis".to_string(),
+            "Redis".to_string(),
+            ServiceType::Database,
+        );
+
+        service.executable = Self::find_redis_path()
+            .unwrap_or_else(|| "C:\\DevPort\\runtime\\redis\\redis-server.exe".to_string());
+        let config_path = Self::redis_config_path().to_string_lossy().to_string();
+        service.args = vec![config_path.clone()];
+        service.work_dir = Self::redis_data_dir().to_string_lossy().to_string();
+        service.port = port;
+
+        // SHUTDOWN saves a snapshot first; a terminate signal does the same on Unix,
+        // but there is no signal to send on Windows
+        let exe_dir = Path::new(&service.executable).parent().unwrap_or(Path::new(""));
+        let cli = ["redis-cli", "valkey-cli", "memurai-cli"]
+            .iter()
+            .map(|name| exe_dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX)))
+            .find(|path| path.exists());
+        if let Some(cli) = cli {
+            service.stop_strategy.command = Some(format!(
+                "\"{}\" -p {} shutdown",
+                cli.display(),
+                port
+            ));
+            service.stop_timeout = 10000;
+        }
+
+        // An open port isn't enough: a server still loading its dataset rejects commands
+        service.health_check = HealthCheckConfig {
+            check_type: HealthCheckType::Tcp,
+            endpoint: Some(format!("127.0.0.1:{}", port)),
+            tcp_send: Some("PING\r\n".to_string()),
+            tcp_expect: Some("+PONG".to_string()),
+            interval: 5000,
+            timeout: 2000,
+            retries: 2,
+            ..Default::default()
+        };
+
+        service.config_files = vec![ConfigFile {
+            name: "redis.conf".to_string(),
+            path: config_path,
+            description: "Redis 설정 (포트, 메모리 한도, 저장 위치)".to_string(),
+        }];
+
+        service.check_installed();
+        service
+    }
+
     pub fn is_running(&self) -> bool {
         matches!(self.status, ServiceStatus::Running)
     }
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+/// Graceful request sent to a process before it is force-killed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StopSignal {
+    /// SIGTERM on Unix; `taskkill /T` without `/F` on Windows, which console
+    /// processes ignore
+    #[cfg_attr(not(windows), default)]
+    Terminate,
+    /// SIGINT on Unix, like pressing Ctrl-C in the terminal
+    Interrupt,
+    /// CTRL_BREAK_EVENT to the process group on Windows; SIGINT elsewhere.
+    /// The default on Windows.
+    #[cfg_attr(windows, default)]
+    CtrlBreak,
+    /// Don't signal; rely on the stop command and force kill
+    None,
+}
+
+/// How a project or service is asked to shut down.
+/// Steps run in order - stop command, signal, force kill - and each graceful
+/// step gets the owner's `stop_timeout` to take effect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StopStrategy {
+    pub signal: StopSignal,
+    /// Shell command run first, e.g. `mysqladmin -u root shutdown`
+    pub command: Option<String>,
+    /// Kill the whole process tree if it is still running after the graceful steps
+    pub force_kill: bool,
+}
+
+impl Default for StopStrategy {
+    fn default() -> Self {
+        Self {
+            signal: StopSignal::default(),
+            command: None,
+            force_kill: true,
+        }
+    }
+}
+
+/// Which step of a stop strategy actually ended the process
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StopStep {
+    /// The process was already gone
+    AlreadyExited,
+    Command,
+    Signal,
+    ForceKill,
+}
+
+/// Result of stopping a process
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopOutcome {
+    pub stopped_by: StopStep,
+    pub exit_code: Option<i32>,
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// A named set of projects and services that are started and stopped together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub project_ids: Vec<String>,
+    #[serde(default)]
+    pub service_ids: Vec<String>,
+    /// Preferred start order (project or service IDs). Dependencies are still
+    /// started first; members not listed here follow in declaration order.
+    #[serde(default)]
+    pub start_order: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Workspace {
+    pub fn new(name: String, project_ids: Vec<String>, service_ids: Vec<String>) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            project_ids,
+            service_ids,
+            start_order: Vec::new(),
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    /// All member IDs, honoring `start_order` first
+    pub fn ordered_members(&self) -> Vec<String> {
+        let mut members: Vec<String> = self
+            .start_order
+            .iter()
+            .filter(|id| self.project_ids.contains(id) || self.service_ids.contains(id))
+            .cloned()
+            .collect();
+
+        for id in self.service_ids.iter().chain(self.project_ids.iter()) {
+            if !members.contains(id) {
+                members.push(id.clone());
+            }
+        }
+
+        members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ordered_members() {
+        let mut workspace = Workspace::new(
+            "client-a".to_string(),
+            vec!["api".to_string(), "web".to_string()],
+            vec!["mariadb".to_string()],
+        );
+        assert_eq!(workspace.ordered_members(), vec!["mariadb", "api", "web"]);
+
+        workspace.start_order = vec!["web".to_string(), "unknown".to_string()];
+        assert_eq!(workspace.ordered_members(), vec!["web", "mariadb", "api"]);
+    }
+}
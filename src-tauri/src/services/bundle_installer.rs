@@ -1,8 +1,9 @@
 use crate::models::{
     BundleComponent, BundleManifest, InstallOptions, InstallPhase, InstallProgress,
-    InstallationState, InstalledComponent, PostInstallAction,
+    InstallationState, InstalledComponent, PostInstallAction, Service,
 };
 use crate::services::bundler::{DEVPORT_BASE_PATH, RUNTIME_BASE_PATH, TOOLS_BASE_PATH};
+use crate::services::postgres_backend::init_cluster;
 use std::fs::{self, File};
 use std::io::{BufReader, Write};
 use std::path::{Path, PathBuf};
@@ -522,6 +523,17 @@ impl BundleInstaller {
                         fs::create_dir_all(&data_dir)
                             .map_err(|e| format!("Failed to create data directory: {}", e))?;
                     }
+                } else if component.id == "postgresql" {
+                    if let Some(bin_dir) = component
+                        .executable_path
+                        .as_ref()
+                        .and_then(|exe| install_path.join(exe).parent().map(|p| p.to_path_buf()))
+                    {
+                        let data_dir = Service::postgresql_data_dir();
+                        if init_cluster(&bin_dir, &data_dir)? {
+                            Self::emit_log(app_handle, "info", &format!("PostgreSQL 클러스터 초기화: {:?}", data_dir));
+                        }
+                    }
                 }
                 Ok(())
             }
@@ -1,11 +1,24 @@
-//! Windows DPAPI-based credential encryption manager
+//! Credential encryption manager
 //!
-//! This module provides secure credential storage using Windows Data Protection API (DPAPI).
-//! DPAPI encrypts data using the user's Windows account credentials, making it secure
-//! and tied to the user's login session.
+//! On Windows credentials are encrypted with the Data Protection API (DPAPI), which ties
+//! them to the user's Windows account. On Linux they go to the freedesktop Secret Service
+//! when a keyring daemon is running. Otherwise (and on other Unix systems) they are
+//! AES-256-GCM encrypted files keyed by a machine-local secret; such files move into
+//! the Secret Service once it becomes available.
 
 use std::fs;
+use std::io::Write;
 use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+#[cfg(target_os = "linux")]
+use crate::services::linux_secret_store::SecretStore;
+#[cfg(unix)]
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+#[cfg(unix)]
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+#[cfg(unix)]
+use sha2::{Digest, Sha256};
 
 #[cfg(windows)]
 use windows::Win32::Foundation::LocalFree;
@@ -14,24 +27,94 @@ use windows::Win32::Security::Cryptography::{
     CryptProtectData, CryptUnprotectData, CRYPT_INTEGER_BLOB, CRYPTPROTECT_UI_FORBIDDEN,
 };
 
-/// CredentialManager handles secure storage and retrieval of credentials using Windows DPAPI.
+/// Header of credential files encrypted with the machine-local key
+#[cfg(unix)]
+const FILE_FORMAT_MAGIC: &[u8] = b"DPC1";
+/// Random secret the file encryption key is derived from
+#[cfg(unix)]
+const MACHINE_KEY_FILE: &str = ".machine.key";
+
+/// Key of the MariaDB/MySQL root password
+pub const MARIADB_ROOT_KEY: &str = "mariadb_root";
+/// Key of the superuser password of DevPort's PostgreSQL cluster
+pub const POSTGRES_SUPERUSER_KEY: &str = "postgresql_superuser";
+
+/// Manager shared by the app, its commands and the CLI
+pub type SharedCredentialManager = Arc<CredentialManager>;
+
+static SHARED: OnceLock<SharedCredentialManager> = OnceLock::new();
+
+/// CredentialManager handles secure storage and retrieval of credentials.
 pub struct CredentialManager {
     credentials_dir: PathBuf,
+    /// Secret Service connection, when a keyring daemon is running
+    #[cfg(target_os = "linux")]
+    secret_store: Option<SecretStore>,
 }
 
 impl CredentialManager {
     /// Creates a new CredentialManager instance.
-    /// The credentials are stored in %APPDATA%/clickdevport/credentials/
+    /// Credential files are stored in %APPDATA%/clickdevport/credentials/
+    /// (~/.local/share/clickdevport/credentials/ on Linux).
     pub fn new() -> Result<Self, String> {
         let credentials_dir = dirs::data_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("clickdevport")
             .join("credentials");
 
+        #[allow(unused_mut)]
+        let mut manager = Self::with_directory(credentials_dir)?;
+
+        #[cfg(target_os = "linux")]
+        {
+            manager.secret_store = SecretStore::connect();
+            if let Err(e) = manager.migrate_to_secret_store() {
+                eprintln!("Failed to move credentials into the keyring: {}", e);
+            }
+        }
+
+        Ok(manager)
+    }
+
+    /// The process-wide manager, created on first use. Connecting to the Secret Service
+    /// and migrating credential files happen only then.
+    pub fn shared() -> Result<SharedCredentialManager, String> {
+        if let Some(manager) = SHARED.get() {
+            return Ok(manager.clone());
+        }
+        let manager = Arc::new(Self::new()?);
+        Ok(SHARED.get_or_init(|| manager).clone())
+    }
+
+    /// CredentialManager keeping credential files in `credentials_dir`, without the Secret Service
+    pub fn with_directory(credentials_dir: PathBuf) -> Result<Self, String> {
         fs::create_dir_all(&credentials_dir)
             .map_err(|e| format!("Failed to create credentials directory: {}", e))?;
 
-        Ok(Self { credentials_dir })
+        Ok(Self {
+            credentials_dir,
+            #[cfg(target_os = "linux")]
+            secret_store: None,
+        })
+    }
+
+    /// Move credential files into the Secret Service, e.g. ones saved before
+    /// a keyring daemon was installed
+    #[cfg(target_os = "linux")]
+    fn migrate_to_secret_store(&self) -> Result<(), String> {
+        let Some(store) = &self.secret_store else {
+            return Ok(());
+        };
+        for key in self.list_credential_files()? {
+            let path = self.get_credential_path(&key);
+            let encrypted = fs::read(&path)
+                .map_err(|e| format!("Failed to read credential '{}': {}", key, e))?;
+            let value = self.decrypt_credential(&encrypted)?;
+            store.save(&key, &value)?;
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove migrated credential '{}': {}", key, e))?;
+        }
+        Ok(())
     }
 
     /// Encrypts credential data using Windows DPAPI.
@@ -131,33 +214,148 @@ impl CredentialManager {
         }
     }
 
-    /// Non-Windows stub for encrypt_credential
-    #[cfg(not(windows))]
+    /// Encrypts credential data with AES-256-GCM under the machine-local key.
+    /// The encrypted data can only be decrypted on this machine by this user.
+    #[cfg(unix)]
+    pub fn encrypt_credential(&self, data: &str) -> Result<Vec<u8>, String> {
+        let cipher = Aes256Gcm::new(&self.file_key()?);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, data.as_bytes())
+            .map_err(|e| format!("Credential encryption failed: {}", e))?;
+
+        let mut encrypted = Vec::with_capacity(FILE_FORMAT_MAGIC.len() + nonce.len() + ciphertext.len());
+        encrypted.extend_from_slice(FILE_FORMAT_MAGIC);
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+        Ok(encrypted)
+    }
+
+    /// Decrypts credential data written by `encrypt_credential`.
+    #[cfg(unix)]
+    pub fn decrypt_credential(&self, encrypted: &[u8]) -> Result<String, String> {
+        let body = encrypted
+            .strip_prefix(FILE_FORMAT_MAGIC)
+            .filter(|body| body.len() > 12)
+            .ok_or("Unrecognized credential data")?;
+        let (nonce, ciphertext) = body.split_at(12);
+
+        let cipher = Aes256Gcm::new(&self.file_key()?);
+        let decrypted = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Credential decryption failed (was it saved on another machine?)".to_string())?;
+        String::from_utf8(decrypted)
+            .map_err(|e| format!("Failed to convert decrypted data to string: {}", e))
+    }
+
+    /// Key of the encrypted credential files: the random secret in `.machine.key`
+    /// (created on first use, readable by the owner only) mixed with the machine id
+    #[cfg(unix)]
+    fn file_key(&self) -> Result<Key<Aes256Gcm>, String> {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let path = self.credentials_dir.join(MACHINE_KEY_FILE);
+        let secret = match fs::read(&path) {
+            Ok(secret) => secret,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                // Write the whole key aside, then link it into place so readers never
+                // see a partial key and a concurrent first use (CLI and app) can't
+                // replace a key that is already in use
+                let secret = Aes256Gcm::generate_key(OsRng).to_vec();
+                let temp = path.with_extension(format!("key.{}.tmp", uuid::Uuid::new_v4().simple()));
+                let mut file = fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .mode(0o600)
+                    .open(&temp)
+                    .map_err(|e| format!("Failed to create credential key: {}", e))?;
+                let linked = file
+                    .write_all(&secret)
+                    .and_then(|_| file.sync_all())
+                    .and_then(|_| fs::hard_link(&temp, &path));
+                let _ = fs::remove_file(&temp);
+                match linked {
+                    Ok(()) => secret,
+                    // Another process created the key first; use that one
+                    Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => fs::read(&path)
+                        .map_err(|e| format!("Failed to read credential key: {}", e))?,
+                    Err(e) => return Err(format!("Failed to write credential key: {}", e)),
+                }
+            }
+            Err(e) => return Err(format!("Failed to read credential key: {}", e)),
+        };
+
+        let machine_id = fs::read("/etc/machine-id")
+            .or_else(|_| fs::read("/var/lib/dbus/machine-id"))
+            .unwrap_or_default();
+        let digest = Sha256::new()
+            .chain_update(b"clickdevport-credentials")
+            .chain_update(&secret)
+            .chain_update(machine_id.trim_ascii())
+            .finalize();
+        Ok(Key::<Aes256Gcm>::clone_from_slice(&digest))
+    }
+
+    /// Stub for platforms without a credential backend
+    #[cfg(not(any(windows, unix)))]
     pub fn encrypt_credential(&self, _data: &str) -> Result<Vec<u8>, String> {
-        Err("DPAPI is only available on Windows".to_string())
+        Err("Credential encryption is not supported on this platform".to_string())
     }
 
-    /// Non-Windows stub for decrypt_credential
-    #[cfg(not(windows))]
+    /// Stub for platforms without a credential backend
+    #[cfg(not(any(windows, unix)))]
     pub fn decrypt_credential(&self, _encrypted: &[u8]) -> Result<String, String> {
-        Err("DPAPI is only available on Windows".to_string())
+        Err("Credential encryption is not supported on this platform".to_string())
     }
 
-    /// Saves an encrypted credential to disk.
+    /// Saves a credential to the Secret Service, or encrypted to disk.
     /// The key is used as the filename (sanitized for filesystem safety).
     pub fn save_credential(&self, key: &str, value: &str) -> Result<(), String> {
+        #[cfg(target_os = "linux")]
+        if let Some(store) = &self.secret_store {
+            match store.save(&Self::sanitize_key(key), value) {
+                // Drop an older file copy so it can't shadow the keyring later
+                Ok(()) => return self.delete_credential_file(key),
+                Err(e) => eprintln!("{}; saving to an encrypted file instead", e),
+            }
+        }
+
         let encrypted = self.encrypt_credential(value)?;
         let file_path = self.get_credential_path(key);
 
-        fs::write(&file_path, &encrypted)
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(&file_path)
+            .map_err(|e| format!("Failed to save credential '{}': {}", key, e))?;
+        // Files saved before they were created owner-only
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))
+                .map_err(|e| format!("Failed to restrict credential '{}': {}", key, e))?;
+        }
+        file.write_all(&encrypted)
             .map_err(|e| format!("Failed to save credential '{}': {}", key, e))?;
 
         Ok(())
     }
 
-    /// Loads and decrypts a credential from disk.
+    /// Loads and decrypts a credential.
     /// Returns None if the credential does not exist.
     pub fn load_credential(&self, key: &str) -> Result<Option<String>, String> {
+        #[cfg(target_os = "linux")]
+        if let Some(store) = &self.secret_store {
+            if let Some(value) = store.load(&Self::sanitize_key(key))? {
+                return Ok(Some(value));
+            }
+        }
+
         let file_path = self.get_credential_path(key);
 
         if !file_path.exists() {
@@ -171,8 +369,17 @@ impl CredentialManager {
         Ok(Some(decrypted))
     }
 
-    /// Deletes a credential from disk.
+    /// Deletes a credential.
     pub fn delete_credential(&self, key: &str) -> Result<(), String> {
+        #[cfg(target_os = "linux")]
+        if let Some(store) = &self.secret_store {
+            store.delete(&Self::sanitize_key(key))?;
+        }
+
+        self.delete_credential_file(key)
+    }
+
+    fn delete_credential_file(&self, key: &str) -> Result<(), String> {
         let file_path = self.get_credential_path(key);
 
         if file_path.exists() {
@@ -185,15 +392,24 @@ impl CredentialManager {
 
     /// Checks if a credential exists.
     pub fn has_credential(&self, key: &str) -> bool {
+        #[cfg(target_os = "linux")]
+        if let Some(store) = &self.secret_store {
+            if store.contains(&Self::sanitize_key(key)) {
+                return true;
+            }
+        }
+
         self.get_credential_path(key).exists()
     }
 
     /// Gets the file path for a credential key.
-    /// The key is sanitized to be filesystem-safe.
     fn get_credential_path(&self, key: &str) -> PathBuf {
-        // Sanitize the key for filesystem safety
-        let safe_key: String = key
-            .chars()
+        self.credentials_dir.join(format!("{}.cred", Self::sanitize_key(key)))
+    }
+
+    /// Sanitize a key for filesystem safety; keyring items use the same form
+    fn sanitize_key(key: &str) -> String {
+        key.chars()
             .map(|c| {
                 if c.is_alphanumeric() || c == '-' || c == '_' {
                     c
@@ -201,13 +417,28 @@ impl CredentialManager {
                     '_'
                 }
             })
-            .collect();
-
-        self.credentials_dir.join(format!("{}.cred", safe_key))
+            .collect()
     }
 
     /// Lists all stored credential keys.
     pub fn list_credentials(&self) -> Result<Vec<String>, String> {
+        #[allow(unused_mut)]
+        let mut keys = self.list_credential_files()?;
+
+        #[cfg(target_os = "linux")]
+        if let Some(store) = &self.secret_store {
+            for key in store.list()? {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Keys of the credentials stored as files
+    fn list_credential_files(&self) -> Result<Vec<String>, String> {
         let mut keys = Vec::new();
 
         let entries = fs::read_dir(&self.credentials_dir)
@@ -252,6 +483,60 @@ mod tests {
         assert_eq!(decrypted, original);
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_encrypted_file_backend() {
+        let dir = std::env::temp_dir().join(format!("devport-credentials-{}", std::process::id()));
+        let manager = CredentialManager::with_directory(dir.clone()).unwrap();
+
+        let encrypted = manager.encrypt_credential("root_pw").unwrap();
+        assert!(encrypted.starts_with(FILE_FORMAT_MAGIC));
+        assert_eq!(manager.decrypt_credential(&encrypted).unwrap(), "root_pw");
+        // Fresh nonce every time
+        assert_ne!(manager.encrypt_credential("root_pw").unwrap(), encrypted);
+
+        manager.save_credential("mariadb:root", "s3cret").unwrap();
+        assert!(manager.has_credential("mariadb:root"));
+        assert_eq!(manager.list_credentials().unwrap(), vec!["mariadb_root".to_string()]);
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let metadata = fs::metadata(manager.get_credential_path("mariadb:root")).unwrap();
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        }
+        // A second manager derives the same key
+        let reopened = CredentialManager::with_directory(dir.clone()).unwrap();
+        assert_eq!(reopened.load_credential("mariadb:root").unwrap(), Some("s3cret".to_string()));
+
+        let mut tampered = fs::read(manager.get_credential_path("mariadb:root")).unwrap();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(manager.decrypt_credential(&tampered).is_err());
+
+        manager.delete_credential("mariadb:root").unwrap();
+        assert_eq!(manager.load_credential("mariadb:root").unwrap(), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_concurrent_first_use_shares_one_key() {
+        let dir = std::env::temp_dir().join(format!("devport-credential-key-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let dir = dir.clone();
+                std::thread::spawn(move || CredentialManager::with_directory(dir).unwrap().file_key().unwrap())
+            })
+            .collect();
+        let keys: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert!(keys.iter().all(|key| *key == keys[0]));
+        // Only the key itself is left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     #[cfg(windows)]
     fn test_save_load_delete_credential() {
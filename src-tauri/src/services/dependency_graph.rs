@@ -0,0 +1,229 @@
+use crate::models::{Project, Service};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DependencyError {
+    #[error("Dependency cycle detected: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    #[error("'{0}' depends on unknown project or service '{1}'")]
+    UnknownDependency(String, String),
+    #[error("Unknown project or service: {0}")]
+    NotFound(String),
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    Project,
+    Service,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyNode {
+    pub id: String,
+    pub name: String,
+    pub kind: DependencyKind,
+    pub depends_on: Vec<String>,
+}
+
+/// Dependency graph over projects and services.
+/// Edges point from a node to the nodes it depends on.
+pub struct DependencyGraph {
+    nodes: HashMap<String, DependencyNode>,
+}
+
+impl DependencyGraph {
+    pub fn new<'a>(projects: &[Project], services: impl IntoIterator<Item = &'a Service>) -> Self {
+        let mut nodes = HashMap::new();
+
+        for service in services {
+            nodes.insert(
+                service.id.clone(),
+                DependencyNode {
+                    id: service.id.clone(),
+                    name: service.name.clone(),
+                    kind: DependencyKind::Service,
+                    depends_on: service.depends_on.clone(),
+                },
+            );
+        }
+
+        for project in projects {
+            nodes.insert(
+                project.id.clone(),
+                DependencyNode {
+                    id: project.id.clone(),
+                    name: project.name.clone(),
+                    kind: DependencyKind::Project,
+                    depends_on: project.depends_on.clone(),
+                },
+            );
+        }
+
+        Self { nodes }
+    }
+
+    /// Nodes that directly depend on the given node
+    pub fn dependents(&self, id: &str) -> Vec<&DependencyNode> {
+        self.nodes
+            .values()
+            .filter(|n| n.depends_on.iter().any(|d| d == id))
+            .collect()
+    }
+
+    /// Order in which the given roots and all their transitive dependencies must be
+    /// started: every node appears after the nodes it depends on.
+    pub fn start_order(&self, roots: &[String]) -> Result<Vec<DependencyNode>, DependencyError> {
+        let mut order = Vec::new();
+        let mut done = HashSet::new();
+        let mut path = Vec::new();
+
+        for root in roots {
+            if !self.nodes.contains_key(root) {
+                return Err(DependencyError::NotFound(root.clone()));
+            }
+            self.visit(root, &mut done, &mut path, &mut order)?;
+        }
+
+        Ok(order)
+    }
+
+    /// Order in which the given roots and their dependencies should be stopped:
+    /// dependents go down before the nodes they depend on.
+    pub fn stop_order(&self, roots: &[String]) -> Result<Vec<DependencyNode>, DependencyError> {
+        let mut order = self.start_order(roots)?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Check the whole graph for cycles and dangling references
+    pub fn validate(&self) -> Result<(), DependencyError> {
+        let mut ids: Vec<String> = self.nodes.keys().cloned().collect();
+        ids.sort();
+        self.start_order(&ids).map(|_| ())
+    }
+
+    /// Depth-first post-order traversal; `path` holds the current chain for cycle reporting
+    fn visit(
+        &self,
+        id: &str,
+        done: &mut HashSet<String>,
+        path: &mut Vec<String>,
+        order: &mut Vec<DependencyNode>,
+    ) -> Result<(), DependencyError> {
+        if done.contains(id) {
+            return Ok(());
+        }
+
+        if let Some(pos) = path.iter().position(|p| p == id) {
+            let mut cycle: Vec<String> = path[pos..].iter().map(|p| self.display_name(p)).collect();
+            cycle.push(self.display_name(id));
+            return Err(DependencyError::Cycle(cycle));
+        }
+
+        let node = match self.nodes.get(id) {
+            Some(node) => node,
+            None => return Err(DependencyError::NotFound(id.to_string())),
+        };
+
+        path.push(id.to_string());
+        for dep in &node.depends_on {
+            if !self.nodes.contains_key(dep) {
+                return Err(DependencyError::UnknownDependency(node.name.clone(), dep.clone()));
+            }
+            self.visit(dep, done, path, order)?;
+        }
+        path.pop();
+
+        done.insert(id.to_string());
+        order.push(node.clone());
+        Ok(())
+    }
+
+    fn display_name(&self, id: &str) -> String {
+        self.nodes
+            .get(id)
+            .map(|n| n.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectType, ServiceType};
+
+    fn project(id: &str, deps: &[&str]) -> Project {
+        let mut p = Project::new(id.to_string(), String::new(), 0, ProjectType::Unknown, String::new());
+        p.id = id.to_string();
+        p.depends_on = deps.iter().map(|d| d.to_string()).collect();
+        p
+    }
+
+    fn ids(nodes: &[DependencyNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_start_order_puts_dependencies_first() {
+        let db = Service::new("mariadb".to_string(), "mariadb".to_string(), ServiceType::Database);
+        let projects = vec![project("web", &["api"]), project("api", &["mariadb"])];
+        let graph = DependencyGraph::new(&projects, [&db]);
+
+        let order = graph.start_order(&["web".to_string()]).unwrap();
+        assert_eq!(ids(&order), vec!["mariadb", "api", "web"]);
+
+        let order = graph.stop_order(&["web".to_string()]).unwrap();
+        assert_eq!(ids(&order), vec!["web", "api", "mariadb"]);
+    }
+
+    #[test]
+    fn test_shared_dependency_started_once() {
+        let projects = vec![
+            project("a", &["c"]),
+            project("b", &["c"]),
+            project("c", &[]),
+        ];
+        let graph = DependencyGraph::new(&projects, []);
+
+        let order = graph.start_order(&["a".to_string(), "b".to_string()]).unwrap();
+        assert_eq!(ids(&order), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_cycle_detected() {
+        let projects = vec![project("a", &["b"]), project("b", &["c"]), project("c", &["a"])];
+        let graph = DependencyGraph::new(&projects, []);
+
+        match graph.start_order(&["a".to_string()]) {
+            Err(DependencyError::Cycle(cycle)) => assert_eq!(cycle, vec!["a", "b", "c", "a"]),
+            other => panic!("expected cycle, got {:?}", other.map(|o| ids(&o).join(","))),
+        }
+        assert!(graph.validate().is_err());
+    }
+
+    #[test]
+    fn test_self_reference_is_a_cycle() {
+        let projects = vec![project("a", &["a"])];
+        let graph = DependencyGraph::new(&projects, []);
+
+        match graph.validate() {
+            Err(DependencyError::Cycle(cycle)) => assert_eq!(cycle, vec!["a", "a"]),
+            other => panic!("expected cycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_dependency() {
+        let projects = vec![project("a", &["missing"])];
+        let graph = DependencyGraph::new(&projects, []);
+
+        assert!(matches!(
+            graph.start_order(&["a".to_string()]),
+            Err(DependencyError::UnknownDependency(_, _))
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::services::secret_vault;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::{self, File};
@@ -204,6 +205,41 @@ impl EnvManager {
         fs::write(&path, content).map_err(|e| e.to_string())
     }
 
+    /// Set one variable, replacing its line or appending one. Unlike `write_env_file`
+    /// this leaves the rest of the file, blank lines included, untouched.
+    pub fn set_variable(&self, file_name: &str, key: &str, value: &str) -> Result<(), String> {
+        let path = self.project_path.join(file_name);
+        let content = if path.exists() {
+            fs::read_to_string(&path).map_err(|e| e.to_string())?
+        } else {
+            String::new()
+        };
+
+        let assignment = format!("{}={}", key, value);
+        let mut replaced = false;
+        let mut lines: Vec<String> = content
+            .lines()
+            .map(|line| {
+                let is_key = line
+                    .trim_start()
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.trim_start().starts_with('='));
+                // Every occurrence: loaders differ on whether the first or last wins
+                if is_key {
+                    replaced = true;
+                    assignment.clone()
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect();
+        if !replaced {
+            lines.push(assignment);
+        }
+
+        fs::write(&path, lines.join("\n") + "\n").map_err(|e| e.to_string())
+    }
+
     pub fn create_env_file(&self, file_name: &str) -> Result<(), String> {
         let path = self.project_path.join(file_name);
 
@@ -292,6 +328,29 @@ impl EnvManager {
             .any(|pattern| key_upper.contains(pattern))
     }
 
+    /// Variables referencing the secret vault (`${vault:name}`) as dotenv loaders see
+    /// them: `.env`, then the active profile, then `.env.local`, later files winning
+    pub fn vault_variables(&self) -> HashMap<String, String> {
+        let mut files = vec![".env".to_string()];
+        if let Ok(active) = self.get_active_profile() {
+            files.push(active);
+        }
+        files.push(".env.local".to_string());
+        files.dedup();
+
+        let mut variables = HashMap::new();
+        for file_name in files {
+            for var in self.read_env_file(&file_name).unwrap_or_default() {
+                if secret_vault::placeholder_names(&var.value).is_empty() {
+                    variables.remove(&var.key);
+                } else {
+                    variables.insert(var.key, var.value);
+                }
+            }
+        }
+        variables
+    }
+
     /// List all available profiles for a project
     pub fn list_profiles(&self) -> Result<Vec<ProfileInfo>, String> {
         let env_files = self.get_env_files();
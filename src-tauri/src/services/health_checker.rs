@@ -1,6 +1,12 @@
+use crate::models::{HealthCheckConfig, HealthCheckType, Project};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use thiserror::Error;
 
+#[cfg(windows)]
+use crate::services::process_manager::CREATE_NO_WINDOW;
+
 #[derive(Error, Debug)]
 pub enum HealthCheckError {
     #[error("Request failed: {0}")]
@@ -28,17 +34,30 @@ impl HealthChecker {
         url: &str,
         timeout_secs: u64,
     ) -> HealthStatus {
+        let config = HealthCheckConfig {
+            check_type: HealthCheckType::Http,
+            timeout: timeout_secs * 1000,
+            expected_status: Some("200-299".to_string()),
+            ..Default::default()
+        };
+        Self::check_http(project_id, url, &config).await
+    }
+
+    /// HTTP probe honouring the config's status range, body and JSON checks,
+    /// headers and TLS settings
+    pub async fn check_http(id: &str, url: &str, config: &HealthCheckConfig) -> HealthStatus {
         let start = std::time::Instant::now();
         let checked_at = chrono::Utc::now().to_rfc3339();
 
         let client = match reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(timeout_secs))
+            .timeout(Duration::from_millis(config.timeout))
+            .danger_accept_invalid_certs(config.insecure_tls)
             .build()
         {
             Ok(c) => c,
             Err(e) => {
                 return HealthStatus {
-                    project_id: project_id.to_string(),
+                    project_id: id.to_string(),
                     is_healthy: false,
                     status_code: None,
                     response_time_ms: None,
@@ -48,29 +67,38 @@ impl HealthChecker {
             }
         };
 
-        match client.get(url).send().await {
+        let mut request = client.get(url);
+        for (name, value) in &config.headers {
+            request = request.header(name, value);
+        }
+
+        match request.send().await {
             Ok(response) => {
-                let elapsed = start.elapsed().as_millis() as u64;
-                let status = response.status();
-                let is_healthy = status.is_success();
+                let status = response.status().as_u16();
+                let mut error = None;
+
+                if !status_matches(config.expected_status.as_deref(), status) {
+                    error = Some(format!("HTTP {}", status));
+                } else if has_body_checks(config) {
+                    match response.text().await {
+                        Ok(body) => error = check_body(config, &body).err(),
+                        Err(e) => error = Some(format!("Failed to read body: {}", e)),
+                    }
+                }
 
                 HealthStatus {
-                    project_id: project_id.to_string(),
-                    is_healthy,
-                    status_code: Some(status.as_u16()),
-                    response_time_ms: Some(elapsed),
-                    error: if !is_healthy {
-                        Some(format!("HTTP {}", status.as_u16()))
-                    } else {
-                        None
-                    },
+                    project_id: id.to_string(),
+                    is_healthy: error.is_none(),
+                    status_code: Some(status),
+                    response_time_ms: Some(start.elapsed().as_millis() as u64),
+                    error,
                     checked_at,
                 }
             }
             Err(e) => {
                 let elapsed = start.elapsed().as_millis() as u64;
                 HealthStatus {
-                    project_id: project_id.to_string(),
+                    project_id: id.to_string(),
                     is_healthy: false,
                     status_code: None,
                     response_time_ms: Some(elapsed),
@@ -81,6 +109,217 @@ impl HealthChecker {
         }
     }
 
+    /// Check health by opening a TCP connection to host:port
+    pub async fn check_tcp(project_id: &str, host: &str, port: u16, timeout_ms: u64) -> HealthStatus {
+        let start = std::time::Instant::now();
+        let checked_at = chrono::Utc::now().to_rfc3339();
+
+        let result = tokio::time::timeout(
+            Duration::from_millis(timeout_ms),
+            tokio::net::TcpStream::connect((host, port)),
+        )
+        .await;
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        let error = match result {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("Connection to {}:{} timed out", host, port)),
+        };
+
+        HealthStatus {
+            project_id: project_id.to_string(),
+            is_healthy: error.is_none(),
+            status_code: None,
+            response_time_ms: Some(elapsed),
+            error,
+            checked_at,
+        }
+    }
+
+    /// Connect, send `send` and require the first reply line to start with `expect`.
+    /// An empty `send` waits for a server that greets first.
+    pub async fn check_tcp_reply(
+        id: &str,
+        host: &str,
+        port: u16,
+        send: &str,
+        expect: &str,
+        timeout_ms: u64,
+    ) -> HealthStatus {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let start = std::time::Instant::now();
+        let checked_at = chrono::Utc::now().to_rfc3339();
+
+        let probe = async {
+            let mut stream = tokio::net::TcpStream::connect((host, port))
+                .await
+                .map_err(|e| e.to_string())?;
+            if !send.is_empty() {
+                stream.write_all(send.as_bytes()).await.map_err(|e| e.to_string())?;
+            }
+            let mut reply = String::new();
+            BufReader::new(stream)
+                .read_line(&mut reply)
+                .await
+                .map_err(|e| e.to_string())?;
+            let reply = reply.trim_end();
+            if reply.is_empty() {
+                Err("Connection closed without a reply".to_string())
+            } else if reply.starts_with(expect) {
+                Ok(())
+            } else {
+                Err(format!("Unexpected reply: {}", reply))
+            }
+        };
+        let result = tokio::time::timeout(Duration::from_millis(timeout_ms), probe).await;
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        let error = match result {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(e),
+            Err(_) => Some(format!("No reply from {}:{} in time", host, port)),
+        };
+
+        HealthStatus {
+            project_id: id.to_string(),
+            is_healthy: error.is_none(),
+            status_code: None,
+            response_time_ms: Some(elapsed),
+            error,
+            checked_at,
+        }
+    }
+
+    /// Run a configured probe. HTTP and TCP probes without an endpoint fall back
+    /// to `port` on localhost; command probes run in `work_dir`.
+    pub async fn run_check(
+        id: &str,
+        config: &HealthCheckConfig,
+        port: Option<u16>,
+        pid: Option<u32>,
+        work_dir: Option<&str>,
+    ) -> HealthStatus {
+        let endpoint = config.endpoint.as_deref().filter(|e| !e.is_empty());
+
+        match config.check_type {
+            HealthCheckType::Http => {
+                match endpoint.map(str::to_string).or_else(|| port.map(Self::default_health_url)) {
+                    Some(url) => Self::check_http(id, &url, config).await,
+                    None => Self::failed(id, "No health check endpoint configured"),
+                }
+            }
+            HealthCheckType::Tcp => {
+                let target = match endpoint {
+                    Some(endpoint) => endpoint
+                        .rsplit_once(':')
+                        .and_then(|(host, p)| Some((host.to_string(), p.parse::<u16>().ok()?))),
+                    None => port.map(|p| ("127.0.0.1".to_string(), p)),
+                };
+                match target {
+                    Some((host, port)) if config.tcp_send.is_some() || config.tcp_expect.is_some() => {
+                        let send = config.tcp_send.as_deref().unwrap_or_default();
+                        let expect = config.tcp_expect.as_deref().unwrap_or_default();
+                        Self::check_tcp_reply(id, &host, port, send, expect, config.timeout).await
+                    }
+                    Some((host, port)) => Self::check_tcp(id, &host, port, config.timeout).await,
+                    None => Self::failed(id, "No TCP endpoint configured"),
+                }
+            }
+            HealthCheckType::Process => match pid {
+                Some(pid) => Self::check_process_alive(id, pid),
+                None => Self::failed(id, "Process is not running"),
+            },
+            HealthCheckType::Command => match config.command.as_deref().filter(|c| !c.is_empty()) {
+                Some(command) => Self::check_command(id, command, work_dir, config.timeout).await,
+                None => Self::failed(id, "No health check command configured"),
+            },
+        }
+    }
+
+    /// Run a shell command; exit code 0 means healthy
+    pub async fn check_command(id: &str, command: &str, work_dir: Option<&str>, timeout_ms: u64) -> HealthStatus {
+        let start = std::time::Instant::now();
+        let checked_at = chrono::Utc::now().to_rfc3339();
+
+        #[cfg(windows)]
+        let mut cmd = {
+            let mut cmd = tokio::process::Command::new("cmd");
+            cmd.args(["/C", command]).creation_flags(CREATE_NO_WINDOW);
+            cmd
+        };
+        #[cfg(not(windows))]
+        let mut cmd = {
+            let mut cmd = tokio::process::Command::new("sh");
+            cmd.args(["-c", command]);
+            cmd
+        };
+        if let Some(dir) = work_dir.filter(|d| !d.is_empty()) {
+            cmd.current_dir(dir);
+        }
+        cmd.stdin(std::process::Stdio::null()).kill_on_drop(true);
+
+        let result = tokio::time::timeout(Duration::from_millis(timeout_ms), cmd.output()).await;
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        let error = match result {
+            Ok(Ok(output)) if output.status.success() => None,
+            Ok(Ok(output)) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let detail = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("");
+                Some(match output.status.code() {
+                    Some(code) => format!("Command exited with code {} {}", code, detail),
+                    None => format!("Command was terminated {}", detail),
+                }.trim_end().to_string())
+            }
+            Ok(Err(e)) => Some(format!("Failed to run command: {}", e)),
+            Err(_) => Some(format!("Command timed out after {}ms", timeout_ms)),
+        };
+
+        HealthStatus {
+            project_id: id.to_string(),
+            is_healthy: error.is_none(),
+            status_code: None,
+            response_time_ms: Some(elapsed),
+            error,
+            checked_at,
+        }
+    }
+
+    fn failed(id: &str, error: &str) -> HealthStatus {
+        HealthStatus {
+            project_id: id.to_string(),
+            is_healthy: false,
+            status_code: None,
+            response_time_ms: None,
+            error: Some(error.to_string()),
+            checked_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Check a project using its configured probe, or the best available one:
+    /// its health check URL, then its port over TCP, then its process.
+    pub async fn check_project(project: &Project, pid: Option<u32>, timeout_secs: u64) -> HealthStatus {
+        if let Some(config) = &project.health_check {
+            let port = Some(project.port).filter(|p| *p > 0);
+            return Self::run_check(&project.id, config, port, pid, Some(&project.path)).await;
+        }
+
+        if let Some(url) = project.health_check_url.as_deref().filter(|u| !u.is_empty()) {
+            return Self::check_health(&project.id, url, timeout_secs).await;
+        }
+
+        if project.port > 0 {
+            return Self::check_tcp(&project.id, "127.0.0.1", project.port, timeout_secs * 1000).await;
+        }
+
+        match pid {
+            Some(pid) => Self::check_process_alive(&project.id, pid),
+            None => Self::failed(&project.id, "Process is not running"),
+        }
+    }
+
     /// Check health by verifying a process is still alive (for desktop/local apps with no port)
     pub fn check_process_alive(project_id: &str, pid: u32) -> HealthStatus {
         let checked_at = chrono::Utc::now().to_rfc3339();
@@ -102,7 +341,7 @@ impl HealthChecker {
     }
 
     /// Check if a PID is still alive
-    fn is_pid_alive(pid: u32) -> bool {
+    pub(crate) fn is_pid_alive(pid: u32) -> bool {
         #[cfg(windows)]
         {
             use std::process::Command;
@@ -136,3 +375,115 @@ impl HealthChecker {
         format!("http://localhost:{}", port)
     }
 }
+
+/// Whether `code` is accepted by a spec like "200-299,302" or "2xx".
+/// Without a spec any 2xx or 3xx counts as healthy.
+fn status_matches(spec: Option<&str>, code: u16) -> bool {
+    let Some(spec) = spec.map(str::trim).filter(|s| !s.is_empty()) else {
+        return (200..400).contains(&code);
+    };
+
+    spec.split(',').map(str::trim).any(|part| {
+        if let Some(class) = part.strip_suffix("xx").or_else(|| part.strip_suffix("XX")) {
+            return class.parse::<u16>().map(|c| code / 100 == c).unwrap_or(false);
+        }
+        match part.split_once('-') {
+            Some((lo, hi)) => match (lo.trim().parse::<u16>(), hi.trim().parse::<u16>()) {
+                (Ok(lo), Ok(hi)) => (lo..=hi).contains(&code),
+                _ => false,
+            },
+            None => part.parse::<u16>().map(|c| c == code).unwrap_or(false),
+        }
+    })
+}
+
+fn has_body_checks(config: &HealthCheckConfig) -> bool {
+    config.body_contains.is_some() || config.body_regex.is_some() || config.json_pointer.is_some()
+}
+
+/// Apply the config's body substring, regex and JSON pointer checks
+fn check_body(config: &HealthCheckConfig, body: &str) -> Result<(), String> {
+    if let Some(needle) = &config.body_contains {
+        if !body.contains(needle.as_str()) {
+            return Err(format!("Response does not contain \"{}\"", needle));
+        }
+    }
+
+    if let Some(pattern) = &config.body_regex {
+        let re = Regex::new(pattern).map_err(|e| format!("Invalid body regex: {}", e))?;
+        if !re.is_match(body) {
+            return Err(format!("Response does not match /{}/", pattern));
+        }
+    }
+
+    if let Some(pointer) = &config.json_pointer {
+        let json: serde_json::Value =
+            serde_json::from_str(body).map_err(|e| format!("Response is not JSON: {}", e))?;
+        let actual = json
+            .pointer(pointer)
+            .ok_or_else(|| format!("{} not found in response", pointer))?;
+        if let Some(expected) = &config.json_value {
+            if actual != expected {
+                return Err(format!("{} is {}, expected {}", pointer, actual, expected));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_ranges() {
+        assert!(status_matches(None, 204));
+        assert!(status_matches(None, 302));
+        assert!(!status_matches(None, 500));
+
+        assert!(status_matches(Some("200-299, 302"), 302));
+        assert!(!status_matches(Some("200-299, 302"), 301));
+        assert!(status_matches(Some("2xx,401"), 401));
+        assert!(!status_matches(Some("2xx"), 500));
+    }
+
+    #[test]
+    fn test_body_checks() {
+        let config = HealthCheckConfig {
+            body_contains: Some("ok".to_string()),
+            body_regex: Some(r#""queue":\s*"\w+""#.to_string()),
+            json_pointer: Some("/db/status".to_string()),
+            json_value: Some(serde_json::json!("ok")),
+            ..Default::default()
+        };
+
+        assert!(check_body(&config, r#"{"queue": "up", "db": {"status": "ok"}}"#).is_ok());
+        assert!(check_body(&config, r#"{"queue": "up", "db": {"status": "down"}, "x": "ok"}"#)
+            .unwrap_err()
+            .contains("expected \"ok\""));
+        assert!(check_body(&config, "not json ok \"queue\": \"up\"").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_reply_check() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            for reply in ["+PONG\r\n", "-LOADING Redis is loading the dataset\r\n"] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 16];
+                let n = socket.read(&mut buf).await.unwrap();
+                assert_eq!(&buf[..n], b"PING\r\n");
+                socket.write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+
+        let status = HealthChecker::check_tcp_reply("redis", "127.0.0.1", port, "PING\r\n", "+PONG", 2000).await;
+        assert!(status.is_healthy, "{:?}", status.error);
+        let status = HealthChecker::check_tcp_reply("redis", "127.0.0.1", port, "PING\r\n", "+PONG", 2000).await;
+        assert_eq!(status.error.as_deref(), Some("Unexpected reply: -LOADING Redis is loading the dataset"));
+    }
+}
@@ -0,0 +1,329 @@
+use crate::services::health_checker::{HealthChecker, HealthStatus};
+use crate::services::storage::Storage;
+use crate::state::AppState;
+use futures_util::future::join_all;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+/// How often the monitor looks for projects due for a probe
+const HEALTH_TICK: Duration = Duration::from_secs(1);
+
+/// Probe interval of projects without their own health check settings
+const DEFAULT_HEALTH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Consecutive failed probes before a project without its own settings turns unhealthy
+const DEFAULT_HEALTH_RETRIES: u32 = 2;
+
+/// Per-probe timeout
+const HEALTH_CHECK_TIMEOUT_SECS: u64 = 5;
+
+/// Samples kept per project (2 hours at the default interval)
+const HISTORY_CAPACITY: usize = 720;
+
+/// Aggregated view of a project's recent health samples
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthSummary {
+    pub project_id: String,
+    pub is_healthy: bool,
+    /// When the project last changed between healthy and unhealthy
+    pub since: String,
+    pub last_check: HealthStatus,
+    pub samples: usize,
+    pub uptime_percent: f64,
+    pub avg_response_ms: Option<u64>,
+    pub p95_response_ms: Option<u64>,
+}
+
+/// Ring buffer of health samples for one project
+struct HealthHistory {
+    samples: VecDeque<HealthStatus>,
+    since: String,
+    /// Reported state; `None` until the first success or enough failures
+    healthy: Option<bool>,
+    /// Failed probes in a row
+    failures: u32,
+}
+
+impl HealthHistory {
+    fn new(first: &HealthStatus) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(HISTORY_CAPACITY),
+            since: first.checked_at.clone(),
+            healthy: None,
+            failures: 0,
+        }
+    }
+
+    fn push(&mut self, status: HealthStatus) {
+        if self.samples.len() == HISTORY_CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(status);
+    }
+
+    fn last(&self) -> Option<&HealthStatus> {
+        self.samples.back()
+    }
+
+    fn summary(&self) -> Option<HealthSummary> {
+        let last = self.last()?;
+
+        let healthy = self.samples.iter().filter(|s| s.is_healthy).count();
+        let mut latencies: Vec<u64> = self
+            .samples
+            .iter()
+            .filter(|s| s.is_healthy)
+            .filter_map(|s| s.response_time_ms)
+            .collect();
+        latencies.sort_unstable();
+
+        let avg_response_ms = (!latencies.is_empty())
+            .then(|| latencies.iter().sum::<u64>() / latencies.len() as u64);
+        // Nearest-rank percentile
+        let p95_response_ms = (!latencies.is_empty())
+            .then(|| latencies[(latencies.len() * 95).div_ceil(100) - 1]);
+
+        Some(HealthSummary {
+            project_id: last.project_id.clone(),
+            is_healthy: self.healthy.unwrap_or(last.is_healthy),
+            since: self.since.clone(),
+            last_check: last.clone(),
+            samples: self.samples.len(),
+            uptime_percent: healthy as f64 * 100.0 / self.samples.len() as f64,
+            avg_response_ms,
+            p95_response_ms,
+        })
+    }
+}
+
+/// Health history of every running project
+#[derive(Default)]
+pub struct HealthMonitor {
+    histories: HashMap<String, HealthHistory>,
+}
+
+impl HealthMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a sample. A project turns unhealthy only after `retries` failed probes
+    /// in a row. Returns the previous health state if this sample changed it, or
+    /// `Some(None)` when it set the first state of a project.
+    pub fn record(&mut self, status: HealthStatus, retries: u32) -> Option<Option<bool>> {
+        let history = self
+            .histories
+            .entry(status.project_id.clone())
+            .or_insert_with(|| HealthHistory::new(&status));
+
+        history.failures = if status.is_healthy { 0 } else { history.failures + 1 };
+        let healthy = if status.is_healthy {
+            Some(true)
+        } else if history.failures >= retries.max(1) {
+            Some(false)
+        } else {
+            history.healthy
+        };
+
+        let previous = history.healthy;
+        let changed = healthy.is_some() && healthy != previous;
+        if changed {
+            history.since = status.checked_at.clone();
+            history.healthy = healthy;
+        }
+        history.push(status);
+
+        changed.then_some(previous)
+    }
+
+    pub fn history(&self, project_id: &str) -> Vec<HealthStatus> {
+        self.histories
+            .get(project_id)
+            .map(|h| h.samples.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn summary(&self, project_id: &str) -> Option<HealthSummary> {
+        self.histories.get(project_id)?.summary()
+    }
+
+    pub fn summaries(&self) -> Vec<HealthSummary> {
+        self.histories.values().filter_map(|h| h.summary()).collect()
+    }
+
+    /// Forget projects that are no longer running
+    pub fn retain_running(&mut self, running: &[String]) {
+        self.histories.retain(|id, _| running.contains(id));
+    }
+}
+
+pub type SharedHealthMonitor = Arc<Mutex<HealthMonitor>>;
+
+pub fn init_health_monitor() -> SharedHealthMonitor {
+    Arc::new(Mutex::new(HealthMonitor::new()))
+}
+
+/// Spawn the background task that probes every running project at its health check
+/// interval. Uses the project's health check settings, then its health check URL,
+/// then its port, then its process, and emits `project-health-changed` when a
+/// project turns healthy or unhealthy.
+pub fn spawn_health_monitor(
+    app_handle: AppHandle,
+    health_monitor: SharedHealthMonitor,
+    app_state: Arc<Mutex<AppState>>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(HEALTH_TICK);
+        // When each running project is due for its next probe
+        let mut next_check: HashMap<String, Instant> = HashMap::new();
+        loop {
+            interval.tick().await;
+
+            let running: Vec<(String, u32, u16)> = app_state
+                .lock()
+                .await
+                .running_processes
+                .values()
+                .map(|p| (p.project_id.clone(), p.pid, p.port))
+                .collect();
+
+            let running_ids: Vec<String> = running.iter().map(|(id, _, _)| id.clone()).collect();
+            health_monitor.lock().await.retain_running(&running_ids);
+            next_check.retain(|id, _| running_ids.contains(id));
+
+            let now = Instant::now();
+            let due: Vec<(String, u32, u16)> = running
+                .into_iter()
+                .filter(|(id, _, _)| next_check.get(id).is_none_or(|at| *at <= now))
+                .collect();
+            if due.is_empty() {
+                continue;
+            }
+
+            let projects = match Storage::new().and_then(|s| s.load_projects()) {
+                Ok(projects) => projects,
+                Err(_) => continue,
+            };
+
+            let mut checks = Vec::new();
+            for (id, pid, port) in due {
+                let Some(mut project) = projects.iter().find(|p| p.id == id).cloned() else {
+                    continue;
+                };
+                let (every, retries) = match &project.health_check {
+                    Some(config) => (Duration::from_millis(config.interval.max(1000)), config.retries),
+                    None => (DEFAULT_HEALTH_INTERVAL, DEFAULT_HEALTH_RETRIES),
+                };
+                next_check.insert(id, now + every);
+                // The dev server may have moved to another port
+                if port > 0 {
+                    project.port = port;
+                }
+                checks.push(async move {
+                    let status =
+                        HealthChecker::check_project(&project, Some(pid), HEALTH_CHECK_TIMEOUT_SECS).await;
+                    (status, retries)
+                });
+            }
+            let results = join_all(checks).await;
+
+            let mut monitor = health_monitor.lock().await;
+            for (status, retries) in results {
+                if let Some(previous) = monitor.record(status.clone(), retries) {
+                    let _ = app_handle.emit(
+                        "project-health-changed",
+                        serde_json::json!({
+                            "projectId": status.project_id,
+                            "isHealthy": status.is_healthy,
+                            "wasHealthy": previous,
+                            "status": status
+                        }),
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(healthy: bool, ms: u64, at: &str) -> HealthStatus {
+        HealthStatus {
+            project_id: "api".to_string(),
+            is_healthy: healthy,
+            status_code: Some(if healthy { 200 } else { 500 }),
+            response_time_ms: Some(ms),
+            error: None,
+            checked_at: at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_transitions() {
+        let mut monitor = HealthMonitor::new();
+        assert_eq!(monitor.record(sample(true, 10, "t0"), 1), Some(None));
+        assert_eq!(monitor.record(sample(true, 10, "t1"), 1), None);
+        assert_eq!(monitor.record(sample(false, 10, "t2"), 1), Some(Some(true)));
+
+        let summary = monitor.summary("api").unwrap();
+        assert!(!summary.is_healthy);
+        assert_eq!(summary.since, "t2");
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut monitor = HealthMonitor::new();
+        for i in 0..HISTORY_CAPACITY + 10 {
+            monitor.record(sample(true, 1, &i.to_string()), 1);
+        }
+        let history = monitor.history("api");
+        assert_eq!(history.len(), HISTORY_CAPACITY);
+        assert_eq!(history[0].checked_at, "10");
+        assert_eq!(monitor.summary("api").unwrap().since, "0");
+    }
+
+    #[test]
+    fn test_summary_latency_and_uptime() {
+        let mut monitor = HealthMonitor::new();
+        for ms in 1..=100 {
+            monitor.record(sample(true, ms, "t"), 1);
+        }
+        monitor.record(sample(false, 5000, "t"), 1);
+
+        let summary = monitor.summary("api").unwrap();
+        assert_eq!(summary.p95_response_ms, Some(95));
+        assert_eq!(summary.avg_response_ms, Some(50));
+        assert!((summary.uptime_percent - 100.0 * 100.0 / 101.0).abs() < 1e-9);
+
+        monitor.retain_running(&[]);
+        assert!(monitor.summary("api").is_none());
+    }
+
+    #[test]
+    fn test_unhealthy_only_after_retries() {
+        let mut monitor = HealthMonitor::new();
+        // Failures before the first success don't set a state until retries is reached
+        assert_eq!(monitor.record(sample(false, 10, "t0"), 3), None);
+        assert_eq!(monitor.record(sample(true, 10, "t1"), 3), Some(None));
+
+        assert_eq!(monitor.record(sample(false, 10, "t2"), 3), None);
+        assert_eq!(monitor.record(sample(false, 10, "t3"), 3), None);
+        assert!(monitor.summary("api").unwrap().is_healthy);
+        // A success in between starts the count over
+        assert_eq!(monitor.record(sample(true, 10, "t4"), 3), None);
+        assert_eq!(monitor.record(sample(false, 10, "t5"), 3), None);
+        assert_eq!(monitor.record(sample(false, 10, "t6"), 3), None);
+        assert_eq!(monitor.record(sample(false, 10, "t7"), 3), Some(Some(true)));
+
+        let summary = monitor.summary("api").unwrap();
+        assert!(!summary.is_healthy);
+        assert_eq!(summary.since, "t7");
+    }
+}
@@ -92,6 +92,22 @@ const TOOL_DEFINITIONS: &[ToolDefinition] = &[
     },
     // Databases - NOTE: mysql/mariadb detection is handled separately in scan_mysql_or_mariadb()
     //            because XAMPP's mysql.exe is actually MariaDB engine.
+    ToolDefinition {
+        id: "redis",
+        name: "Redis",
+        category: InventoryCategory::Database,
+        // Valkey and Memurai are drop-in Redis servers
+        commands: &["redis-server", "valkey-server", "memurai"],
+        known_paths: &[
+            "C:\\DevPort\\runtime\\redis\\redis-server.exe",
+            "C:\\Program Files\\Redis\\redis-server.exe",
+            "C:\\Program Files\\Memurai\\memurai.exe",
+            "C:\\laragon\\bin\\redis\\redis-x64-5.0.14.1\\redis-server.exe",
+        ],
+        version_arg: "--version",
+        version_regex: r"v=(\d+\.\d+\.\d+)",
+        port: Some(6379),
+    },
     // Build Tools
     ToolDefinition {
         id: "vite",
@@ -0,0 +1,102 @@
+//! Credential storage in the freedesktop Secret Service (GNOME Keyring, KWallet, KeePassXC)
+
+use secret_service::blocking::{Item, SecretService};
+use secret_service::EncryptionType;
+use std::collections::HashMap;
+
+/// Attribute marking the items stored by ClickDevPort
+const APPLICATION_ATTRIBUTE: &str = "application";
+const APPLICATION: &str = "clickdevport";
+/// Attribute holding the credential key
+const KEY_ATTRIBUTE: &str = "credential";
+
+pub struct SecretStore {
+    service: SecretService<'static>,
+}
+
+impl SecretStore {
+    /// Connect to the session's Secret Service; `None` when no keyring daemon is running
+    pub fn connect() -> Option<Self> {
+        let service = SecretService::connect(EncryptionType::Dh).ok()?;
+        // A running service without a default collection can't store anything
+        service.get_default_collection().ok()?;
+        Some(Self { service })
+    }
+
+    pub fn save(&self, key: &str, value: &str) -> Result<(), String> {
+        let collection = self
+            .service
+            .get_default_collection()
+            .map_err(|e| format!("Secret Service unavailable: {}", e))?;
+        collection
+            .ensure_unlocked()
+            .map_err(|e| format!("Failed to unlock keyring: {}", e))?;
+        collection
+            .create_item(
+                &format!("ClickDevPort credential '{}'", key),
+                Self::attributes(Some(key)),
+                value.as_bytes(),
+                true,
+                "text/plain",
+            )
+            .map_err(|e| format!("Failed to save credential '{}' to keyring: {}", key, e))?;
+        Ok(())
+    }
+
+    pub fn load(&self, key: &str) -> Result<Option<String>, String> {
+        let Some(item) = self.items(Some(key))?.into_iter().next() else {
+            return Ok(None);
+        };
+        item.ensure_unlocked()
+            .map_err(|e| format!("Failed to unlock keyring: {}", e))?;
+        let secret = item
+            .get_secret()
+            .map_err(|e| format!("Failed to read credential '{}' from keyring: {}", key, e))?;
+        String::from_utf8(secret)
+            .map(Some)
+            .map_err(|e| format!("Failed to convert credential to string: {}", e))
+    }
+
+    pub fn delete(&self, key: &str) -> Result<(), String> {
+        for item in self.items(Some(key))? {
+            item.delete()
+                .map_err(|e| format!("Failed to delete credential '{}' from keyring: {}", key, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.items(Some(key)).is_ok_and(|items| !items.is_empty())
+    }
+
+    /// Keys of every credential in the keyring; readable while it is locked
+    pub fn list(&self) -> Result<Vec<String>, String> {
+        let mut keys = Vec::new();
+        for item in self.items(None)? {
+            let attributes = item
+                .get_attributes()
+                .map_err(|e| format!("Failed to read keyring item: {}", e))?;
+            if let Some(key) = attributes.get(KEY_ATTRIBUTE) {
+                keys.push(key.clone());
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Items of one credential, or of all of them, locked or not
+    fn items(&self, key: Option<&str>) -> Result<Vec<Item<'_>>, String> {
+        let found = self
+            .service
+            .search_items(Self::attributes(key))
+            .map_err(|e| format!("Failed to search keyring: {}", e))?;
+        Ok(found.unlocked.into_iter().chain(found.locked).collect())
+    }
+
+    fn attributes(key: Option<&str>) -> HashMap<&str, &str> {
+        let mut attributes = HashMap::from([(APPLICATION_ATTRIBUTE, APPLICATION)]);
+        if let Some(key) = key {
+            attributes.insert(KEY_ATTRIBUTE, key);
+        }
+        attributes
+    }
+}
@@ -1,12 +1,22 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::thread::JoinHandle;
+use std::time::Instant;
+use crate::models::LogConfig;
+use crate::services::log_parser::{parse_timestamp, LogFormat, LogLevel, LogParserRegistry, ParsedLine};
+use crate::services::log_tail::MergedTail;
 use chrono::{DateTime, Local, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc, RwLock};
-use tokio::time::{interval, Duration};
+use tokio::sync::{mpsc, Notify, RwLock};
+use tokio::time::{interval, sleep, Duration};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -17,12 +27,161 @@ pub struct LogEntry {
     pub source: String,
 }
 
+impl From<ParsedLine> for LogEntry {
+    fn from(parsed: ParsedLine) -> Self {
+        Self {
+            timestamp: parsed.timestamp.unwrap_or_else(|| Utc::now().to_rfc3339()),
+            level: parsed.level.unwrap_or(LogLevel::Info).as_str().to_string(),
+            source: parsed.source.unwrap_or_else(|| "system".to_string()),
+            message: parsed.message,
+        }
+    }
+}
+
+/// Matches returned by `search_logs` when the query sets no limit
+const DEFAULT_SEARCH_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSourceKind {
+    Project,
+    Service,
+}
+
+/// A service (by id) or project (by name) whose logs are kept by DevPort
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct LogSource {
+    pub kind: LogSourceKind,
+    pub name: String,
+}
+
+impl LogSource {
+    /// The name becomes a directory under the log root, so it must be a plain name
+    pub fn check_name(&self) -> Result<(), String> {
+        let name = &self.name;
+        if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+            return Err(format!("Invalid log source name: {}", name));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LogSearchQuery {
+    /// Empty matches every level
+    pub levels: Vec<LogLevel>,
+    /// RFC 3339 bounds
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// Regex matched against the message
+    pub pattern: Option<String>,
+    pub case_sensitive: bool,
+    /// Empty searches every project and service
+    pub sources: Vec<LogSource>,
+    pub limit: Option<usize>,
+}
+
+/// A log line tagged with the source and file it came from
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogSearchHit {
+    pub origin: LogSource,
+    /// File the line came from, e.g. "stderr" or "stdout.1"
+    pub log_type: String,
+    pub format: LogFormat,
+    #[serde(flatten)]
+    pub entry: LogEntry,
+}
+
+/// Disk space taken by one source's logs, rotated and compressed files included
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogDiskUsage {
+    #[serde(flatten)]
+    pub source: LogSource,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// When a log is rotated besides reaching `max_file_size`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RotationPolicy {
+    #[default]
+    Size,
+    Daily,
+    Hourly,
+}
+
+impl RotationPolicy {
+    /// Read `LogConfig::rotation_policy`; unknown values fall back to size only
+    pub fn parse(policy: &str) -> Self {
+        match policy.trim().to_lowercase().as_str() {
+            "daily" => RotationPolicy::Daily,
+            "hourly" => RotationPolicy::Hourly,
+            _ => RotationPolicy::Size,
+        }
+    }
+
+    /// Period `time` falls in; a log is rotated once the period changes
+    fn period(self, time: DateTime<Local>) -> Option<String> {
+        match self {
+            RotationPolicy::Size => None,
+            RotationPolicy::Daily => Some(time.format("%Y-%m-%d").to_string()),
+            RotationPolicy::Hourly => Some(time.format("%Y-%m-%d %H").to_string()),
+        }
+    }
+}
+
+/// Search hit ordered by recency; lines without a time rank lowest, and of two
+/// lines with the same time the one found first ranks higher
+struct RankedHit {
+    time: Option<DateTime<Utc>>,
+    seq: usize,
+    hit: LogSearchHit,
+}
+
+impl RankedHit {
+    fn key(&self) -> (Option<DateTime<Utc>>, Reverse<usize>) {
+        (self.time, Reverse(self.seq))
+    }
+
+    fn ranks_below(&self, time: Option<DateTime<Utc>>, seq: usize) -> bool {
+        self.key() < (time, Reverse(seq))
+    }
+}
+
+impl PartialEq for RankedHit {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for RankedHit {}
+
+impl PartialOrd for RankedHit {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RankedHit {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LogManager {
     pub base_path: PathBuf,
     pub max_file_size: u64,
+    /// Files kept per log, the current one included
     pub max_files: u32,
     pub retention_days: u32,
+    pub rotation_policy: RotationPolicy,
+    /// Gzip rotated files
+    pub compress: bool,
 }
 
 impl LogManager {
@@ -37,6 +196,8 @@ impl LogManager {
             max_file_size: 50 * 1024 * 1024,
             max_files: 5,
             retention_days: 30,
+            rotation_policy: RotationPolicy::Size,
+            compress: true,
         }
     }
 
@@ -46,7 +207,22 @@ impl LogManager {
             max_file_size: 50 * 1024 * 1024,
             max_files: 5,
             retention_days: 30,
+            rotation_policy: RotationPolicy::Size,
+            compress: true,
+        }
+    }
+
+    /// Manager whose rotation and retention follow `config`
+    pub fn with_config(config: &LogConfig) -> Self {
+        let mut manager = Self::new();
+        if let Some(max_size) = parse_size(&config.max_size) {
+            manager.max_file_size = max_size;
         }
+        manager.max_files = config.max_files.max(1);
+        manager.retention_days = config.retention_days;
+        manager.rotation_policy = RotationPolicy::parse(&config.rotation_policy);
+        manager.compress = config.compress;
+        manager
     }
 
     pub fn ensure_directories(&self) -> std::io::Result<()> {
@@ -108,58 +284,123 @@ impl LogManager {
     }
 
     fn rotate_if_needed(&self, path: &Path) -> std::io::Result<()> {
+        if self.needs_rotation(path)? {
+            self.rotate_log(path)?;
+        }
+        Ok(())
+    }
+
+    /// Whether a non-empty log reached the size limit or outlived its period
+    fn needs_rotation(&self, path: &Path) -> std::io::Result<bool> {
         if !path.exists() {
-            return Ok(());
+            return Ok(false);
         }
 
         let metadata = fs::metadata(path)?;
-        if metadata.len() < self.max_file_size {
-            return Ok(());
+        if metadata.len() == 0 {
+            return Ok(false);
         }
+        let period_ended = metadata
+            .modified()
+            .is_ok_and(|modified| self.period_ended(modified.into()));
+        Ok(metadata.len() >= self.max_file_size || period_ended)
+    }
 
-        self.rotate_log(path)
+    /// Whether the rotation period `since` falls in is over
+    fn period_ended(&self, since: DateTime<Local>) -> bool {
+        self.rotation_policy.period(since) != self.rotation_policy.period(Local::now())
     }
 
     fn rotate_log(&self, path: &Path) -> std::io::Result<()> {
-        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
-        let ext = path.extension().unwrap_or_default().to_string_lossy();
-        let parent = path.parent().unwrap_or(Path::new("."));
-
-        for i in (1..self.max_files).rev() {
-            let old_path = parent.join(format!("{}.{}.{}", stem, i, ext));
-            let new_path = parent.join(format!("{}.{}.{}", stem, i + 1, ext));
-
-            if old_path.exists() {
-                if i + 1 >= self.max_files {
-                    fs::remove_file(&old_path)?;
-                } else {
-                    fs::rename(&old_path, &new_path)?;
-                }
+        if let Some(rotated_path) = self.shift_generations(path, false)? {
+            compress_rotated(&rotated_path);
+        }
+        Ok(())
+    }
+
+    /// Move `path` to generation 1, shifting older generations up and dropping the
+    /// ones beyond `max_files`. With `copy_truncate` the log is copied and emptied in
+    /// place instead, for a writer that keeps it open. Returns the rotated file when
+    /// it still has to be compressed.
+    fn shift_generations(&self, path: &Path, copy_truncate: bool) -> std::io::Result<Option<PathBuf>> {
+        // Highest generation first, so a rename never overwrites the next one
+        let mut rotated = rotated_files(path);
+        rotated.sort_by_key(|(generation, _, _)| std::cmp::Reverse(*generation));
+        for (generation, compressed, old_path) in rotated {
+            if generation + 1 >= self.max_files {
+                fs::remove_file(&old_path)?;
+            } else {
+                fs::rename(&old_path, generation_path(path, generation + 1, compressed))?;
+            }
+        }
+
+        if self.max_files <= 1 {
+            if copy_truncate {
+                OpenOptions::new().write(true).open(path)?.set_len(0)?;
+            } else {
+                fs::remove_file(path)?;
             }
+            return Ok(None);
         }
 
-        let rotated_path = parent.join(format!("{}.1.{}", stem, ext));
-        fs::rename(path, rotated_path)?;
+        let rotated_path = generation_path(path, 1, false);
+        if copy_truncate {
+            // The writer appends, so it carries on at the start of the emptied file
+            fs::copy(path, &rotated_path)?;
+            OpenOptions::new().write(true).open(path)?.set_len(0)?;
+        } else {
+            fs::rename(path, &rotated_path)?;
+        }
 
+        Ok(Some(rotated_path).filter(|_| self.compress))
+    }
+
+    /// Rotate a log written by the service itself (Apache access/error, MariaDB) and
+    /// drop rotated files beyond `max_files`. Call it while the service is down and
+    /// doesn't hold the file open.
+    pub fn maintain_log(&self, path: &Path) -> std::io::Result<()> {
+        self.rotate_if_needed(path)?;
+        for (generation, _, old_path) in rotated_files(path) {
+            if generation >= self.max_files {
+                fs::remove_file(&old_path)?;
+            }
+        }
         Ok(())
     }
 
-    pub fn read_log_tail(&self, path: &Path, lines: usize) -> std::io::Result<Vec<String>> {
-        if !path.exists() {
-            return Ok(Vec::new());
+    /// Rotate a log a running service still holds open (copy, then truncate in place)
+    /// once it's over the size limit or its period ended
+    pub fn truncate_log_if_needed(&self, path: &Path) -> std::io::Result<()> {
+        if !self.needs_rotation(path)? {
+            return Ok(());
+        }
+        if let Some(rotated_path) = self.shift_generations(path, true)? {
+            compress_rotated(&rotated_path);
         }
+        Ok(())
+    }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let all_lines: Vec<String> = reader.lines().filter_map(Result::ok).collect();
+    /// Last `lines` lines of a log, continuing into rotated files (`stdout.1.log`,
+    /// `stdout.2.log.gz`, ...) when the current file was rotated recently
+    pub fn read_log_tail(&self, path: &Path, lines: usize) -> std::io::Result<Vec<String>> {
+        let mut tail: VecDeque<String> = VecDeque::with_capacity(lines);
+        let mut current = Some(path.to_path_buf()).filter(|p| p.exists());
+        let mut generation = 0;
+        while let Some(file_path) = current.filter(|_| tail.len() < lines) {
+            let file_lines: Vec<String> = open_log(&file_path)?.lines().map_while(Result::ok).collect();
+            let start = file_lines.len().saturating_sub(lines - tail.len());
+            for line in file_lines.into_iter().skip(start).rev() {
+                tail.push_front(line);
+            }
 
-        let start = if all_lines.len() > lines {
-            all_lines.len() - lines
-        } else {
-            0
-        };
+            generation += 1;
+            current = [false, true]
+                .into_iter()
+                .map(|compressed| generation_path(path, generation, compressed))
+                .find(|p| p.exists());
+        }
 
-        Ok(all_lines[start..].to_vec())
+        Ok(tail.into())
     }
 
     pub fn read_log_entries(&self, path: &Path, lines: usize) -> std::io::Result<Vec<LogEntry>> {
@@ -173,47 +414,233 @@ impl LogManager {
     }
 
     pub fn parse_log_line(&self, line: &str) -> LogEntry {
-        if let Some(timestamp_end) = line.find(']') {
-            let timestamp = line[1..timestamp_end].to_string();
-            let rest = &line[timestamp_end + 2..];
-
-            if rest.starts_with('[') {
-                if let Some(level_end) = rest.find(']') {
-                    let level = rest[1..level_end].to_string();
-                    let rest2 = &rest[level_end + 2..];
-
-                    if rest2.starts_with('[') {
-                        if let Some(source_end) = rest2.find(']') {
-                            let source = rest2[1..source_end].to_string();
-                            let message = rest2[source_end + 2..].to_string();
-
-                            return LogEntry {
-                                timestamp,
-                                level,
-                                source,
-                                message,
-                            };
+        LogEntry::from(LogParserRegistry::global().parse(line))
+    }
+
+    /// Log directories of every service and project that has written logs
+    pub fn list_sources(&self) -> Vec<LogSource> {
+        let subdirs = |dir: &Path| -> Vec<String> {
+            fs::read_dir(dir)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .filter(|e| e.path().is_dir())
+                        .filter_map(|e| e.file_name().to_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let services = subdirs(&self.base_path)
+            .into_iter()
+            .filter(|name| name != "projects")
+            .map(|name| LogSource { kind: LogSourceKind::Service, name });
+        let projects = subdirs(&self.base_path.join("projects"))
+            .into_iter()
+            .map(|name| LogSource { kind: LogSourceKind::Project, name });
+        services.chain(projects).collect()
+    }
+
+    pub fn source_dir(&self, source: &LogSource) -> PathBuf {
+        match source.kind {
+            LogSourceKind::Service => self.base_path.join(&source.name),
+            LogSourceKind::Project => self.base_path.join("projects").join(&source.name),
+        }
+    }
+
+    /// Search the logs (including rotated files) of the given sources, or of all of them.
+    /// Returns the newest matches first.
+    pub fn search_logs(&self, query: &LogSearchQuery) -> Result<Vec<LogSearchHit>, String> {
+        let pattern = query
+            .pattern
+            .as_deref()
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                RegexBuilder::new(p)
+                    .case_insensitive(!query.case_sensitive)
+                    .build()
+                    .map_err(|e| format!("Invalid pattern: {}", e))
+            })
+            .transpose()?;
+        let bound = |value: &Option<String>| -> Result<Option<DateTime<Utc>>, String> {
+            value
+                .as_deref()
+                .map(|v| {
+                    DateTime::parse_from_rfc3339(v)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .map_err(|e| format!("Invalid time '{}': {}", v, e))
+                })
+                .transpose()
+        };
+        let since = bound(&query.since)?;
+        let until = bound(&query.until)?;
+
+        for source in &query.sources {
+            source.check_name()?;
+        }
+        let sources = if query.sources.is_empty() {
+            self.list_sources()
+        } else {
+            query.sources.clone()
+        };
+
+        let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let registry = LogParserRegistry::global();
+        // Only the newest `limit` hits are kept; the heap's top is the oldest of them
+        let mut hits: BinaryHeap<Reverse<RankedHit>> = BinaryHeap::with_capacity(limit + 1);
+        let mut seq = 0;
+
+        for source in sources {
+            let Ok(entries) = fs::read_dir(self.source_dir(&source)) else {
+                continue;
+            };
+            for path in entries.flatten().map(|e| e.path()) {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let Some(log_type) = name
+                    .strip_suffix(".log.gz")
+                    .or_else(|| name.strip_suffix(".log"))
+                    .map(str::to_string)
+                else {
+                    continue;
+                };
+                let Ok(reader) = open_log(&path) else {
+                    continue;
+                };
+
+                for line in reader.lines().map_while(Result::ok) {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let parsed = registry.parse(&line);
+
+                    let level = parsed.level.unwrap_or(LogLevel::Info);
+                    if !query.levels.is_empty() && !query.levels.contains(&level) {
+                        continue;
+                    }
+                    if let Some(re) = &pattern {
+                        if !re.is_match(&parsed.message) {
+                            continue;
                         }
                     }
+                    let time = parsed.timestamp.as_deref().and_then(parse_timestamp);
+                    // Lines without a readable time can't satisfy a time range
+                    if since.is_some_and(|s| time.is_none_or(|t| t < s))
+                        || until.is_some_and(|u| time.is_none_or(|t| t > u))
+                    {
+                        continue;
+                    }
+
+                    seq += 1;
+                    let full = hits.len() >= limit;
+                    if full && hits.peek().is_some_and(|Reverse(oldest)| !oldest.ranks_below(time, seq)) {
+                        continue;
+                    }
+                    hits.push(Reverse(RankedHit {
+                        time,
+                        seq,
+                        hit: LogSearchHit {
+                            origin: source.clone(),
+                            log_type: log_type.clone(),
+                            format: parsed.format,
+                            entry: LogEntry::from(parsed),
+                        },
+                    }));
+                    if full {
+                        hits.pop();
+                    }
                 }
             }
+        }
 
-            return LogEntry {
-                timestamp,
-                level: "INFO".to_string(),
-                source: "system".to_string(),
-                message: rest.to_string(),
-            };
+        // Newest first; lines without a time go last
+        Ok(hits
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ranked)| ranked.hit)
+            .collect())
+    }
+
+    /// Delete a project's log files older than `retention_days` (0 keeps them forever)
+    pub fn cleanup_project_logs(&self, project_name: &str) -> std::io::Result<()> {
+        self.cleanup_source(&self.base_path.join("projects").join(project_name))
+    }
+
+    /// Delete a service's captured log files older than `retention_days` (0 keeps them forever)
+    pub fn cleanup_service_logs(&self, service_id: &str) -> std::io::Result<()> {
+        self.cleanup_source(&self.base_path.join(service_id))
+    }
+
+    fn cleanup_source(&self, dir: &Path) -> std::io::Result<()> {
+        if self.retention_days == 0 {
+            return Ok(());
         }
+        let cutoff = Utc::now() - chrono::Duration::days(self.retention_days as i64);
+        self.cleanup_directory(dir, cutoff)
+    }
+
+    /// Writer that persists one output stream of a project
+    pub fn project_log_writer(&self, project_name: &str, stream: &str) -> LogFileWriter {
+        self.log_writer(self.get_project_log_path(project_name, stream), stream)
+    }
 
-        LogEntry {
-            timestamp: Utc::now().to_rfc3339(),
-            level: "INFO".to_string(),
-            source: "system".to_string(),
-            message: line.to_string(),
+    /// Writer that persists one output stream to `path`
+    pub fn log_writer(&self, path: PathBuf, stream: &str) -> LogFileWriter {
+        LogFileWriter {
+            manager: self.clone(),
+            path,
+            stream: stream.to_string(),
+            file: None,
+            size: 0,
+            period: None,
+            alerts: None,
+            compression: None,
         }
     }
 
+    /// Log disk usage of every captured source, plus logs the services write themselves
+    /// (`native_logs`), largest first
+    pub fn disk_usage(&self, native_logs: &[(LogSource, PathBuf)]) -> Vec<LogDiskUsage> {
+        let mut usage: Vec<LogDiskUsage> = self
+            .list_sources()
+            .into_iter()
+            .map(|source| {
+                let (files, bytes) = fs::read_dir(self.source_dir(&source))
+                    .map(|entries| {
+                        entries
+                            .flatten()
+                            .filter_map(|e| e.metadata().ok())
+                            .filter(|m| m.is_file())
+                            .fold((0, 0), |(files, bytes), m| (files + 1, bytes + m.len()))
+                    })
+                    .unwrap_or_default();
+                LogDiskUsage { source, files, bytes }
+            })
+            .collect();
+
+        for (source, path) in native_logs {
+            let family = std::iter::once(path.clone())
+                .chain(rotated_files(path).into_iter().map(|(_, _, p)| p))
+                .filter_map(|p| fs::metadata(p).ok());
+            let (files, bytes) = family.fold((0, 0), |(files, bytes), m| (files + 1, bytes + m.len()));
+            if files == 0 {
+                continue;
+            }
+            match usage.iter_mut().find(|u| u.source == *source) {
+                Some(existing) => {
+                    existing.files += files;
+                    existing.bytes += bytes;
+                }
+                None => usage.push(LogDiskUsage { source: source.clone(), files, bytes }),
+            }
+        }
+
+        usage.sort_by_key(|u| std::cmp::Reverse(u.bytes));
+        usage
+    }
+
     pub fn cleanup_old_logs(&self) -> std::io::Result<()> {
         let cutoff = Utc::now() - chrono::Duration::days(self.retention_days as i64);
 
@@ -233,7 +660,7 @@ impl LogManager {
 
             if path.is_dir() {
                 self.cleanup_directory(&path, cutoff)?;
-            } else if path.extension().map_or(false, |ext| ext == "log") {
+            } else if path.extension().is_some_and(|ext| ext == "log" || ext == "gz") {
                 let metadata = fs::metadata(&path)?;
                 if let Ok(modified) = metadata.modified() {
                     let modified: DateTime<Utc> = modified.into();
@@ -262,28 +689,342 @@ impl LogManager {
             Ok(0)
         }
     }
+}
 
-    /// Append a raw line to a log file (static method for use from threads)
-    pub fn append_line_to_file(path: &Path, line: &str) -> std::io::Result<()> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
+impl Default for LogManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a human-readable size such as "50MB", "512 KB" or "1G" into bytes
+pub fn parse_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1024,
+        "M" | "MB" | "MIB" => 1024 * 1024,
+        "G" | "GB" | "GIB" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as u64).filter(|bytes| *bytes > 0)
+}
+
+/// `stdout.log` -> `stdout.2.log`, or `stdout.2.log.gz` when compressed
+fn generation_path(path: &Path, generation: u32, compressed: bool) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().unwrap_or_default().to_string_lossy();
+    let name = format!("{}.{}.{}{}", stem, generation, ext, if compressed { ".gz" } else { "" });
+    path.with_file_name(name)
+}
+
+/// Rotated files of a log as `(generation, compressed, path)`, in no particular order
+fn rotated_files(path: &Path) -> Vec<(u32, bool, PathBuf)> {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().unwrap_or_default().to_string_lossy();
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let Ok(entries) = fs::read_dir(parent) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let rest = name.strip_prefix(stem.as_ref())?.strip_prefix('.')?;
+            let (rest, compressed) = match rest.strip_suffix(".gz") {
+                Some(rest) => (rest, true),
+                None => (rest, false),
+            };
+            let generation = rest.strip_suffix(ext.as_ref())?.strip_suffix('.')?.parse().ok()?;
+            Some((generation, compressed, entry.path()))
+        })
+        .collect()
+}
+
+/// Replace a rotated file with a gzipped copy (`stdout.1.log` -> `stdout.1.log.gz`)
+fn compress_file(path: &Path) -> std::io::Result<()> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".gz");
+    let target = path.with_file_name(name);
+
+    let mut encoder = GzEncoder::new(File::create(&target)?, Compression::default());
+    let result = std::io::copy(&mut File::open(path)?, &mut encoder).and_then(|_| encoder.finish());
+    if let Err(e) = result {
+        let _ = fs::remove_file(&target);
+        return Err(e);
+    }
+    fs::remove_file(path)
+}
+
+/// Compress a freshly rotated file; a failure leaves the plain file in place
+fn compress_rotated(path: &Path) {
+    if let Err(e) = compress_file(path) {
+        eprintln!("Failed to compress {}: {}", path.display(), e);
+    }
+}
+
+/// Line reader for a log, decompressing `.gz` files
+fn open_log(path: &Path) -> std::io::Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        Ok(Box::new(BufReader::new(GzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Appends captured process output to a log file as `[ts] [stream] line`,
+/// rotating the file once it reaches the manager's size limit or its period ends
+pub struct LogFileWriter {
+    manager: LogManager,
+    path: PathBuf,
+    stream: String,
+    file: Option<File>,
+    size: u64,
+    /// Rotation period the open file belongs to
+    period: Option<String>,
+    /// Alert rules every written line is checked against
+    alerts: Option<(LogSource, SharedLogAlerts)>,
+    /// Background gzip of the last rotated file, so the output pipe isn't stalled
+    compression: Option<JoinHandle<()>>,
+}
+
+impl LogFileWriter {
+    /// Check written lines against the alert rules of `origin`
+    pub fn with_alerts(mut self, origin: LogSource, alerts: Option<SharedLogAlerts>) -> Self {
+        self.alerts = alerts.map(|alerts| (origin, alerts));
+        self
+    }
+
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        if let Some((origin, alerts)) = &self.alerts {
+            check_log_alerts(alerts, origin, line);
         }
 
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(path)?;
+        let now = Local::now();
+        let period = self.manager.rotation_policy.period(now);
+        if self.file.is_some() && (self.size >= self.manager.max_file_size || period != self.period) {
+            // Close the handle first; Windows can't rename an open file
+            self.file = None;
+            self.rotate()?;
+        }
 
-        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
-        writeln!(file, "[{}] {}", timestamp, line)?;
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => {
+                if let Some(parent) = self.path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                if self.manager.needs_rotation(&self.path)? {
+                    self.rotate()?;
+                }
+                let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+                self.size = file.metadata()?.len();
+                self.period = period;
+                self.file.insert(file)
+            }
+        };
 
+        let timestamp = now.format("%Y-%m-%d %H:%M:%S%.3f");
+        let text = format!("[{}] [{}] {}\n", timestamp, self.stream, line);
+        file.write_all(text.as_bytes())?;
+        self.size += text.len() as u64;
         Ok(())
     }
+
+    /// Rotate the closed log, compressing the rotated file on a background thread
+    fn rotate(&mut self) -> std::io::Result<()> {
+        // The previous generation must be fully compressed before it's shifted
+        self.finish_compression();
+        if let Some(rotated_path) = self.manager.shift_generations(&self.path, false)? {
+            self.compression = Some(std::thread::spawn(move || compress_rotated(&rotated_path)));
+        }
+        Ok(())
+    }
+
+    fn finish_compression(&mut self) {
+        if let Some(compression) = self.compression.take() {
+            let _ = compression.join();
+        }
+    }
 }
 
-impl Default for LogManager {
+impl Drop for LogFileWriter {
+    fn drop(&mut self) {
+        self.finish_compression();
+    }
+}
+
+/// Seconds a rule stays quiet for a source after firing, unless it sets its own
+const DEFAULT_ALERT_COOLDOWN_SECS: u64 = 300;
+
+/// User-defined rule raising an alert when a matching line is logged,
+/// e.g. "ERROR in mariadb" or "/Unhandled promise rejection/ in api-project"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LogAlertRule {
+    pub id: String,
+    pub name: String,
+    /// Project or service watched; `None` watches every source
+    pub source: Option<LogSource>,
+    /// Regex matched against the message
+    pub pattern: Option<String>,
+    /// Lowest level that fires; with a pattern, both must match
+    pub min_level: Option<LogLevel>,
+    /// Seconds before the rule fires again for the same source
+    pub cooldown_secs: u64,
+    pub enabled: bool,
+}
+
+impl Default for LogAlertRule {
     fn default() -> Self {
-        Self::new()
+        Self {
+            id: String::new(),
+            name: String::new(),
+            source: None,
+            pattern: None,
+            min_level: None,
+            cooldown_secs: DEFAULT_ALERT_COOLDOWN_SECS,
+            enabled: true,
+        }
+    }
+}
+
+/// Payload of the `log-alert` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogAlert {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub origin: LogSource,
+    pub level: LogLevel,
+    pub message: String,
+    pub timestamp: String,
+    /// Alerts raised since they were last cleared
+    pub unread: u32,
+}
+
+/// Called for every alert raised, outside the engine's lock
+pub type LogAlertHandler = Arc<dyn Fn(&LogAlert) + Send + Sync>;
+
+/// Evaluates alert rules against logged lines
+#[derive(Default)]
+pub struct LogAlertEngine {
+    rules: Vec<(LogAlertRule, Option<Regex>)>,
+    /// Last time each rule fired, per source
+    last_fired: HashMap<(String, LogSource), Instant>,
+    unread: u32,
+    handler: Option<LogAlertHandler>,
+}
+
+impl LogAlertEngine {
+    /// Replace the rules; fails if a pattern doesn't compile or a rule matches nothing
+    pub fn set_rules(&mut self, rules: Vec<LogAlertRule>) -> Result<(), String> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let pattern = rule.pattern.as_deref().filter(|p| !p.is_empty());
+            if pattern.is_none() && rule.min_level.is_none() {
+                return Err(format!("Alert rule '{}' needs a pattern or a level", rule.name));
+            }
+            let regex = pattern
+                .map(Regex::new)
+                .transpose()
+                .map_err(|e| format!("Invalid pattern in alert rule '{}': {}", rule.name, e))?;
+            compiled.push((rule, regex));
+        }
+
+        self.last_fired
+            .retain(|(rule_id, _), _| compiled.iter().any(|(rule, _)| rule.id == *rule_id));
+        self.rules = compiled;
+        Ok(())
+    }
+
+    pub fn rules(&self) -> Vec<LogAlertRule> {
+        self.rules.iter().map(|(rule, _)| rule.clone()).collect()
+    }
+
+    pub fn set_handler(&mut self, handler: LogAlertHandler) {
+        self.handler = Some(handler);
+    }
+
+    pub fn unread(&self) -> u32 {
+        self.unread
+    }
+
+    pub fn clear_unread(&mut self) {
+        self.unread = 0;
+    }
+
+    /// Alerts `line` of `origin` raises at `now`, honouring each rule's cooldown
+    pub fn evaluate(&mut self, origin: &LogSource, line: &str, now: Instant) -> Vec<LogAlert> {
+        let applies = |rule: &LogAlertRule| rule.enabled && rule.source.as_ref().is_none_or(|s| s == origin);
+        // Most lines meet no rule; skip parsing them
+        if !self.rules.iter().any(|(rule, _)| applies(rule)) {
+            return Vec::new();
+        }
+
+        let parsed = LogParserRegistry::global().parse(line);
+        let level = parsed.level.unwrap_or(LogLevel::Info);
+        let mut alerts = Vec::new();
+        for (rule, regex) in &self.rules {
+            if !applies(rule)
+                || rule.min_level.is_some_and(|min| level < min)
+                || regex.as_ref().is_some_and(|re| !re.is_match(&parsed.message))
+            {
+                continue;
+            }
+
+            let key = (rule.id.clone(), origin.clone());
+            let cooldown = Duration::from_secs(rule.cooldown_secs);
+            if self.last_fired.get(&key).is_some_and(|last| now.duration_since(*last) < cooldown) {
+                continue;
+            }
+            self.last_fired.insert(key, now);
+            self.unread += 1;
+
+            alerts.push(LogAlert {
+                rule_id: rule.id.clone(),
+                rule_name: rule.name.clone(),
+                origin: origin.clone(),
+                level,
+                message: parsed.message.clone(),
+                timestamp: Utc::now().to_rfc3339(),
+                unread: self.unread,
+            });
+        }
+        alerts
+    }
+}
+
+/// Locked from the output reader threads, so a std mutex rather than tokio's
+pub type SharedLogAlerts = Arc<StdMutex<LogAlertEngine>>;
+
+pub fn init_log_alerts(rules: Vec<LogAlertRule>) -> SharedLogAlerts {
+    let mut engine = LogAlertEngine::default();
+    if let Err(e) = engine.set_rules(rules) {
+        eprintln!("Ignoring saved log alert rules: {}", e);
+    }
+    Arc::new(StdMutex::new(engine))
+}
+
+/// Check a logged line against the alert rules and hand any alert to the handler
+pub fn check_log_alerts(alerts: &SharedLogAlerts, origin: &LogSource, line: &str) {
+    let (raised, handler) = {
+        let Ok(mut engine) = alerts.lock() else {
+            return;
+        };
+        (engine.evaluate(origin, line, Instant::now()), engine.handler.clone())
+    };
+    if let Some(handler) = handler {
+        for alert in &raised {
+            handler(alert);
+        }
     }
 }
 
@@ -295,10 +1036,15 @@ pub struct LogUpdatePayload {
     pub entries: Vec<LogEntry>,
 }
 
+/// How long a merged tail waits for the webview to acknowledge a batch
+const TAIL_ACK_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Manages active log file watchers
 pub struct LogStreamManager {
     /// Active watchers by source ID (service/project id + log type)
     active_streams: HashMap<String, mpsc::Sender<()>>,
+    /// Acknowledgements of merged tails by stream ID
+    tail_acks: HashMap<String, Arc<Notify>>,
     /// Log manager reference
     log_manager: LogManager,
 }
@@ -307,10 +1053,15 @@ impl LogStreamManager {
     pub fn new(log_manager: LogManager) -> Self {
         Self {
             active_streams: HashMap::new(),
+            tail_acks: HashMap::new(),
             log_manager,
         }
     }
 
+    pub fn log_manager(&self) -> &LogManager {
+        &self.log_manager
+    }
+
     /// Start watching a log file for changes
     pub fn start_stream<F>(
         &mut self,
@@ -365,8 +1116,63 @@ impl LogStreamManager {
         true
     }
 
+    /// Follow several logs as one time-ordered stream. After each batch the task waits
+    /// for `ack_stream` (or `TAIL_ACK_TIMEOUT`) so a busy webview isn't flooded.
+    pub fn start_merged_stream<F>(&mut self, stream_id: String, mut tail: MergedTail, on_batch: F) -> bool
+    where
+        F: Fn(Vec<LogSearchHit>, usize) + Send + Sync + 'static,
+    {
+        if self.active_streams.contains_key(&stream_id) {
+            return false;
+        }
+
+        let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
+        let ack = Arc::new(Notify::new());
+        self.active_streams.insert(stream_id.clone(), stop_tx);
+        self.tail_acks.insert(stream_id, ack.clone());
+
+        tokio::spawn(async move {
+            let mut poll_interval = interval(Duration::from_millis(500));
+
+            loop {
+                tokio::select! {
+                    _ = poll_interval.tick() => {}
+                    _ = stop_rx.recv() => break,
+                }
+
+                // Send what is due, one acknowledged batch at a time
+                loop {
+                    let lines = tail.poll();
+                    if lines.is_empty() {
+                        break;
+                    }
+                    on_batch(lines, tail.pending());
+                    tokio::select! {
+                        _ = ack.notified() => {}
+                        _ = sleep(TAIL_ACK_TIMEOUT) => {}
+                        _ = stop_rx.recv() => return,
+                    }
+                }
+            }
+        });
+
+        true
+    }
+
+    /// Let a merged stream send its next batch
+    pub fn ack_stream(&self, stream_id: &str) -> bool {
+        match self.tail_acks.get(stream_id) {
+            Some(ack) => {
+                ack.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Stop watching a log file
     pub fn stop_stream(&mut self, source_id: &str) -> bool {
+        self.tail_acks.remove(source_id);
         if let Some(stop_tx) = self.active_streams.remove(source_id) {
             // Send stop signal (ignore if receiver is dropped)
             let _ = stop_tx.try_send(());
@@ -383,6 +1189,7 @@ impl LogStreamManager {
 
     /// Stop all active streams
     pub fn stop_all(&mut self) {
+        self.tail_acks.clear();
         for (_, stop_tx) in self.active_streams.drain() {
             let _ = stop_tx.try_send(());
         }
@@ -444,3 +1251,255 @@ impl LogStreamManager {
 
 /// Thread-safe wrapper for LogStreamManager
 pub type SharedLogStreamManager = Arc<RwLock<LogStreamManager>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_logs_across_sources() {
+        let base = std::env::temp_dir().join(format!("devport-log-search-{}", std::process::id()));
+        let manager = LogManager::with_base_path(base.clone());
+        fs::create_dir_all(base.join("mariadb")).unwrap();
+        fs::create_dir_all(base.join("projects").join("shop")).unwrap();
+        fs::write(
+            base.join("mariadb").join("stderr.log"),
+            "[2024-01-15 10:00:00.000] 2024-01-15 10:00:00 0 [Note] Server socket created\n\
+             [2024-01-15 10:00:01.000] 2024-01-15 10:00:01 0 [ERROR] Can't open the mysql.plugin table\n",
+        )
+        .unwrap();
+        fs::write(
+            base.join("projects").join("shop").join("stdout.log"),
+            "[2024-01-15 11:00:00.000] {\"level\":50,\"msg\":\"cannot open cart\"}\n\
+             [2024-01-15 11:00:01.000] ready in 300ms\n",
+        )
+        .unwrap();
+
+        let errors = manager
+            .search_logs(&LogSearchQuery {
+                levels: vec![LogLevel::Error],
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(errors.len(), 2);
+        // Newest first
+        assert_eq!(errors[0].origin.kind, LogSourceKind::Project);
+        assert_eq!(errors[0].format, LogFormat::JsonLines);
+        assert_eq!(errors[1].log_type, "stderr");
+
+        let matched = manager
+            .search_logs(&LogSearchQuery {
+                pattern: Some("CAN(NOT|'T) OPEN".to_string()),
+                sources: vec![LogSource { kind: LogSourceKind::Service, name: "mariadb".to_string() }],
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].entry.level, "ERROR");
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_search_logs_keeps_newest_hits_and_rejects_paths() {
+        let base = std::env::temp_dir().join(format!("devport-log-limit-{}", std::process::id()));
+        let manager = LogManager::with_base_path(base.clone());
+        fs::create_dir_all(base.join("apache")).unwrap();
+        let lines: String = (0..50)
+            .map(|i| format!("[2024-01-15 10:00:{:02}.000] request {}\n", i, i))
+            .collect();
+        fs::write(base.join("apache").join("stdout.log"), lines).unwrap();
+
+        let newest = manager
+            .search_logs(&LogSearchQuery {
+                limit: Some(3),
+                ..Default::default()
+            })
+            .unwrap();
+        let messages: Vec<&str> = newest.iter().map(|h| h.entry.message.as_str()).collect();
+        assert_eq!(messages, vec!["request 49", "request 48", "request 47"]);
+
+        let escape = manager.search_logs(&LogSearchQuery {
+            sources: vec![LogSource { kind: LogSourceKind::Project, name: "../..".to_string() }],
+            ..Default::default()
+        });
+        assert!(escape.is_err());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_project_output_rotates_and_tails_across_files() {
+        let base = std::env::temp_dir().join(format!("devport-log-writer-{}", std::process::id()));
+        let mut manager = LogManager::with_base_path(base.clone());
+        manager.max_file_size = 200;
+        manager.max_files = 3;
+
+        let mut writer = manager.project_log_writer("api", "stderr");
+        for i in 0..20 {
+            writer.write_line(&format!("line {:02} of captured output", i)).unwrap();
+        }
+        // Waits for the last rotated file to be compressed
+        drop(writer);
+
+        let path = manager.get_project_log_path("api", "stderr");
+        let dir = path.parent().unwrap();
+        // Current file plus max_files - 1 rotated ones
+        assert_eq!(fs::read_dir(dir).unwrap().count(), 3);
+        assert!(fs::metadata(&path).unwrap().len() <= 200 + 64);
+
+        let tail = manager.read_log_tail(&path, 8).unwrap();
+        assert_eq!(tail.len(), 8);
+        assert!(tail[7].ends_with("[stderr] line 19 of captured output"));
+        assert!(tail[0].ends_with("line 12 of captured output"));
+
+        let entry = manager.parse_log_line(&tail[7]);
+        assert_eq!(entry.source, "stderr");
+        assert_eq!(entry.message, "line 19 of captured output");
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_rotation_compresses_and_prunes_generations() {
+        let base = std::env::temp_dir().join(format!("devport-log-gzip-{}", std::process::id()));
+        let mut manager = LogManager::with_base_path(base.clone());
+        manager.max_file_size = 100;
+        manager.max_files = 3;
+
+        let path = manager.get_log_path("apache", "error");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // Left over from a larger max_files
+        fs::write(generation_path(&path, 7, true), "stale").unwrap();
+        for i in 0..4 {
+            fs::write(&path, format!("{}\n", "x".repeat(120 + i))).unwrap();
+            manager.maintain_log(&path).unwrap();
+        }
+
+        let mut generations: Vec<(u32, bool)> =
+            rotated_files(&path).into_iter().map(|(g, c, _)| (g, c)).collect();
+        generations.sort();
+        assert_eq!(generations, vec![(1, true), (2, true)]);
+        assert!(!path.exists());
+
+        let tail = manager.read_log_tail(&generation_path(&path, 1, true), 1).unwrap();
+        assert_eq!(tail, vec!["x".repeat(123)]);
+
+        let usage = manager.disk_usage(&[]);
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].files, 2);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_truncate_rotation_keeps_the_writer_appending() {
+        let base = std::env::temp_dir().join(format!("devport-log-truncate-{}", std::process::id()));
+        let mut manager = LogManager::with_base_path(base.clone());
+        manager.max_file_size = 100;
+        manager.max_files = 3;
+
+        let path = manager.get_log_path("mariadb", "error");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // Held open by the "service" across the rotation
+        let mut service_log = OpenOptions::new().create(true).append(true).open(&path).unwrap();
+        writeln!(service_log, "{}", "x".repeat(40)).unwrap();
+        manager.truncate_log_if_needed(&path).unwrap();
+        assert!(rotated_files(&path).is_empty());
+
+        writeln!(service_log, "{}", "y".repeat(80)).unwrap();
+        manager.truncate_log_if_needed(&path).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+
+        writeln!(service_log, "after rotation").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "after rotation\n");
+        let tail = manager.read_log_tail(&path, 2).unwrap();
+        assert_eq!(tail, vec!["y".repeat(80), "after rotation".to_string()]);
+        assert!(generation_path(&path, 1, true).exists());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_daily_policy_rotates_previous_days_log() {
+        let base = std::env::temp_dir().join(format!("devport-log-daily-{}", std::process::id()));
+        let mut manager = LogManager::with_base_path(base.clone());
+        manager.rotation_policy = RotationPolicy::parse("daily");
+        manager.compress = false;
+
+        let path = manager.get_project_log_path("web", "stdout");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "yesterday\n").unwrap();
+        let yesterday = std::time::SystemTime::now() - std::time::Duration::from_secs(26 * 3600);
+        File::options().write(true).open(&path).unwrap().set_modified(yesterday).unwrap();
+
+        let mut writer = manager.project_log_writer("web", "stdout");
+        writer.write_line("today").unwrap();
+
+        assert_eq!(fs::read_to_string(generation_path(&path, 1, false)).unwrap(), "yesterday\n");
+        let tail = manager.read_log_tail(&path, 5).unwrap();
+        assert_eq!(tail.len(), 2);
+        assert!(tail[1].ends_with("[stdout] today"));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_alert_rules_match_and_cool_down() {
+        let mariadb = LogSource { kind: LogSourceKind::Service, name: "mariadb".to_string() };
+        let api = LogSource { kind: LogSourceKind::Project, name: "api".to_string() };
+        let mut engine = LogAlertEngine::default();
+        engine
+            .set_rules(vec![
+                LogAlertRule {
+                    id: "db-errors".to_string(),
+                    name: "ERROR in mariadb".to_string(),
+                    source: Some(mariadb.clone()),
+                    min_level: Some(LogLevel::Error),
+                    cooldown_secs: 60,
+                    ..Default::default()
+                },
+                LogAlertRule {
+                    id: "rejections".to_string(),
+                    name: "Unhandled rejection".to_string(),
+                    pattern: Some("Unhandled promise rejection".to_string()),
+                    cooldown_secs: 0,
+                    ..Default::default()
+                },
+            ])
+            .unwrap();
+        assert!(engine.set_rules(vec![LogAlertRule::default()]).is_err());
+
+        let start = Instant::now();
+        let error = "2024-01-15 10:00:01 0 [ERROR] Can't open the mysql.plugin table";
+        let note = "2024-01-15 10:00:00 0 [Note] Server socket created";
+        assert!(engine.evaluate(&mariadb, note, start).is_empty());
+        assert!(engine.evaluate(&api, error, start).is_empty());
+
+        let fired = engine.evaluate(&mariadb, error, start);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].rule_id, "db-errors");
+        assert_eq!(fired[0].level, LogLevel::Error);
+        // Within the cooldown
+        assert!(engine.evaluate(&mariadb, error, start + Duration::from_secs(30)).is_empty());
+        assert_eq!(engine.evaluate(&mariadb, error, start + Duration::from_secs(61)).len(), 1);
+
+        let rejection = "(node:4242) Unhandled promise rejection: TypeError";
+        assert_eq!(engine.evaluate(&api, rejection, start).len(), 1);
+        let again = engine.evaluate(&api, rejection, start);
+        assert_eq!(again[0].unread, 4);
+
+        engine.clear_unread();
+        assert_eq!(engine.unread(), 0);
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("50MB"), Some(50 * 1024 * 1024));
+        assert_eq!(parse_size("512 kb"), Some(512 * 1024));
+        assert_eq!(parse_size("1.5G"), Some(3 * 512 * 1024 * 1024));
+        assert_eq!(parse_size("100"), Some(100));
+        assert_eq!(parse_size("lots"), None);
+        assert_eq!(parse_size("0MB"), None);
+    }
+}
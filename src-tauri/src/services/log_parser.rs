@@ -0,0 +1,511 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Normalized severity; serialized the way `LogEntry.level` has always been written
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Map the level names used by Apache, MariaDB, Monolog, Python, pino, ...
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "trace" | "debug" | "verbose" | "silly" => Some(LogLevel::Debug),
+            "info" | "information" | "notice" | "note" | "system" | "http" => Some(LogLevel::Info),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "error" | "err" | "fatal" | "critical" | "crit" | "alert" | "emerg" | "emergency"
+            | "panic" => Some(LogLevel::Error),
+            // Apache trace1..trace8
+            other if other.starts_with("trace") => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
+
+    /// pino/bunyan numeric levels
+    fn from_number(level: u64) -> Self {
+        match level {
+            0..=20 => LogLevel::Debug,
+            21..=30 => LogLevel::Info,
+            31..=40 => LogLevel::Warn,
+            _ => LogLevel::Error,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+/// Which parser recognised a line
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    /// `[ts] [LEVEL] [source] message`, written by `LogManager::write_log_entry`
+    DevPort,
+    JsonLines,
+    ApacheError,
+    ApacheAccess,
+    Mariadb,
+    Laravel,
+    Python,
+    Plain,
+}
+
+/// Fields extracted from one log line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedLine {
+    pub format: LogFormat,
+    /// Timestamp as written in the line
+    pub timestamp: Option<String>,
+    pub level: Option<LogLevel>,
+    /// Logger, module or channel name
+    pub source: Option<String>,
+    pub message: String,
+}
+
+/// A log format that can be recognised line by line
+pub trait LogParser: Send + Sync {
+    /// Parse `line`, or `None` if it isn't in this format
+    fn parse(&self, line: &str) -> Option<ParsedLine>;
+}
+
+/// Ordered list of parsers; the first that recognises a line wins
+pub struct LogParserRegistry {
+    parsers: Vec<Box<dyn LogParser>>,
+}
+
+impl LogParserRegistry {
+    pub fn new() -> Self {
+        Self { parsers: Vec::new() }
+    }
+
+    /// All built-in formats, most specific first
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(DevPortParser));
+        registry.register(Box::new(JsonLinesParser));
+        registry.register(Box::new(LaravelParser));
+        registry.register(Box::new(ApacheErrorParser));
+        registry.register(Box::new(MariadbParser));
+        registry.register(Box::new(PythonParser));
+        registry.register(Box::new(ApacheAccessParser));
+        registry
+    }
+
+    /// Shared registry of the built-in formats
+    pub fn global() -> &'static Self {
+        static REGISTRY: OnceLock<LogParserRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(Self::with_builtin)
+    }
+
+    pub fn register(&mut self, parser: Box<dyn LogParser>) {
+        self.parsers.push(parser);
+    }
+
+    /// Parse a line. Lines captured from process output carry a `[ts] ` or
+    /// `[ts] [stream] ` prefix added by `LogManager`; the text after it is parsed
+    /// too, keeping the prefix time and stream when the text has none of its own.
+    pub fn parse(&self, line: &str) -> ParsedLine {
+        if let Some(parsed) = self.parse_raw(line) {
+            return parsed;
+        }
+
+        if let Some(caps) = capture_prefix_regex().captures(line) {
+            let rest = &line[caps.get(0).map_or(0, |m| m.end())..];
+            let mut parsed = self.parse_raw(rest).unwrap_or_else(|| plain(rest));
+            if parsed.timestamp.is_none() {
+                parsed.timestamp = Some(caps[1].to_string());
+            }
+            if parsed.source.is_none() {
+                parsed.source = caps.get(2).map(|m| m.as_str().to_string());
+            }
+            return parsed;
+        }
+
+        plain(line)
+    }
+
+    fn parse_raw(&self, line: &str) -> Option<ParsedLine> {
+        self.parsers.iter().find_map(|p| p.parse(line))
+    }
+}
+
+impl Default for LogParserRegistry {
+    fn default() -> Self {
+        Self::with_builtin()
+    }
+}
+
+/// Keyword heuristic for lines in no known format
+pub fn detect_level(line: &str) -> Option<LogLevel> {
+    let lower = line.to_lowercase();
+    if lower.contains("error") || lower.contains("fatal") || lower.contains("fail") || lower.contains("crit") {
+        Some(LogLevel::Error)
+    } else if lower.contains("warn") {
+        Some(LogLevel::Warn)
+    } else if lower.contains("note") || lower.contains("notice") || lower.contains("info") || lower.contains("ready for connections") {
+        Some(LogLevel::Info)
+    } else {
+        None
+    }
+}
+
+fn plain(line: &str) -> ParsedLine {
+    ParsedLine {
+        format: LogFormat::Plain,
+        timestamp: None,
+        level: detect_level(line),
+        source: None,
+        message: line.to_string(),
+    }
+}
+
+/// Parse any of the timestamp styles the parsers extract
+pub fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    // Apache access log
+    if let Ok(dt) = DateTime::parse_from_str(raw, "%d/%b/%Y:%H:%M:%S %z") {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    // Python writes milliseconds after a comma; old MariaDB pads the hour with spaces
+    let normalized = raw.replace(',', ".");
+    let normalized = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
+    const LOCAL_FORMATS: [&str; 5] = [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%a %b %d %H:%M:%S%.f %Y",
+        "%y%m%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ];
+    LOCAL_FORMATS.iter().find_map(|format| {
+        let naive = NaiveDateTime::parse_from_str(&normalized, format).ok()?;
+        Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+    })
+}
+
+fn capture_prefix_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^\[(\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}(?:\.\d+)?)\] (?:\[(stdout|stderr)\] )?").unwrap()
+    })
+}
+
+/// `[ts] [LEVEL] [source] message`
+struct DevPortParser;
+
+impl LogParser for DevPortParser {
+    fn parse(&self, line: &str) -> Option<ParsedLine> {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        let re = RE.get_or_init(|| {
+            Regex::new(r"^\[(\d[^\]]*)\] \[([A-Z]+)\] \[([^\]]*)\] ?(.*)$").unwrap()
+        });
+        let caps = re.captures(line)?;
+        let level = LogLevel::from_name(&caps[2])?;
+        Some(ParsedLine {
+            format: LogFormat::DevPort,
+            timestamp: Some(caps[1].to_string()),
+            level: Some(level),
+            source: Some(caps[3].to_string()),
+            message: caps[4].to_string(),
+        })
+    }
+}
+
+/// One JSON object per line: pino, winston, bunyan, structlog, Monolog's JsonFormatter
+struct JsonLinesParser;
+
+impl JsonLinesParser {
+    fn field<'a>(object: &'a serde_json::Map<String, Value>, keys: &[&str]) -> Option<&'a Value> {
+        keys.iter().find_map(|key| object.get(*key)).filter(|v| !v.is_null())
+    }
+}
+
+impl LogParser for JsonLinesParser {
+    fn parse(&self, line: &str) -> Option<ParsedLine> {
+        let trimmed = line.trim();
+        if !trimmed.starts_with('{') {
+            return None;
+        }
+        let value: Value = serde_json::from_str(trimmed).ok()?;
+        let object = value.as_object()?;
+
+        let level = match Self::field(object, &["level", "severity", "levelname", "level_name", "log.level"]) {
+            Some(Value::Number(n)) => n.as_u64().map(LogLevel::from_number),
+            Some(Value::String(s)) => LogLevel::from_name(s),
+            _ => None,
+        };
+
+        let timestamp = match Self::field(object, &["time", "timestamp", "@timestamp", "ts", "datetime"]) {
+            // pino writes epoch milliseconds
+            Some(Value::Number(n)) => n
+                .as_i64()
+                .and_then(DateTime::from_timestamp_millis)
+                .map(|dt| dt.to_rfc3339()),
+            Some(Value::String(s)) => Some(s.clone()),
+            _ => None,
+        };
+
+        let message = match Self::field(object, &["msg", "message", "event"]) {
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => trimmed.to_string(),
+        };
+
+        let source = Self::field(object, &["name", "logger", "channel", "context"])
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        Some(ParsedLine {
+            format: LogFormat::JsonLines,
+            timestamp,
+            level,
+            source,
+            message,
+        })
+    }
+}
+
+/// `[2024-01-15 10:23:45] local.ERROR: message {"exception":...}`
+struct LaravelParser;
+
+impl LogParser for LaravelParser {
+    fn parse(&self, line: &str) -> Option<ParsedLine> {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        let re = RE.get_or_init(|| {
+            Regex::new(r"^\[(\d{4}-\d{2}-\d{2}[ T][0-9:.+\-]+)\] ([\w-]+)\.([A-Z]+): (.*)$").unwrap()
+        });
+        let caps = re.captures(line)?;
+        Some(ParsedLine {
+            format: LogFormat::Laravel,
+            timestamp: Some(caps[1].to_string()),
+            level: LogLevel::from_name(&caps[3]),
+            source: Some(caps[2].to_string()),
+            message: caps[4].to_string(),
+        })
+    }
+}
+
+/// `[Wed Oct 11 14:32:52.123456 2000] [core:error] [pid 1234:tid 5678] [client 127.0.0.1:5555] message`
+struct ApacheErrorParser;
+
+impl LogParser for ApacheErrorParser {
+    fn parse(&self, line: &str) -> Option<ParsedLine> {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        let re = RE.get_or_init(|| {
+            Regex::new(
+                r"^\[(\w{3} \w{3} +\d{1,2} [\d:.]+ \d{4})\] \[(?:([\w-]+):)?([a-z]+\d?)\](?: \[pid [^\]]*\])?(?: \[client [^\]]*\])? (.*)$",
+            )
+            .unwrap()
+        });
+        let caps = re.captures(line)?;
+        Some(ParsedLine {
+            format: LogFormat::ApacheError,
+            timestamp: Some(caps[1].to_string()),
+            level: LogLevel::from_name(&caps[3]),
+            source: caps.get(2).map(|m| m.as_str().to_string()),
+            message: caps[4].to_string(),
+        })
+    }
+}
+
+/// `2024-01-15 10:23:45 0 [Note] InnoDB: ...`, `2024-01-15T10:23:45.123456Z 0 [System] [MY-010116] [Server] ...`
+/// and the old `240115 10:23:45 [ERROR] ...`
+struct MariadbParser;
+
+impl LogParser for MariadbParser {
+    fn parse(&self, line: &str) -> Option<ParsedLine> {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        let re = RE.get_or_init(|| {
+            Regex::new(
+                r"^(\d{4}-\d{2}-\d{2}[ T]\s*\d{1,2}:\d{2}:\d{2}(?:\.\d+)?Z?|\d{6}\s+\d{1,2}:\d{2}:\d{2})(?: +\d+)? \[(Note|Warning|ERROR|Error|System)\] (?:\[MY-\d+\] \[(\w+)\] )?(.*)$",
+            )
+            .unwrap()
+        });
+        let caps = re.captures(line)?;
+        let message = caps[4].to_string();
+        // "InnoDB: ..." and friends name the subsystem
+        let source = caps.get(3).map(|m| m.as_str().to_string()).or_else(|| {
+            message
+                .split_once(": ")
+                .filter(|(name, _)| !name.contains(' '))
+                .map(|(name, _)| name.to_string())
+        });
+        Some(ParsedLine {
+            format: LogFormat::Mariadb,
+            timestamp: Some(caps[1].to_string()),
+            level: LogLevel::from_name(&caps[2]),
+            source,
+            message,
+        })
+    }
+}
+
+/// Python `logging` defaults: `WARNING:root:message` (basicConfig),
+/// `2024-01-15 10:23:45,123 - app - INFO - message` and uvicorn's `INFO:     message`
+struct PythonParser;
+
+impl LogParser for PythonParser {
+    fn parse(&self, line: &str) -> Option<ParsedLine> {
+        static TIMED: OnceLock<Regex> = OnceLock::new();
+        static BASIC: OnceLock<Regex> = OnceLock::new();
+        let timed = TIMED.get_or_init(|| {
+            Regex::new(r"^(\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2},\d{3}) - (\S+) - (DEBUG|INFO|WARNING|ERROR|CRITICAL) - (.*)$").unwrap()
+        });
+        let basic = BASIC.get_or_init(|| {
+            Regex::new(r"^(DEBUG|INFO|WARNING|ERROR|CRITICAL):(?:([^:\s]+):|\s+)(.*)$").unwrap()
+        });
+
+        if let Some(caps) = timed.captures(line) {
+            return Some(ParsedLine {
+                format: LogFormat::Python,
+                timestamp: Some(caps[1].to_string()),
+                level: LogLevel::from_name(&caps[3]),
+                source: Some(caps[2].to_string()),
+                message: caps[4].to_string(),
+            });
+        }
+
+        let caps = basic.captures(line)?;
+        Some(ParsedLine {
+            format: LogFormat::Python,
+            timestamp: None,
+            level: LogLevel::from_name(&caps[1]),
+            source: caps.get(2).map(|m| m.as_str().to_string()),
+            message: caps[3].to_string(),
+        })
+    }
+}
+
+/// Common/combined access log; the level follows the status code
+struct ApacheAccessParser;
+
+impl LogParser for ApacheAccessParser {
+    fn parse(&self, line: &str) -> Option<ParsedLine> {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        let re = RE.get_or_init(|| {
+            Regex::new(r#"^(\S+) \S+ \S+ \[([^\]]+)\] "([^"]*)" (\d{3}) (?:\d+|-)"#).unwrap()
+        });
+        let caps = re.captures(line)?;
+        let level = match caps[4].as_bytes()[0] {
+            b'5' => LogLevel::Error,
+            b'4' => LogLevel::Warn,
+            _ => LogLevel::Info,
+        };
+        Some(ParsedLine {
+            format: LogFormat::ApacheAccess,
+            timestamp: Some(caps[2].to_string()),
+            level: Some(level),
+            source: Some(caps[1].to_string()),
+            message: format!("{} {}", &caps[3], &caps[4]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> ParsedLine {
+        LogParserRegistry::global().parse(line)
+    }
+
+    #[test]
+    fn test_devport_formats() {
+        let entry = parse("[2024-01-15 10:23:45.123] [WARN] [apache] Port 80 busy");
+        assert_eq!(entry.format, LogFormat::DevPort);
+        assert_eq!(entry.level, Some(LogLevel::Warn));
+        assert_eq!(entry.source.as_deref(), Some("apache"));
+        assert_eq!(entry.message, "Port 80 busy");
+
+        // Captured output: the prefix time is kept, the text is parsed on its own
+        let captured = parse("[2024-01-15 10:23:45.123] ERROR:root:boom");
+        assert_eq!(captured.format, LogFormat::Python);
+        assert_eq!(captured.level, Some(LogLevel::Error));
+        assert_eq!(captured.timestamp.as_deref(), Some("2024-01-15 10:23:45.123"));
+
+        let plain = parse("[2024-01-15 10:23:45.123] compiled successfully");
+        assert_eq!(plain.format, LogFormat::Plain);
+        assert_eq!(plain.message, "compiled successfully");
+
+        // Persisted project output is tagged with its stream
+        let tagged = parse("[2024-01-15 10:23:45.123] [stderr] npm WARN deprecated glob@7");
+        assert_eq!(tagged.source.as_deref(), Some("stderr"));
+        assert_eq!(tagged.level, Some(LogLevel::Warn));
+        assert_eq!(tagged.message, "npm WARN deprecated glob@7");
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let pino = parse(r#"{"level":50,"time":1705314225000,"pid":1,"msg":"db down","name":"api"}"#);
+        assert_eq!(pino.format, LogFormat::JsonLines);
+        assert_eq!(pino.level, Some(LogLevel::Error));
+        assert_eq!(pino.message, "db down");
+        assert_eq!(pino.source.as_deref(), Some("api"));
+        assert!(parse_timestamp(pino.timestamp.as_deref().unwrap()).is_some());
+
+        let structlog = parse(r#"{"event": "user logged in", "level": "warning", "timestamp": "2024-01-15T10:23:45Z"}"#);
+        assert_eq!(structlog.level, Some(LogLevel::Warn));
+        assert_eq!(structlog.message, "user logged in");
+    }
+
+    #[test]
+    fn test_server_formats() {
+        let apache = parse("[Mon Jan 15 10:23:45.123456 2024] [core:error] [pid 1234:tid 5678] [client 127.0.0.1:5555] AH00126: Invalid URI");
+        assert_eq!(apache.format, LogFormat::ApacheError);
+        assert_eq!(apache.level, Some(LogLevel::Error));
+        assert_eq!(apache.source.as_deref(), Some("core"));
+        assert_eq!(apache.message, "AH00126: Invalid URI");
+        assert!(parse_timestamp(apache.timestamp.as_deref().unwrap()).is_some());
+
+        let access = parse(r#"127.0.0.1 - - [15/Jan/2024:10:23:45 +0900] "GET /api HTTP/1.1" 502 312 "-" "curl/8.0""#);
+        assert_eq!(access.format, LogFormat::ApacheAccess);
+        assert_eq!(access.level, Some(LogLevel::Error));
+        assert!(parse_timestamp(access.timestamp.as_deref().unwrap()).is_some());
+
+        let mariadb = parse("2024-01-15 10:23:45 0 [Note] InnoDB: Buffer pool(s) load completed");
+        assert_eq!(mariadb.format, LogFormat::Mariadb);
+        assert_eq!(mariadb.level, Some(LogLevel::Info));
+        assert_eq!(mariadb.source.as_deref(), Some("InnoDB"));
+
+        let old = parse("240115 10:23:45 [ERROR] Can't start server: Bind on TCP/IP port");
+        assert_eq!(old.level, Some(LogLevel::Error));
+        assert!(parse_timestamp(old.timestamp.as_deref().unwrap()).is_some());
+    }
+
+    #[test]
+    fn test_app_formats() {
+        let laravel = parse(r#"[2024-01-15 10:23:45] local.ERROR: SQLSTATE[HY000] [2002] Connection refused {"exception":"[object]"}"#);
+        assert_eq!(laravel.format, LogFormat::Laravel);
+        assert_eq!(laravel.level, Some(LogLevel::Error));
+        assert_eq!(laravel.source.as_deref(), Some("local"));
+
+        let python = parse("2024-01-15 10:23:45,123 - myapp.db - WARNING - slow query");
+        assert_eq!(python.level, Some(LogLevel::Warn));
+        assert_eq!(python.source.as_deref(), Some("myapp.db"));
+        assert!(parse_timestamp(python.timestamp.as_deref().unwrap()).is_some());
+
+        let uvicorn = parse("INFO:     Uvicorn running on http://127.0.0.1:8000");
+        assert_eq!(uvicorn.level, Some(LogLevel::Info));
+        assert_eq!(uvicorn.message, "Uvicorn running on http://127.0.0.1:8000");
+    }
+}
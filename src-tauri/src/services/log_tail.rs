@@ -0,0 +1,389 @@
+use crate::services::log_manager::{
+    check_log_alerts, LogEntry, LogManager, LogSearchHit, LogSource, SharedLogAlerts,
+};
+use crate::services::log_parser::{parse_timestamp, LogLevel, LogParserRegistry};
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Lines per file replayed when a tail starts
+const DEFAULT_BACKLOG: usize = 50;
+/// Most lines sent to the webview in one batch
+const DEFAULT_MAX_BATCH: usize = 200;
+/// Lines of one native log read at a time while checking alerts
+const ALERT_READ_CHUNK: usize = 500;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LogTailOptions {
+    /// Projects and services to merge; empty follows all of them
+    pub sources: Vec<LogSource>,
+    /// Empty matches every level
+    pub levels: Vec<LogLevel>,
+    /// Regex matched against the message, e.g. a request id to follow across sources
+    pub pattern: Option<String>,
+    pub case_sensitive: bool,
+    /// Lines per file replayed before following new output
+    pub backlog: usize,
+    pub max_batch: usize,
+}
+
+impl Default for LogTailOptions {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            levels: Vec::new(),
+            pattern: None,
+            case_sensitive: false,
+            backlog: DEFAULT_BACKLOG,
+            max_batch: DEFAULT_MAX_BATCH,
+        }
+    }
+}
+
+/// Payload of the `log-tail` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogTailBatch {
+    pub tail_id: String,
+    /// Oldest first; `origin` and `logType` tag where each line came from
+    pub lines: Vec<LogSearchHit>,
+    /// Matching lines already read and held for the next batches
+    pub pending: usize,
+}
+
+/// A log file followed by a merged tail
+#[derive(Debug, Clone)]
+pub struct TailFile {
+    pub origin: LogSource,
+    pub log_type: String,
+    pub path: PathBuf,
+    position: u64,
+    /// Time of the last line read while more output was left unread
+    behind: Option<DateTime<Utc>>,
+}
+
+impl TailFile {
+    pub fn new(origin: LogSource, path: PathBuf) -> Self {
+        let log_type = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        Self { origin, log_type, path, position: 0, behind: None }
+    }
+}
+
+/// Current log files of `sources` (rotated ones excluded), plus the logs services
+/// write themselves (`native_logs`). Empty `sources` selects everything.
+pub fn tail_files(
+    manager: &LogManager,
+    sources: &[LogSource],
+    native_logs: &[(LogSource, PathBuf)],
+) -> Vec<TailFile> {
+    let selected = |source: &LogSource| sources.is_empty() || sources.contains(source);
+    let captured = if sources.is_empty() {
+        manager.list_sources()
+    } else {
+        sources.to_vec()
+    };
+
+    let mut files = Vec::new();
+    for source in captured {
+        let Ok(entries) = fs::read_dir(manager.source_dir(&source)) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+            .filter(|path| {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                stem.rsplit_once('.').is_none_or(|(_, n)| n.parse::<u32>().is_err())
+            })
+            .collect();
+        paths.sort();
+        files.extend(paths.into_iter().map(|path| TailFile::new(source.clone(), path)));
+    }
+    for (source, path) in native_logs.iter().filter(|(source, _)| selected(source)) {
+        files.push(TailFile::new(source.clone(), path.clone()));
+    }
+    files
+}
+
+/// Several logs merged into one stream ordered by each line's own timestamp
+pub struct MergedTail {
+    files: Vec<TailFile>,
+    levels: Vec<LogLevel>,
+    pattern: Option<Regex>,
+    max_batch: usize,
+    /// Matching lines not sent yet, in time order
+    pending: Vec<(DateTime<Utc>, LogSearchHit)>,
+}
+
+impl MergedTail {
+    pub fn new(files: Vec<TailFile>, options: &LogTailOptions) -> Result<Self, String> {
+        let pattern = options
+            .pattern
+            .as_deref()
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                RegexBuilder::new(p)
+                    .case_insensitive(!options.case_sensitive)
+                    .build()
+                    .map_err(|e| format!("Invalid pattern: {}", e))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            files,
+            levels: options.levels.clone(),
+            pattern,
+            max_batch: options.max_batch.max(1),
+            pending: Vec::new(),
+        })
+    }
+
+    /// Queue the last `lines` lines of every file and follow each from its current end
+    pub fn load_backlog(&mut self, manager: &LogManager, lines: usize) {
+        let now = Utc::now();
+        for index in 0..self.files.len() {
+            let path = self.files[index].path.clone();
+            self.files[index].position = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            if lines == 0 {
+                continue;
+            }
+            for line in manager.read_log_tail(&path, lines).unwrap_or_default() {
+                self.queue(index, &line, now);
+            }
+        }
+        self.pending.sort_by_key(|(time, _)| *time);
+    }
+
+    /// Matching lines read but not sent yet
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Read new output and return the next batch, oldest first. Lines newer than
+    /// unread output of a busy file wait, so files are merged in time order.
+    pub fn poll(&mut self) -> Vec<LogSearchHit> {
+        let now = Utc::now();
+        // Memory stays bounded; unread output waits in the files
+        if self.pending.len() < self.max_batch {
+            for index in 0..self.files.len() {
+                let file = &mut self.files[index];
+                let (lines, more) = read_new_lines(&file.path, &mut file.position, self.max_batch)
+                    .unwrap_or_default();
+                let mut last = None;
+                for line in lines {
+                    last = Some(self.queue(index, &line, now));
+                }
+                self.files[index].behind = if more { last } else { None };
+            }
+            self.pending.sort_by_key(|(time, _)| *time);
+        }
+
+        let watermark = self.files.iter().filter_map(|f| f.behind).min();
+        let due = self
+            .pending
+            .iter()
+            .take_while(|(time, _)| watermark.is_none_or(|w| *time <= w))
+            .count()
+            .min(self.max_batch);
+        self.pending.drain(..due).map(|(_, hit)| hit).collect()
+    }
+
+    /// Parse a line of file `index` and queue it if it passes the filters.
+    /// Returns the line's time; lines without one count as read `now`.
+    fn queue(&mut self, index: usize, line: &str, now: DateTime<Utc>) -> DateTime<Utc> {
+        let parsed = LogParserRegistry::global().parse(line);
+        let time = parsed.timestamp.as_deref().and_then(parse_timestamp).unwrap_or(now);
+
+        let level = parsed.level.unwrap_or(LogLevel::Info);
+        if !self.levels.is_empty() && !self.levels.contains(&level) {
+            return time;
+        }
+        if self.pattern.as_ref().is_some_and(|re| !re.is_match(&parsed.message)) {
+            return time;
+        }
+
+        let file = &self.files[index];
+        self.pending.push((
+            time,
+            LogSearchHit {
+                origin: file.origin.clone(),
+                log_type: file.log_type.clone(),
+                format: parsed.format,
+                entry: LogEntry::from(parsed),
+            },
+        ));
+        time
+    }
+}
+
+/// Logs services write themselves, followed so their new lines reach the alert rules.
+/// Captured output is checked as it's written instead.
+#[derive(Default)]
+pub struct NativeLogAlerts {
+    files: Vec<TailFile>,
+}
+
+impl NativeLogAlerts {
+    /// Follow `native_logs`; files not followed yet start from their current end
+    pub fn set_files(&mut self, native_logs: &[(LogSource, PathBuf)]) {
+        let mut files = Vec::with_capacity(native_logs.len());
+        for (source, path) in native_logs {
+            let followed = self
+                .files
+                .iter()
+                .position(|file| file.origin == *source && file.path == *path);
+            files.push(match followed {
+                Some(index) => self.files.swap_remove(index),
+                None => {
+                    let mut file = TailFile::new(source.clone(), path.clone());
+                    file.position = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                    file
+                }
+            });
+        }
+        self.files = files;
+    }
+
+    /// Check the lines appended since the last call
+    pub fn poll(&mut self, alerts: &SharedLogAlerts) {
+        for file in &mut self.files {
+            loop {
+                let (lines, more) = read_new_lines(&file.path, &mut file.position, ALERT_READ_CHUNK)
+                    .unwrap_or_default();
+                for line in &lines {
+                    check_log_alerts(alerts, &file.origin, line);
+                }
+                if !more {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Complete lines appended to `path` since `position`, at most `max_lines`.
+/// The flag is set when more output is left to read.
+fn read_new_lines(path: &Path, position: &mut u64, max_lines: usize) -> std::io::Result<(Vec<String>, bool)> {
+    let Ok(metadata) = fs::metadata(path) else {
+        *position = 0;
+        return Ok((Vec::new(), false));
+    };
+    // Truncated or rotated
+    if metadata.len() < *position {
+        *position = 0;
+    }
+    if metadata.len() == *position {
+        return Ok((Vec::new(), false));
+    }
+
+    let mut reader = BufReader::new(File::open(path)?);
+    reader.seek(SeekFrom::Start(*position))?;
+
+    let mut lines = Vec::new();
+    let mut buf = Vec::new();
+    while lines.len() < max_lines {
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf)?;
+        // End of file, or a line still being written
+        if read == 0 || buf.last() != Some(&b'\n') {
+            return Ok((lines, false));
+        }
+        *position += read as u64;
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim_end();
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    Ok((lines, *position < metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::log_manager::{init_log_alerts, LogAlertRule, LogSourceKind};
+    use std::io::Write;
+
+    #[test]
+    fn test_merges_sources_in_time_order() {
+        let base = std::env::temp_dir().join(format!("devport-log-tail-{}", std::process::id()));
+        let manager = LogManager::with_base_path(base.clone());
+        let apache = LogSource { kind: LogSourceKind::Service, name: "apache".to_string() };
+        let shop = LogSource { kind: LogSourceKind::Project, name: "shop".to_string() };
+        fs::create_dir_all(base.join("apache")).unwrap();
+        fs::create_dir_all(base.join("projects").join("shop")).unwrap();
+        let access = base.join("apache").join("stdout.log");
+        let app = base.join("projects").join("shop").join("stderr.log");
+        fs::write(&access, "[2024-01-15 10:00:00.000] GET /cart req=7\n").unwrap();
+        fs::write(&app, "[2024-01-15 09:59:00.000] [stderr] booting\n").unwrap();
+
+        let files = tail_files(&manager, &[], &[]);
+        assert_eq!(files.len(), 2);
+        let options = LogTailOptions {
+            pattern: Some("req=7".to_string()),
+            max_batch: 2,
+            ..Default::default()
+        };
+        let mut tail = MergedTail::new(files, &options).unwrap();
+        tail.load_backlog(&manager, 10);
+        let backlog = tail.poll();
+        assert_eq!(backlog.len(), 1);
+        assert_eq!(backlog[0].origin, apache);
+
+        let append = |path: &Path, text: &str| {
+            let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+            file.write_all(text.as_bytes()).unwrap();
+        };
+        append(
+            &app,
+            "[2024-01-15 10:00:01.000] [stderr] session loaded\n\
+             [2024-01-15 10:00:03.000] [stderr] cart failed req=7\n\
+             [2024-01-15 10:00:04.000] [stderr] gave up req=7\n",
+        );
+        append(&access, "[2024-01-15 10:00:05.000] POST /cart req=7\n");
+
+        // The project file still has unread output, so the later access line waits
+        let first = tail.poll();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].origin, shop);
+        assert_eq!(first[0].entry.message, "cart failed req=7");
+        let rest: Vec<String> = tail.poll().into_iter().map(|hit| hit.entry.message).collect();
+        assert_eq!(rest, vec!["gave up req=7", "POST /cart req=7"]);
+        assert!(tail.poll().is_empty());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_native_log_lines_raise_alerts() {
+        let base = std::env::temp_dir().join(format!("devport-native-alerts-{}", std::process::id()));
+        fs::create_dir_all(&base).unwrap();
+        let error_log = base.join("error.log");
+        fs::write(&error_log, "[ERROR] InnoDB: out of memory before watching\n").unwrap();
+
+        let alerts = init_log_alerts(vec![LogAlertRule {
+            id: "oom".to_string(),
+            name: "Out of memory".to_string(),
+            pattern: Some("out of memory".to_string()),
+            ..Default::default()
+        }]);
+        let mariadb = LogSource { kind: LogSourceKind::Service, name: "mariadb".to_string() };
+        let mut watch = NativeLogAlerts::default();
+        watch.set_files(&[(mariadb.clone(), error_log.clone())]);
+        watch.poll(&alerts);
+        assert_eq!(alerts.lock().unwrap().unread(), 0);
+
+        let mut file = fs::OpenOptions::new().append(true).open(&error_log).unwrap();
+        file.write_all(b"[ERROR] InnoDB: out of memory\n").unwrap();
+        watch.set_files(&[(mariadb, error_log)]);
+        watch.poll(&alerts);
+        assert_eq!(alerts.lock().unwrap().unread(), 1);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -2,21 +2,38 @@ pub mod bundle_installer;
 pub mod bundler;
 pub mod credential_manager;
 pub mod database_manager;
+pub mod dependency_graph;
 pub mod download_manager;
 pub mod env_manager;
 pub mod health_checker;
+pub mod health_monitor;
 pub mod hosts_manager;
 pub mod inventory_scanner;
+#[cfg(target_os = "linux")]
+pub mod linux_secret_store;
 pub mod log_manager;
+pub mod log_parser;
+pub mod log_tail;
 pub mod mariadb_diagnostics;
+pub mod mysql_backend;
+pub mod port_conflict;
+pub mod port_registry;
+pub mod postgres_backend;
 pub mod port_scanner;
 pub mod process_manager;
+pub mod process_stopper;
 pub mod project_detector;
 pub mod project_watcher;
 pub mod recovery;
+pub mod redis_manager;
+pub mod resource_monitor;
 pub mod scheduler;
+pub mod secret_vault;
+pub mod server_url;
 pub mod service_manager;
 pub mod storage;
+#[cfg(unix)]
+pub mod unix_process;
 pub mod uninstaller;
 pub mod updater;
 pub mod version_resolver;
@@ -25,11 +42,16 @@ pub use bundle_installer::{init_bundle_installer, BundleInstaller, SharedBundleI
 pub use database_manager::DatabaseManager;
 pub use download_manager::{init_download_manager, DownloadManager, SharedDownloadManager};
 pub use env_manager::EnvManager;
+pub use health_monitor::{init_health_monitor, spawn_health_monitor, SharedHealthMonitor};
 pub use hosts_manager::HostsManager;
-pub use log_manager::{LogManager, LogStreamManager, LogUpdatePayload, SharedLogStreamManager};
+pub use log_manager::{
+    init_log_alerts, LogManager, LogStreamManager, LogUpdatePayload, SharedLogStreamManager,
+};
+pub use process_manager::{init_process_manager, spawn_exit_monitor, SharedProcessManager};
 pub use project_watcher::{init_project_watcher, ProjectWatcher, SharedProjectWatcher};
 pub use recovery::RecoveryManager;
+pub use resource_monitor::{init_resource_monitor, spawn_resource_monitor, SharedResourceMonitor};
 pub use scheduler::SchedulerManager;
-pub use service_manager::ServiceManager;
+pub use service_manager::{spawn_native_log_alerts, spawn_native_log_rotation, ServiceManager};
 pub use uninstaller::UninstallManager;
 pub use updater::UpdateManager;
@@ -0,0 +1,353 @@
+//! MySQL/MariaDB backend over a pooled native connection
+
+use async_trait::async_trait;
+use mysql_async::consts::{ColumnFlags, ColumnType};
+use mysql_async::prelude::Queryable;
+use mysql_async::{Column, Conn, Opts, OptsBuilder, Pool, PoolConstraints, PoolOpts, Value};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::models::Service;
+use crate::services::database_manager::{
+    client_tool, other_error, run_client_tool, timeout_error, DatabaseBackend, DatabaseCredentials,
+    DatabaseError, QueryResult, SecretFile, SqlValue, CONNECT_TIMEOUT,
+};
+
+/// Idle pooled connections are closed after this long
+const POOL_IDLE_TTL: Duration = Duration::from_secs(60);
+const POOL_MAX_CONNECTIONS: usize = 4;
+
+pub struct MySqlBackend {
+    /// Where the client tools used for dumps and restores live
+    bin_dir: PathBuf,
+    root_credentials: DatabaseCredentials,
+    pool: Pool,
+}
+
+impl MySqlBackend {
+    /// Must be called within the Tokio runtime, which runs the connection pool
+    pub fn new(creds: DatabaseCredentials) -> Self {
+        let pool_opts = PoolOpts::default()
+            .with_constraints(PoolConstraints::new(0, POOL_MAX_CONNECTIONS).unwrap())
+            .with_inactive_connection_ttl(POOL_IDLE_TTL);
+        let opts = Self::connection_opts(&creds).pool_opts(pool_opts);
+        Self {
+            bin_dir: Service::find_mariadb_bin_dir().unwrap_or_default(),
+            root_credentials: creds,
+            pool: Pool::new(opts),
+        }
+    }
+
+    fn connection_opts(creds: &DatabaseCredentials) -> OptsBuilder {
+        OptsBuilder::default()
+            .ip_or_hostname(creds.host.clone())
+            .tcp_port(creds.port)
+            .user(Some(creds.username.clone()))
+            .pass(Some(creds.password.clone()))
+            .db_name(Some(creds.database.clone()).filter(|db| !db.is_empty()))
+    }
+
+    fn connection_error(creds: &DatabaseCredentials, err: mysql_async::Error) -> DatabaseError {
+        let code = match &err {
+            mysql_async::Error::Server(server) => Some(server.code),
+            _ => None,
+        };
+        let message = match err {
+            mysql_async::Error::Server(server) => server.message,
+            other => other.to_string(),
+        };
+        DatabaseError::Connection {
+            host: creds.host.clone(),
+            port: creds.port,
+            code,
+            message,
+        }
+    }
+
+    fn query_error(creds: &DatabaseCredentials, err: mysql_async::Error) -> DatabaseError {
+        match err {
+            mysql_async::Error::Server(server) => DatabaseError::Query {
+                code: Some(server.code),
+                state: server.state,
+                message: server.message,
+            },
+            // Lost the connection mid-query
+            err @ (mysql_async::Error::Io(_) | mysql_async::Error::Driver(_)) => {
+                Self::connection_error(creds, err)
+            }
+            other => DatabaseError::Other(other.to_string()),
+        }
+    }
+
+    async fn root_conn(&self) -> Result<Conn, DatabaseError> {
+        let creds = &self.root_credentials;
+        match tokio::time::timeout(CONNECT_TIMEOUT, self.pool.get_conn()).await {
+            Ok(conn) => conn.map_err(|e| Self::connection_error(creds, e)),
+            Err(_) => Err(timeout_error(creds)),
+        }
+    }
+
+    /// Client tool command logged in as root; the password goes through an option file
+    fn client_command(&self, name: &str) -> Result<(Command, SecretFile), DatabaseError> {
+        let creds = &self.root_credentials;
+        let escaped = creds.password.replace('\\', "\\\\").replace('"', "\\\"");
+        let option_file = SecretFile::create("cnf", &format!("[client]\npassword=\"{}\"\n", escaped))?;
+
+        let mut cmd = Command::new(client_tool(&self.bin_dir, name));
+        // Must come first
+        cmd.arg(format!("--defaults-extra-file={}", option_file.path().display()))
+            .args([
+                // `localhost` would otherwise mean the Unix socket, ignoring the port
+                "--protocol=TCP",
+                "-h", &creds.host,
+                "-P", &creds.port.to_string(),
+                "-u", &creds.username,
+            ]);
+        Ok((cmd, option_file))
+    }
+
+    /// Whether `creds` can log in; `Err` only when the server can't be reached
+    pub async fn check_login(creds: &DatabaseCredentials) -> Result<bool, DatabaseError> {
+        let connect = Conn::new(Opts::from(Self::connection_opts(creds)));
+        match tokio::time::timeout(CONNECT_TIMEOUT, connect).await {
+            Ok(Ok(conn)) => {
+                let _ = conn.disconnect().await;
+                Ok(true)
+            }
+            Ok(Err(mysql_async::Error::Server(_))) => Ok(false),
+            Ok(Err(e)) => Err(Self::connection_error(creds, e)),
+            Err(_) => Err(timeout_error(creds)),
+        }
+    }
+}
+
+#[async_trait]
+impl DatabaseBackend for MySqlBackend {
+    fn credentials(&self) -> &DatabaseCredentials {
+        &self.root_credentials
+    }
+
+    async fn execute_sql(&self, sql: &str) -> Result<QueryResult, DatabaseError> {
+        let creds = &self.root_credentials;
+        let mut conn = self.root_conn().await?;
+        let mut result = conn
+            .query_iter(sql)
+            .await
+            .map_err(|e| Self::query_error(creds, e))?;
+
+        let mut output = QueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            affected_rows: 0,
+            last_insert_id: None,
+        };
+        // Multi-statement input yields one result set per statement
+        loop {
+            let columns = result.columns().unwrap_or_default();
+            let rows: Vec<mysql_async::Row> = result
+                .collect()
+                .await
+                .map_err(|e| Self::query_error(creds, e))?;
+            output.affected_rows += result.affected_rows();
+            output.last_insert_id = result.last_insert_id().or(output.last_insert_id);
+            if !columns.is_empty() {
+                output.columns = columns.iter().map(|c| c.name_str().to_string()).collect();
+                output.rows = rows
+                    .into_iter()
+                    .map(|row| {
+                        row.unwrap()
+                            .into_iter()
+                            .zip(columns.iter())
+                            .map(|(value, column)| convert_value(value, column))
+                            .collect()
+                    })
+                    .collect();
+            }
+            if result.is_empty() {
+                break;
+            }
+        }
+        Ok(output)
+    }
+
+    async fn create_database(&self, db_name: &str) -> Result<(), DatabaseError> {
+        let sql = format!("CREATE DATABASE IF NOT EXISTS {}", quote_identifier(db_name));
+        self.execute_sql(&sql).await?;
+        Ok(())
+    }
+
+    async fn drop_database(&self, db_name: &str) -> Result<(), DatabaseError> {
+        let sql = format!("DROP DATABASE IF EXISTS {}", quote_identifier(db_name));
+        self.execute_sql(&sql).await?;
+        Ok(())
+    }
+
+    async fn create_user(&self, username: &str, password: &str, db_name: &str) -> Result<(), DatabaseError> {
+        let create_user = format!(
+            "CREATE USER IF NOT EXISTS {}@'localhost' IDENTIFIED BY {}",
+            quote_literal(username),
+            quote_literal(password)
+        );
+        self.execute_sql(&create_user).await?;
+
+        let grant = format!(
+            "GRANT ALL PRIVILEGES ON {}.* TO {}@'localhost'",
+            quote_identifier(db_name),
+            quote_literal(username)
+        );
+        self.execute_sql(&grant).await?;
+
+        self.execute_sql("FLUSH PRIVILEGES").await?;
+
+        Ok(())
+    }
+
+    async fn drop_user(&self, username: &str) -> Result<(), DatabaseError> {
+        let sql = format!("DROP USER IF EXISTS {}@'localhost'", quote_literal(username));
+        self.execute_sql(&sql).await?;
+        Ok(())
+    }
+
+    async fn reset_password(&self, username: &str, new_password: &str) -> Result<(), DatabaseError> {
+        let sql = format!(
+            "ALTER USER {}@'localhost' IDENTIFIED BY {}",
+            quote_literal(username),
+            quote_literal(new_password)
+        );
+        self.execute_sql(&sql).await?;
+        self.execute_sql("FLUSH PRIVILEGES").await?;
+        Ok(())
+    }
+
+    async fn list_databases(&self) -> Result<Vec<String>, DatabaseError> {
+        let result = self.execute_sql("SHOW DATABASES").await?;
+        let databases: Vec<String> = result
+            .first_column()
+            .into_iter()
+            .filter(|name| {
+                !["information_schema", "performance_schema", "mysql", "sys"]
+                    .contains(&name.as_str())
+            })
+            .collect();
+
+        Ok(databases)
+    }
+
+    async fn dump(&self, db_name: &str) -> Result<Vec<u8>, DatabaseError> {
+        let (mut cmd, _option_file) = self.client_command("mysqldump")?;
+        cmd.args([
+            "--routines",
+            "--triggers",
+            "--single-transaction",
+            db_name,
+        ]);
+        run_client_tool(cmd).await
+    }
+
+    /// Restore with the mysql client, which also understands the
+    /// DELIMITER blocks dumps of routines and triggers contain
+    async fn restore(&self, db_name: &str, dump_path: &Path) -> Result<(), DatabaseError> {
+        let dump = std::fs::File::open(dump_path).map_err(other_error)?;
+        let (mut cmd, _option_file) = self.client_command("mysql")?;
+        cmd.arg(db_name).stdin(Stdio::from(dump));
+        run_client_tool(cmd).await?;
+        Ok(())
+    }
+}
+
+/// `name` as a backquoted identifier
+pub fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// `value` as a single-quoted string literal
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Type a text-protocol value after its column definition
+fn convert_value(value: Value, column: &Column) -> SqlValue {
+    let bytes = match value {
+        Value::NULL => return SqlValue::Null,
+        Value::Int(i) => return SqlValue::Int(i),
+        Value::UInt(u) => return SqlValue::UInt(u),
+        Value::Float(f) => return SqlValue::Float(f as f64),
+        Value::Double(d) => return SqlValue::Float(d),
+        Value::Date(y, mo, d, h, mi, s, _) => {
+            return SqlValue::Text(format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", y, mo, d, h, mi, s))
+        }
+        Value::Time(negative, days, h, mi, s, _) => {
+            let sign = if negative { "-" } else { "" };
+            return SqlValue::Text(format!("{}{:02}:{:02}:{:02}", sign, days * 24 + h as u32, mi, s));
+        }
+        Value::Bytes(bytes) => bytes,
+    };
+
+    let text = std::str::from_utf8(&bytes).ok();
+    let unsigned = column.flags().contains(ColumnFlags::UNSIGNED_FLAG);
+    let typed = match column.column_type() {
+        ColumnType::MYSQL_TYPE_TINY
+        | ColumnType::MYSQL_TYPE_SHORT
+        | ColumnType::MYSQL_TYPE_INT24
+        | ColumnType::MYSQL_TYPE_LONG
+        | ColumnType::MYSQL_TYPE_LONGLONG
+        | ColumnType::MYSQL_TYPE_YEAR => text.and_then(|t| {
+            if unsigned {
+                t.parse().ok().map(SqlValue::UInt)
+            } else {
+                t.parse().ok().map(SqlValue::Int)
+            }
+        }),
+        ColumnType::MYSQL_TYPE_FLOAT | ColumnType::MYSQL_TYPE_DOUBLE => {
+            text.and_then(|t| t.parse().ok()).map(SqlValue::Float)
+        }
+        ColumnType::MYSQL_TYPE_BIT => Some(SqlValue::UInt(
+            bytes.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64),
+        )),
+        _ => None,
+    };
+    typed.unwrap_or_else(|| match String::from_utf8(bytes) {
+        Ok(text) => SqlValue::Text(text),
+        Err(e) => SqlValue::Bytes(e.into_bytes()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(column_type: ColumnType, flags: ColumnFlags) -> Column {
+        Column::new(column_type).with_flags(flags)
+    }
+
+    #[test]
+    fn test_converts_text_values_by_column_type() {
+        let text = |s: &str| Value::Bytes(s.as_bytes().to_vec());
+        let int = column(ColumnType::MYSQL_TYPE_LONG, ColumnFlags::empty());
+        let unsigned = column(ColumnType::MYSQL_TYPE_LONGLONG, ColumnFlags::UNSIGNED_FLAG);
+        let decimal = column(ColumnType::MYSQL_TYPE_NEWDECIMAL, ColumnFlags::empty());
+        let blob = column(ColumnType::MYSQL_TYPE_BLOB, ColumnFlags::BINARY_FLAG);
+
+        assert_eq!(convert_value(text("-42"), &int), SqlValue::Int(-42));
+        assert_eq!(
+            convert_value(text("18446744073709551615"), &unsigned),
+            SqlValue::UInt(u64::MAX)
+        );
+        assert_eq!(convert_value(text("0.10"), &decimal), SqlValue::Text("0.10".to_string()));
+        assert_eq!(
+            convert_value(text("2.5"), &column(ColumnType::MYSQL_TYPE_DOUBLE, ColumnFlags::empty())),
+            SqlValue::Float(2.5)
+        );
+        assert_eq!(convert_value(Value::NULL, &int), SqlValue::Null);
+        assert_eq!(convert_value(Value::Bytes(vec![0xff, 0x00]), &blob), SqlValue::Bytes(vec![0xff, 0x00]));
+        assert_eq!(
+            convert_value(Value::Bytes(vec![0x01, 0x02]), &column(ColumnType::MYSQL_TYPE_BIT, ColumnFlags::UNSIGNED_FLAG)),
+            SqlValue::UInt(258)
+        );
+
+        assert_eq!(quote_identifier("shop`db"), "`shop``db`");
+        assert_eq!(quote_literal(r"it's\"), r"'it\'s\\'");
+    }
+}
@@ -0,0 +1,95 @@
+//! Detection of a taken project port before a start, with the ways to resolve it
+
+use crate::models::PortInfo;
+use crate::services::port_registry::PortRegistry;
+use crate::services::port_scanner::PortScanner;
+use crate::services::storage::Storage;
+use serde::{Deserialize, Serialize};
+
+/// How far past the configured port to look for a free one
+const NEXT_PORT_SEARCH_RANGE: u16 = 100;
+
+/// Ways to resolve a port conflict reported by `start_project`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum PortConflictAction {
+    /// Kill the process holding the port, then start
+    KillOwner,
+    /// Move the project to the next free port, persist it, then start
+    UseNextPort,
+    /// Leave everything as it is
+    Abort,
+}
+
+/// Payload of the `PORT_IN_USE` error returned when a project's port is taken
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortConflict {
+    pub project_id: String,
+    pub port: u16,
+    pub owner_pid: Option<u32>,
+    pub owner_name: Option<String>,
+    pub next_free_port: Option<u16>,
+    pub actions: Vec<PortConflictAction>,
+}
+
+impl PortConflict {
+    pub fn message(&self) -> String {
+        let owner = match (&self.owner_name, self.owner_pid) {
+            (Some(name), Some(pid)) => format!(" by {} (PID {})", name, pid),
+            (None, Some(pid)) => format!(" by PID {}", pid),
+            _ => String::new(),
+        };
+        format!("Port {} is already in use{}", self.port, owner)
+    }
+}
+
+pub fn port_owner(port: u16) -> Option<PortInfo> {
+    PortScanner::scan_ports()
+        .ok()?
+        .into_iter()
+        .find(|p| p.port == port)
+}
+
+/// Next free port after `port` that isn't reserved by another project or service
+pub fn next_free_port(port: u16) -> Option<u16> {
+    let reserved = Storage::new()
+        .and_then(|s| PortRegistry::load(&s))
+        .map(|r| r.reserved_ports())
+        .unwrap_or_default();
+    PortScanner::find_available_port(
+        port.saturating_add(1),
+        port.saturating_add(NEXT_PORT_SEARCH_RANGE),
+        &reserved,
+    )
+}
+
+/// The conflict a project starting on `port` would run into, if the port is taken.
+/// Scans the system's ports, so it blocks.
+pub fn find_port_conflict(project_id: &str, port: u16) -> Option<PortConflict> {
+    if port == 0 || PortScanner::is_port_available(port) {
+        return None;
+    }
+
+    let owner = port_owner(port);
+    let owner_pid = owner.as_ref().and_then(|o| o.pid).filter(|pid| *pid != 0);
+    let next_free_port = next_free_port(port);
+
+    let mut actions = Vec::new();
+    if owner_pid.is_some() {
+        actions.push(PortConflictAction::KillOwner);
+    }
+    if next_free_port.is_some() {
+        actions.push(PortConflictAction::UseNextPort);
+    }
+    actions.push(PortConflictAction::Abort);
+
+    Some(PortConflict {
+        project_id: project_id.to_string(),
+        port,
+        owner_pid,
+        owner_name: owner.and_then(|o| o.process_name),
+        next_free_port,
+        actions,
+    })
+}
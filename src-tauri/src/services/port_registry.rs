@@ -0,0 +1,149 @@
+use crate::models::{PortOwnerKind, PortReservation, Project, Service};
+use crate::services::storage::{Storage, StorageError};
+
+/// Persistent registry of ports claimed by projects, services and Apache.
+///
+/// Unlike `PortScanner`, which only sees what is listening right now, the
+/// registry knows that two stopped projects are both configured for the same
+/// port. Derived reservations are rebuilt by `sync`; manual ones are kept.
+pub struct PortRegistry {
+    reservations: Vec<PortReservation>,
+}
+
+impl PortRegistry {
+    pub fn load(storage: &Storage) -> Result<Self, StorageError> {
+        Ok(Self {
+            reservations: storage.load_port_reservations()?,
+        })
+    }
+
+    pub fn save(&self, storage: &Storage) -> Result<(), StorageError> {
+        storage.save_port_reservations(&self.reservations)
+    }
+
+    pub fn reservations(&self) -> &[PortReservation] {
+        &self.reservations
+    }
+
+    /// Replace every derived reservation with the ports currently configured
+    /// on projects and services plus the given Apache ports
+    pub fn sync<'a>(
+        &mut self,
+        projects: &[Project],
+        services: impl IntoIterator<Item = &'a Service>,
+        apache: Vec<PortReservation>,
+    ) {
+        self.reservations.retain(|r| r.kind == PortOwnerKind::Manual);
+
+        for project in projects.iter().filter(|p| p.port > 0) {
+            self.reservations.push(PortReservation::new(
+                project.port,
+                PortOwnerKind::Project,
+                &project.id,
+                &project.name,
+            ));
+        }
+
+        for service in services {
+            let ports = std::iter::once(service.port).chain(service.additional_ports.iter().copied());
+            for port in ports.filter(|p| *p > 0) {
+                self.reservations.push(PortReservation::new(
+                    port,
+                    PortOwnerKind::Service,
+                    &service.id,
+                    &service.name,
+                ));
+            }
+        }
+
+        self.reservations.extend(apache);
+        self.reservations.sort_by_key(|r| r.port);
+    }
+
+    /// Reservations of `port` held by anyone other than `owner_id`
+    pub fn conflicts(&self, port: u16, owner_id: Option<&str>) -> Vec<&PortReservation> {
+        self.reservations
+            .iter()
+            .filter(|r| r.port == port && Some(r.owner_id.as_str()) != owner_id)
+            .collect()
+    }
+
+    /// Every reserved port, for excluding from suggestions
+    pub fn reserved_ports(&self) -> Vec<u16> {
+        let mut ports: Vec<u16> = self.reservations.iter().map(|r| r.port).collect();
+        ports.sort_unstable();
+        ports.dedup();
+        ports
+    }
+
+    /// Reserve a port by hand for something DevPort doesn't manage
+    pub fn reserve(&mut self, port: u16, name: &str) -> PortReservation {
+        let reservation = PortReservation::new(
+            port,
+            PortOwnerKind::Manual,
+            format!("manual:{}", port),
+            name,
+        );
+        self.reservations.retain(|r| r.owner_id != reservation.owner_id);
+        self.reservations.push(reservation.clone());
+        self.reservations.sort_by_key(|r| r.port);
+        reservation
+    }
+
+    /// Drop a manual reservation. Returns false if there was none for the port.
+    pub fn release(&mut self, port: u16) -> bool {
+        let before = self.reservations.len();
+        self.reservations
+            .retain(|r| !(r.kind == PortOwnerKind::Manual && r.port == port));
+        self.reservations.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectType, ServiceType};
+
+    fn project(id: &str, port: u16) -> Project {
+        let mut p = Project::new(id.to_string(), String::new(), port, ProjectType::Vite, String::new());
+        p.id = id.to_string();
+        p
+    }
+
+    #[test]
+    fn test_stopped_projects_conflict() {
+        let mut registry = PortRegistry { reservations: Vec::new() };
+        registry.sync(&[project("a", 5173), project("b", 3000)], [], Vec::new());
+
+        let conflicts = registry.conflicts(5173, Some("b"));
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].owner_id, "a");
+
+        // A project doesn't conflict with its own reservation
+        assert!(registry.conflicts(5173, Some("a")).is_empty());
+    }
+
+    #[test]
+    fn test_sync_keeps_manual_reservations() {
+        let mut registry = PortRegistry { reservations: Vec::new() };
+        registry.reserve(4000, "docker");
+        registry.sync(&[project("a", 5173)], [], Vec::new());
+        registry.sync(&[], [], Vec::new());
+
+        assert_eq!(registry.reserved_ports(), vec![4000]);
+        assert!(registry.release(4000));
+        assert!(!registry.release(4000));
+    }
+
+    #[test]
+    fn test_service_and_apache_ports_reserved() {
+        let mut mariadb = Service::new("mariadb".to_string(), "MariaDB".to_string(), ServiceType::Database);
+        mariadb.port = 3306;
+        let apache = vec![PortReservation::new(8080, PortOwnerKind::Apache, "8080_localhost", "localhost")];
+
+        let mut registry = PortRegistry { reservations: Vec::new() };
+        registry.sync(&[project("desktop", 0)], [&mariadb], apache);
+
+        assert_eq!(registry.reserved_ports(), vec![3306, 8080]);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::models::PortInfo;
+#[cfg(not(target_os = "linux"))]
 use std::process::Command;
 use thiserror::Error;
 
@@ -19,6 +20,58 @@ pub enum PortScannerError {
 pub struct PortScanner;
 
 impl PortScanner {
+    /// Linux: read the kernel socket tables directly (`netstat -ano` is Windows syntax
+    /// and has no PID column here)
+    #[cfg(target_os = "linux")]
+    pub fn scan_ports() -> Result<Vec<PortInfo>, PortScannerError> {
+        let mut sockets = procfs::read_sockets().map_err(PortScannerError::ExecutionError)?;
+
+        // Prefer the listening socket when a port shows up more than once
+        sockets.sort_by_key(|(_, socket)| socket.state != "LISTENING");
+
+        let mut seen_ports = std::collections::HashSet::new();
+        let mut selected = Vec::new();
+        for (protocol, socket) in sockets {
+            if socket.port == 0 || seen_ports.contains(&socket.port) {
+                continue;
+            }
+
+            // Same filter as the netstat backend: listening sockets or common dev ports
+            let is_listening = socket.state == "LISTENING";
+            let is_dev_port = (3000..=9999).contains(&socket.port);
+            if is_listening || is_dev_port {
+                seen_ports.insert(socket.port);
+                selected.push((protocol, socket));
+            }
+        }
+
+        let inodes = selected
+            .iter()
+            .map(|(_, socket)| socket.inode)
+            .filter(|inode| *inode != 0)
+            .collect();
+        let owners = procfs::socket_owners(&inodes);
+
+        let mut ports: Vec<PortInfo> = selected
+            .into_iter()
+            .map(|(protocol, socket)| {
+                let mut port_info = PortInfo::new(
+                    socket.port,
+                    protocol.to_string(),
+                    socket.state.to_string(),
+                    socket.local_address(),
+                );
+                port_info.pid = owners.get(&socket.inode).copied();
+                port_info.process_name = port_info.pid.and_then(procfs::process_name);
+                port_info
+            })
+            .collect();
+
+        ports.sort_by_key(|p| p.port);
+        Ok(ports)
+    }
+
+    #[cfg(not(target_os = "linux"))]
     pub fn scan_ports() -> Result<Vec<PortInfo>, PortScannerError> {
         #[cfg(windows)]
         let output = Command::new("netstat")
@@ -43,6 +96,7 @@ impl PortScanner {
         Self::parse_netstat_output(&stdout)
     }
 
+    #[cfg(not(target_os = "linux"))]
     fn parse_netstat_output(output: &str) -> Result<Vec<PortInfo>, PortScannerError> {
         let mut ports = Vec::new();
         let mut seen_ports = std::collections::HashSet::new();
@@ -108,6 +162,7 @@ impl PortScanner {
         Ok(ports)
     }
 
+    #[cfg(not(target_os = "linux"))]
     fn get_process_name(pid: u32) -> Option<String> {
         if pid == 0 {
             return None;
@@ -205,6 +260,17 @@ impl PortScanner {
         suggestions
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn is_port_available(port: u16) -> bool {
+        procfs::read_sockets()
+            .unwrap_or_default()
+            .iter()
+            .all(|(_, socket)| {
+                socket.port != port || !matches!(socket.state, "LISTENING" | "ESTABLISHED")
+            })
+    }
+
+    #[cfg(not(target_os = "linux"))]
     pub fn is_port_available(port: u16) -> bool {
         #[cfg(windows)]
         let output = Command::new("netstat")
@@ -232,3 +298,236 @@ impl PortScanner {
         true
     }
 }
+
+/// Linux socket tables from /proc/net, with socket inodes mapped to PIDs
+/// through /proc/<pid>/fd
+#[cfg(target_os = "linux")]
+mod procfs {
+    use std::collections::{HashMap, HashSet};
+    use std::fs;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    /// Socket tables and the protocol they are reported as
+    const TABLES: [(&str, &str); 4] = [
+        ("/proc/net/tcp", "TCP"),
+        ("/proc/net/tcp6", "TCP"),
+        ("/proc/net/udp", "UDP"),
+        ("/proc/net/udp6", "UDP"),
+    ];
+
+    /// One row of a /proc/net socket table
+    #[derive(Debug, PartialEq)]
+    pub(super) struct SocketEntry {
+        pub ip: String,
+        pub port: u16,
+        /// TCP state using the same names as `netstat -ano`; "N/A" for UDP
+        pub state: &'static str,
+        pub inode: u64,
+    }
+
+    impl SocketEntry {
+        /// Address formatted like netstat: `IP:PORT` or `[IPv6]:PORT`
+        pub fn local_address(&self) -> String {
+            if self.ip.contains(':') {
+                format!("[{}]:{}", self.ip, self.port)
+            } else {
+                format!("{}:{}", self.ip, self.port)
+            }
+        }
+    }
+
+    /// Read every socket table. Fails only if none of them can be read.
+    pub(super) fn read_sockets() -> Result<Vec<(&'static str, SocketEntry)>, String> {
+        let mut sockets = Vec::new();
+        let mut readable = false;
+
+        for (path, protocol) in TABLES {
+            // tcp6/udp6 are missing when IPv6 is disabled
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            readable = true;
+            sockets.extend(
+                parse_table(&content, protocol == "TCP")
+                    .into_iter()
+                    .map(|socket| (protocol, socket)),
+            );
+        }
+
+        if readable {
+            Ok(sockets)
+        } else {
+            Err("Failed to read /proc/net socket tables".to_string())
+        }
+    }
+
+    pub(super) fn parse_table(content: &str, tcp: bool) -> Vec<SocketEntry> {
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| parse_line(line, tcp))
+            .collect()
+    }
+
+    /// `sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode ...`
+    fn parse_line(line: &str, tcp: bool) -> Option<SocketEntry> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 10 {
+            return None;
+        }
+
+        let (ip_hex, port_hex) = fields[1].split_once(':')?;
+        let state_code = u8::from_str_radix(fields[3], 16).ok()?;
+
+        Some(SocketEntry {
+            ip: parse_ip(ip_hex)?,
+            port: u16::from_str_radix(port_hex, 16).ok()?,
+            state: if tcp { tcp_state(state_code) } else { "N/A" },
+            inode: fields[9].parse().ok()?,
+        })
+    }
+
+    /// The kernel prints addresses as 32-bit words in host byte order
+    fn parse_ip(hex: &str) -> Option<String> {
+        let word = |i: usize| {
+            hex.get(i * 8..i * 8 + 8)
+                .and_then(|w| u32::from_str_radix(w, 16).ok())
+                .map(u32::to_ne_bytes)
+        };
+
+        match hex.len() {
+            8 => Some(Ipv4Addr::from(word(0)?).to_string()),
+            32 => {
+                let mut octets = [0u8; 16];
+                for i in 0..4 {
+                    octets[i * 4..i * 4 + 4].copy_from_slice(&word(i)?);
+                }
+                let ip = Ipv6Addr::from(octets);
+                // Show IPv4-mapped addresses (dual-stack sockets) as plain IPv4
+                Some(match ip.to_ipv4_mapped() {
+                    Some(v4) => v4.to_string(),
+                    None => ip.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn tcp_state(code: u8) -> &'static str {
+        match code {
+            0x01 => "ESTABLISHED",
+            0x02 => "SYN_SENT",
+            0x03 => "SYN_RECEIVED",
+            0x04 => "FIN_WAIT_1",
+            0x05 => "FIN_WAIT_2",
+            0x06 => "TIME_WAIT",
+            0x07 => "CLOSED",
+            0x08 => "CLOSE_WAIT",
+            0x09 => "LAST_ACK",
+            0x0A => "LISTENING",
+            0x0B => "CLOSING",
+            _ => "UNKNOWN",
+        }
+    }
+
+    /// Map socket inodes to the PID holding them open.
+    /// Processes we are not allowed to inspect (other users) are skipped.
+    pub(super) fn socket_owners(inodes: &HashSet<u64>) -> HashMap<u64, u32> {
+        let mut owners = HashMap::new();
+        if inodes.is_empty() {
+            return owners;
+        }
+
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return owners;
+        };
+
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+                continue;
+            };
+            let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+                continue;
+            };
+
+            for fd in fds.flatten() {
+                let inode = fs::read_link(fd.path())
+                    .ok()
+                    .and_then(|target| socket_inode(&target.to_string_lossy()));
+                if let Some(inode) = inode.filter(|i| inodes.contains(i)) {
+                    owners.entry(inode).or_insert(pid);
+                }
+            }
+
+            if owners.len() == inodes.len() {
+                break;
+            }
+        }
+
+        owners
+    }
+
+    /// Parse an fd link target such as `socket:[12345]`
+    fn socket_inode(target: &str) -> Option<u64> {
+        target.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+    }
+
+    pub(super) fn process_name(pid: u32) -> Option<String> {
+        fs::read_to_string(format!("/proc/{}/comm", pid))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const TCP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0CEA 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 123456 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:0CEA 0100007F:D2F0 01 00000000:00000000 00:00000000 00000000  1000        0 123457 1 0000000000000000 20 4 30 10 -1
+";
+
+        const TCP6: &str = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:1F90 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 223344 1 0000000000000000 100 0 0 10 0
+   1: 0000000000000000FFFF00000100007F:1F91 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 223345 1 0000000000000000 100 0 0 10 0
+";
+
+        #[test]
+        fn test_parse_tcp_table() {
+            let sockets = parse_table(TCP, true);
+            assert_eq!(sockets.len(), 2);
+            assert_eq!(sockets[0].ip, "127.0.0.1");
+            assert_eq!(sockets[0].port, 3306);
+            assert_eq!(sockets[0].state, "LISTENING");
+            assert_eq!(sockets[0].inode, 123456);
+            assert_eq!(sockets[0].local_address(), "127.0.0.1:3306");
+            assert_eq!(sockets[1].state, "ESTABLISHED");
+        }
+
+        #[test]
+        fn test_parse_tcp6_table() {
+            let sockets = parse_table(TCP6, true);
+            assert_eq!(sockets[0].local_address(), "[::]:8080");
+            // IPv4-mapped address on a dual-stack socket
+            assert_eq!(sockets[1].local_address(), "127.0.0.1:8081");
+        }
+
+        #[test]
+        fn test_udp_has_no_state() {
+            let udp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+ 1: 00000000:14E9 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 9876 2 0000000000000000 0
+";
+            let sockets = parse_table(udp, false);
+            assert_eq!(sockets[0].port, 5353);
+            assert_eq!(sockets[0].state, "N/A");
+        }
+
+        #[test]
+        fn test_socket_inode() {
+            assert_eq!(socket_inode("socket:[123456]"), Some(123456));
+            assert_eq!(socket_inode("pipe:[123456]"), None);
+            assert_eq!(socket_inode("/dev/null"), None);
+        }
+    }
+}
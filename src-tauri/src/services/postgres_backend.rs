@@ -0,0 +1,339 @@
+//! PostgreSQL backend, plus initialization of the cluster DevPort runs
+
+use async_trait::async_trait;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tokio_postgres::{Client, Config, NoTls, SimpleQueryMessage};
+
+use crate::models::Service;
+use crate::services::credential_manager::{CredentialManager, POSTGRES_SUPERUSER_KEY};
+use crate::services::database_manager::{
+    client_tool, run_client_tool, DatabaseBackend, DatabaseCredentials, DatabaseEngine,
+    DatabaseError, QueryResult, SecretFile, SqlValue, CONNECT_TIMEOUT,
+};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Superuser of the cluster created by [`init_cluster`]
+pub const SUPERUSER: &str = "postgres";
+
+pub struct PostgresBackend {
+    /// Where pg_dump and psql live
+    bin_dir: PathBuf,
+    root_credentials: DatabaseCredentials,
+    /// Log in with the superuser password stored for DevPort's cluster. It is read on
+    /// every login: the cluster may only be created after the backend.
+    stored_password: bool,
+}
+
+impl PostgresBackend {
+    pub fn new(creds: DatabaseCredentials) -> Self {
+        Self {
+            bin_dir: Service::find_postgresql_bin_dir().unwrap_or_default(),
+            root_credentials: creds,
+            stored_password: false,
+        }
+    }
+
+    /// Backend for the cluster DevPort runs, logged in as [`SUPERUSER`]
+    pub fn devport_cluster() -> Self {
+        Self {
+            stored_password: true,
+            ..Self::new(DatabaseEngine::Postgres.default_admin())
+        }
+    }
+
+    /// Credentials to log in with
+    fn login(&self) -> Result<DatabaseCredentials, DatabaseError> {
+        let mut creds = self.root_credentials.clone();
+        if self.stored_password {
+            creds.password = superuser_password().map_err(DatabaseError::Other)?;
+        }
+        Ok(creds)
+    }
+
+    fn config(creds: &DatabaseCredentials) -> Config {
+        let mut config = Config::new();
+        config
+            .host(&creds.host)
+            .port(creds.port)
+            .user(&creds.username)
+            .password(&creds.password)
+            .dbname(if creds.database.is_empty() { SUPERUSER } else { &creds.database })
+            .application_name("clickdevport")
+            .connect_timeout(CONNECT_TIMEOUT);
+        config
+    }
+
+    fn connection_error(creds: &DatabaseCredentials, err: tokio_postgres::Error) -> DatabaseError {
+        let message = match err.as_db_error() {
+            Some(db) => db.message().to_string(),
+            None => err.to_string(),
+        };
+        DatabaseError::Connection {
+            host: creds.host.clone(),
+            port: creds.port,
+            code: None,
+            message,
+        }
+    }
+
+    fn query_error(creds: &DatabaseCredentials, err: tokio_postgres::Error) -> DatabaseError {
+        match err.as_db_error() {
+            Some(db) => DatabaseError::Query {
+                code: None,
+                state: db.code().code().to_string(),
+                message: db.message().to_string(),
+            },
+            // Lost the connection mid-query
+            None if err.is_closed() => Self::connection_error(creds, err),
+            None => DatabaseError::Other(err.to_string()),
+        }
+    }
+
+    /// Connections are cheap next to how rarely these statements run, so each
+    /// operation opens its own instead of keeping a pool
+    async fn connect(creds: &DatabaseCredentials) -> Result<Client, tokio_postgres::Error> {
+        let (client, connection) = Self::config(creds).connect(NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("PostgreSQL connection error: {}", e);
+            }
+        });
+        Ok(client)
+    }
+
+    /// Client tool command logged in as the superuser; the password goes through a
+    /// password file instead of the command line
+    fn client_command(&self, name: &str, db_name: &str) -> Result<(Command, SecretFile), DatabaseError> {
+        let creds = &self.login()?;
+        let escaped = creds.password.replace('\\', "\\\\").replace(':', "\\:");
+        let passfile = SecretFile::create("pgpass", &format!("*:*:*:*:{}\n", escaped))?;
+
+        let mut cmd = Command::new(client_tool(&self.bin_dir, name));
+        cmd.env("PGPASSFILE", passfile.path())
+            .env("PGCONNECT_TIMEOUT", CONNECT_TIMEOUT.as_secs().to_string())
+            .args([
+                "-h", &creds.host,
+                "-p", &creds.port.to_string(),
+                "-U", &creds.username,
+                "-d", db_name,
+                // Fail instead of prompting for a password
+                "-w",
+            ]);
+        Ok((cmd, passfile))
+    }
+
+    async fn database_exists(&self, db_name: &str) -> Result<bool, DatabaseError> {
+        let sql = format!("SELECT 1 FROM pg_database WHERE datname = {}", quote_literal(db_name));
+        Ok(!self.execute_sql(&sql).await?.rows.is_empty())
+    }
+
+    async fn role_exists(&self, username: &str) -> Result<bool, DatabaseError> {
+        let sql = format!("SELECT 1 FROM pg_roles WHERE rolname = {}", quote_literal(username));
+        Ok(!self.execute_sql(&sql).await?.rows.is_empty())
+    }
+
+    /// Whether `creds` can log in; `Err` only when the server can't be reached
+    pub async fn check_login(creds: &DatabaseCredentials) -> Result<bool, DatabaseError> {
+        match Self::connect(creds).await {
+            Ok(_) => Ok(true),
+            // The server answered, but refused the login or database
+            Err(e) if e.as_db_error().is_some() => Ok(false),
+            Err(e) => Err(Self::connection_error(creds, e)),
+        }
+    }
+}
+
+#[async_trait]
+impl DatabaseBackend for PostgresBackend {
+    fn credentials(&self) -> &DatabaseCredentials {
+        &self.root_credentials
+    }
+
+    /// The simple query protocol returns every value as text
+    async fn execute_sql(&self, sql: &str) -> Result<QueryResult, DatabaseError> {
+        let creds = &self.login()?;
+        let client = Self::connect(creds)
+            .await
+            .map_err(|e| Self::connection_error(creds, e))?;
+        let messages = client
+            .simple_query(sql)
+            .await
+            .map_err(|e| Self::query_error(creds, e))?;
+
+        let mut output = QueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            affected_rows: 0,
+            last_insert_id: None,
+        };
+        // Multi-statement input describes the columns of each statement before its rows
+        for message in messages {
+            match message {
+                SimpleQueryMessage::RowDescription(columns) => {
+                    output.columns = columns.iter().map(|c| c.name().to_string()).collect();
+                    output.rows.clear();
+                }
+                SimpleQueryMessage::Row(row) => output.rows.push(
+                    (0..row.len())
+                        .map(|i| match row.get(i) {
+                            Some(text) => SqlValue::Text(text.to_string()),
+                            None => SqlValue::Null,
+                        })
+                        .collect(),
+                ),
+                SimpleQueryMessage::CommandComplete(count) => output.affected_rows += count,
+                _ => {}
+            }
+        }
+        Ok(output)
+    }
+
+    async fn create_database(&self, db_name: &str) -> Result<(), DatabaseError> {
+        // CREATE DATABASE has no IF NOT EXISTS
+        if !self.database_exists(db_name).await? {
+            let sql = format!("CREATE DATABASE {}", quote_identifier(db_name));
+            self.execute_sql(&sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn drop_database(&self, db_name: &str) -> Result<(), DatabaseError> {
+        // A database with open sessions can't be dropped, e.g. the project's dev server
+        let disconnect = format!(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+             WHERE datname = {} AND pid <> pg_backend_pid()",
+            quote_literal(db_name)
+        );
+        self.execute_sql(&disconnect).await?;
+        let sql = format!("DROP DATABASE IF EXISTS {}", quote_identifier(db_name));
+        self.execute_sql(&sql).await?;
+        Ok(())
+    }
+
+    /// The user becomes the owner of `db_name`, which also covers its public schema
+    async fn create_user(&self, username: &str, password: &str, db_name: &str) -> Result<(), DatabaseError> {
+        let verb = if self.role_exists(username).await? { "ALTER" } else { "CREATE" };
+        let role = format!(
+            "{} ROLE {} WITH LOGIN PASSWORD {}",
+            verb,
+            quote_identifier(username),
+            quote_literal(password)
+        );
+        self.execute_sql(&role).await?;
+
+        let owner = format!(
+            "ALTER DATABASE {} OWNER TO {}",
+            quote_identifier(db_name),
+            quote_identifier(username)
+        );
+        self.execute_sql(&owner).await?;
+
+        let grant = format!(
+            "GRANT ALL PRIVILEGES ON DATABASE {} TO {}",
+            quote_identifier(db_name),
+            quote_identifier(username)
+        );
+        self.execute_sql(&grant).await?;
+
+        Ok(())
+    }
+
+    async fn drop_user(&self, username: &str) -> Result<(), DatabaseError> {
+        let sql = format!("DROP ROLE IF EXISTS {}", quote_identifier(username));
+        self.execute_sql(&sql).await?;
+        Ok(())
+    }
+
+    async fn reset_password(&self, username: &str, new_password: &str) -> Result<(), DatabaseError> {
+        let sql = format!(
+            "ALTER ROLE {} WITH PASSWORD {}",
+            quote_identifier(username),
+            quote_literal(new_password)
+        );
+        self.execute_sql(&sql).await?;
+        Ok(())
+    }
+
+    async fn list_databases(&self) -> Result<Vec<String>, DatabaseError> {
+        let sql = format!(
+            "SELECT datname FROM pg_database WHERE NOT datistemplate AND datname <> {} ORDER BY datname",
+            quote_literal(SUPERUSER)
+        );
+        Ok(self.execute_sql(&sql).await?.first_column())
+    }
+
+    /// Dump without ownership, so it restores into a database owned by any user
+    async fn dump(&self, db_name: &str) -> Result<Vec<u8>, DatabaseError> {
+        let (mut cmd, _passfile) = self.client_command("pg_dump", db_name)?;
+        cmd.args(["--no-owner", "--encoding=UTF8"]);
+        run_client_tool(cmd).await
+    }
+
+    async fn restore(&self, db_name: &str, dump_path: &Path) -> Result<(), DatabaseError> {
+        let (mut cmd, _passfile) = self.client_command("psql", db_name)?;
+        cmd.args(["-q", "-v", "ON_ERROR_STOP=1", "-f"]).arg(dump_path);
+        run_client_tool(cmd).await?;
+        Ok(())
+    }
+}
+
+/// Password of the [`SUPERUSER`] of DevPort's cluster, generated and stored on first use
+pub fn superuser_password() -> Result<String, String> {
+    let credentials = CredentialManager::shared()?;
+    if let Some(password) = credentials.load_credential(POSTGRES_SUPERUSER_KEY)? {
+        return Ok(password);
+    }
+    let password = uuid::Uuid::new_v4().simple().to_string();
+    credentials.save_credential(POSTGRES_SUPERUSER_KEY, &password)?;
+    Ok(password)
+}
+
+/// Create a cluster in `data_dir` with initdb unless it already holds one.
+/// Every login needs a password (SCRAM); the superuser's is kept by the credential
+/// manager under [`POSTGRES_SUPERUSER_KEY`]. Returns whether a new cluster was created.
+pub fn init_cluster(bin_dir: &Path, data_dir: &Path) -> Result<bool, String> {
+    if data_dir.join("PG_VERSION").exists() {
+        return Ok(false);
+    }
+    let password = superuser_password()?;
+    fs::create_dir_all(data_dir)
+        .map_err(|e| format!("Failed to create {}: {}", data_dir.display(), e))?;
+
+    let pwfile = SecretFile::create("pw", &format!("{}\n", password)).map_err(|e| e.to_string())?;
+    let mut cmd = std::process::Command::new(client_tool(bin_dir, "initdb"));
+    cmd.arg("-D")
+        .arg(data_dir)
+        .args(["-U", SUPERUSER, "-E", "UTF8", "--auth=scram-sha-256", "--pwfile"])
+        .arg(pwfile.path());
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to run initdb: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "initdb failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(true)
+}
+
+/// `name` as a double-quoted identifier
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// `value` as a single-quoted string literal
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
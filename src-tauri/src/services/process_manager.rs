@@ -1,19 +1,40 @@
 use crate::models::process_info::ProcessInfo;
-use crate::models::{Project, ProjectType};
+use crate::models::{Project, ProjectType, RestartPolicy, StopOutcome, StopStrategy};
+use crate::services::log_manager::{LogManager, LogSource, LogSourceKind, SharedLogAlerts};
+use crate::services::port_conflict::find_port_conflict;
+use crate::services::process_stopper::{stop_process, StopTarget};
+use crate::services::secret_vault;
+use crate::services::server_url::parse_server_url;
+use crate::services::storage::Storage;
+use crate::state::AppState;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader};
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread;
-use tauri::{AppHandle, Emitter};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
 use thiserror::Error;
+use tokio::sync::Mutex;
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
+#[cfg(windows)]
+use std::process::Command;
+
+#[cfg(unix)]
+use crate::services::unix_process;
 
 /// Windows constant for creating a process without a console window
 #[cfg(windows)]
 pub const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+/// Windows flag giving a child its own process group so it can receive CTRL_BREAK_EVENT
+#[cfg(windows)]
+pub const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+
 #[derive(Error, Debug)]
 pub enum ProcessError {
     #[error("Failed to start process: {0}")]
@@ -35,7 +56,7 @@ pub struct KillResult {
 
 /// Kill a process tree by PID.
 /// On Windows, uses `taskkill /F /T /PID` to kill the process and all child processes.
-/// On Unix, uses `kill -9`.
+/// On Unix, sends SIGKILL to the process group if `pid` leads one, otherwise to `pid`.
 ///
 /// # Arguments
 /// * `pid` - The process ID to kill
@@ -69,24 +90,16 @@ pub fn kill_process_tree(pid: u32) -> KillResult {
         }
     }
 
-    #[cfg(not(windows))]
+    #[cfg(unix)]
     {
-        let output = Command::new("kill")
-            .args(["-9", &pid.to_string()])
-            .output();
+        let result = if unix_process::is_group_leader(pid) {
+            unix_process::signal_group(pid, libc::SIGKILL)
+        } else {
+            unix_process::signal_process(pid, libc::SIGKILL)
+        };
 
-        match output {
-            Ok(out) => {
-                if out.status.success() {
-                    KillResult { success: true, error: None }
-                } else {
-                    let stderr = String::from_utf8_lossy(&out.stderr).to_string();
-                    KillResult {
-                        success: false,
-                        error: Some(stderr.trim().to_string())
-                    }
-                }
-            }
+        match result {
+            Ok(()) => KillResult { success: true, error: None },
             Err(e) => KillResult {
                 success: false,
                 error: Some(e.to_string()),
@@ -101,14 +114,201 @@ pub fn kill_process_tree_silent(pid: u32) {
     let _ = kill_process_tree(pid);
 }
 
+/// How often the exit monitor polls managed children for termination
+const EXIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Time each graceful stop step gets when no project settings were captured
+const DEFAULT_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Payload for the `process-exited` event, emitted when a project process ends on its own
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessExit {
+    pub project_id: String,
+    pub pid: u32,
+    pub exit_code: Option<i32>,
+    pub success: bool,
+}
+
+/// What the exit monitor should do after a project process exited
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestartDecision {
+    /// Leave the project stopped
+    None,
+    /// Restart after `delay_ms`; `generation` identifies this scheduled restart
+    Restart { attempt: u32, delay_ms: u64, generation: u64 },
+    /// Too many consecutive restarts - give up and mark the project as errored
+    CrashLoop { attempts: u32 },
+}
+
+/// Consecutive-restart bookkeeping for one project
+#[derive(Debug, Clone)]
+struct RestartTracker {
+    attempts: u32,
+    started_at: Instant,
+    pending: bool,
+    generation: u64,
+}
+
+impl RestartTracker {
+    fn new() -> Self {
+        Self {
+            attempts: 0,
+            started_at: Instant::now(),
+            pending: false,
+            generation: 0,
+        }
+    }
+}
+
+/// Watches a project's stdout/stderr for the URL its dev server actually bound to.
+/// Vite, Next.js and friends move to the next port when theirs is taken, so this
+/// can differ from `Project::port`.
+#[derive(Clone)]
+struct PortDetector {
+    project_id: String,
+    configured_port: u16,
+    persist: bool,
+    found: Arc<AtomicBool>,
+}
+
+impl PortDetector {
+    fn new(project: &Project) -> Self {
+        Self {
+            project_id: project.id.clone(),
+            configured_port: project.port,
+            persist: project.persist_detected_port,
+            // Projects without a web port have nothing to correct
+            found: Arc::new(AtomicBool::new(project.port == 0)),
+        }
+    }
+
+    /// Check one output line; only the first local URL reported is used
+    fn inspect(&self, app: &AppHandle, line: &str) {
+        if self.found.load(Ordering::Relaxed) {
+            return;
+        }
+        let Some(server) = parse_server_url(line) else {
+            return;
+        };
+        if self.found.swap(true, Ordering::Relaxed) || server.port == self.configured_port {
+            return;
+        }
+
+        // Keep the running process info in step so health checks and the browser use it.
+        // Updated from a task: this reader thread must keep draining the pipe meanwhile.
+        if let Some(state) = app.try_state::<Arc<Mutex<AppState>>>() {
+            let state = state.inner().clone();
+            let project_id = self.project_id.clone();
+            let port = server.port;
+            tauri::async_runtime::spawn(async move {
+                if let Some(info) = state.lock().await.running_processes.get_mut(&project_id) {
+                    info.port = port;
+                }
+            });
+        }
+
+        let persisted = self.persist && self.persist_port(server.port);
+
+        let _ = app.emit(
+            "project-port-changed",
+            serde_json::json!({
+                "projectId": self.project_id,
+                "host": server.host,
+                "port": server.port,
+                "previousPort": self.configured_port,
+                "url": server.url(),
+                "persisted": persisted
+            }),
+        );
+    }
+
+    fn persist_port(&self, port: u16) -> bool {
+        let result = Storage::new().and_then(|storage| {
+            let mut project = storage.get_project(&self.project_id)?;
+            project.port = port;
+            project.updated_at = chrono::Utc::now().to_rfc3339();
+            storage.update_project(project)
+        });
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("Failed to save detected port for {}: {}", self.project_id, e);
+                false
+            }
+        }
+    }
+}
+
+/// How a running project should be stopped, captured when it starts
+struct StopPlan {
+    strategy: StopStrategy,
+    timeout: Duration,
+    work_dir: String,
+}
+
+impl StopPlan {
+    fn new(project: &Project) -> Self {
+        Self {
+            strategy: project.stop_strategy.clone(),
+            timeout: Duration::from_millis(project.stop_timeout),
+            work_dir: project.path.clone(),
+        }
+    }
+
+    /// Plan for a child whose project settings weren't captured
+    fn fallback() -> Self {
+        Self {
+            strategy: StopStrategy::default(),
+            timeout: DEFAULT_STOP_TIMEOUT,
+            work_dir: String::new(),
+        }
+    }
+
+    fn stop(&self, child: &mut Child) -> Result<StopOutcome, String> {
+        stop_process(
+            StopTarget::Child(child),
+            &self.strategy,
+            self.timeout,
+            Some(&self.work_dir),
+        )
+    }
+}
+
+/// A running project taken out of [`ProcessManager`] while it is being stopped
+pub struct StoppingProject {
+    project_id: String,
+    child: Child,
+    plan: StopPlan,
+}
+
+impl StoppingProject {
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    /// Run the stop strategy and wait for the process to exit.
+    /// Blocks for up to the stop timeout.
+    pub fn stop(&mut self) -> Result<StopOutcome, String> {
+        self.plan.stop(&mut self.child)
+    }
+}
+
+/// Owns the `Child` handle of every running project.
+/// A single instance lives in Tauri state (see `SharedProcessManager`) so that
+/// handles survive across commands and exits can be reaped.
 pub struct ProcessManager {
     processes: HashMap<String, Child>,
+    restarts: HashMap<String, RestartTracker>,
+    stop_plans: HashMap<String, StopPlan>,
 }
 
 impl ProcessManager {
     pub fn new() -> Self {
         Self {
             processes: HashMap::new(),
+            restarts: HashMap::new(),
+            stop_plans: HashMap::new(),
         }
     }
 
@@ -197,13 +397,24 @@ impl ProcessManager {
         }
     }
 
+    /// Spawn a project's start command.
+    /// Without an app handle (headless CLI) the output is inherited by the caller's
+    /// terminal instead of being streamed as events.
     pub fn start_project(
         &mut self,
         project: &Project,
-        app_handle: AppHandle,
+        app_handle: Option<AppHandle>,
     ) -> Result<ProcessInfo, ProcessError> {
         let project_id = project.id.clone();
 
+        // Replacing a tracked child would orphan it with the port still bound
+        if self.processes.contains_key(&project_id) {
+            return Err(ProcessError::StartError(format!(
+                "{} is already running",
+                project.name
+            )));
+        }
+
         // Build environment variables
         let mut env_vars = project.env_vars.clone();
         // Only inject PORT for web projects (port > 0)
@@ -236,14 +447,25 @@ impl ProcessManager {
             #[cfg(not(windows))]
             let venv_bin = venv_abs.join("bin");
 
+            #[cfg(windows)]
+            const PATH_SEPARATOR: char = ';';
+            #[cfg(not(windows))]
+            const PATH_SEPARATOR: char = ':';
+
             if let Ok(current_path) = std::env::var("PATH") {
                 env_vars.insert(
                     "PATH".to_string(),
-                    format!("{};{}", venv_bin.to_string_lossy(), current_path),
+                    format!("{}{}{}", venv_bin.to_string_lossy(), PATH_SEPARATOR, current_path),
                 );
             }
         }
 
+        // Resolve ${vault:name} placeholders of .env files and project variables;
+        // process variables take precedence over what dotenv loaders read from disk
+        let vault_vars = secret_vault::resolve_project_env(project, &env_vars)
+            .map_err(ProcessError::StartError)?;
+        env_vars.extend(vault_vars);
+
         // Build command with port option
         let command_with_port = Self::build_command_with_port(
             &project.start_command,
@@ -251,6 +473,14 @@ impl ProcessManager {
             project.port,
         );
 
+        let output = || {
+            if app_handle.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            }
+        };
+
         // Start process - on Windows, run through cmd.exe to handle .cmd scripts
         // Use CREATE_NO_WINDOW to prevent console window from appearing
         #[cfg(target_os = "windows")]
@@ -258,40 +488,63 @@ impl ProcessManager {
             .args(["/C", &command_with_port])
             .current_dir(&project.path)
             .envs(&env_vars)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .creation_flags(CREATE_NO_WINDOW)
+            .stdout(output())
+            .stderr(output())
+            .creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP)
             .spawn()
             .map_err(|e| ProcessError::StartError(e.to_string()))?;
 
-        #[cfg(not(target_os = "windows"))]
+        // Elsewhere run through `sh -c` in a new process group so the whole tree can be stopped
+        #[cfg(unix)]
         let mut child = {
-            let parts: Vec<&str> = command_with_port.split_whitespace().collect();
-            if parts.is_empty() {
+            if command_with_port.trim().is_empty() {
                 return Err(ProcessError::StartError("Empty command".to_string()));
             }
-            Command::new(parts[0])
-                .args(&parts[1..])
+            unix_process::shell_command(&command_with_port)
                 .current_dir(&project.path)
                 .envs(&env_vars)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
+                // A background process group reading the terminal would be stopped by SIGTTIN
+                .stdin(Stdio::null())
+                .stdout(output())
+                .stderr(output())
                 .spawn()
                 .map_err(|e| ProcessError::StartError(e.to_string()))?
         };
 
         let pid = child.id();
 
+        // Persist output to the project's log files so it survives app restarts
+        let log_manager = LogManager::with_config(&project.log_config);
+        if app_handle.is_some() {
+            if let Err(e) = log_manager.cleanup_project_logs(&project.name) {
+                eprintln!("Failed to clean up old logs of {}: {}", project.name, e);
+            }
+            let _ = log_manager.write_log(
+                &log_manager.get_project_log_path(&project.name, "stdout"),
+                &format!("Project {} started with PID {}", project.name, pid),
+            );
+        }
+
+        // Every captured line is also checked against the log alert rules
+        let log_alerts = app_handle
+            .as_ref()
+            .and_then(|app| app.try_state::<SharedLogAlerts>())
+            .map(|alerts| alerts.inner().clone());
+        let log_origin = LogSource { kind: LogSourceKind::Project, name: project.name.clone() };
+
         // Set up log streaming
         let stdout = child.stdout.take();
         let stderr = child.stderr.take();
         let project_id_clone = project_id.clone();
-        let app_handle_clone = app_handle.clone();
+        let port_detector = PortDetector::new(project);
 
-        if let Some(stdout) = stdout {
+        if let (Some(stdout), Some(app)) = (stdout, app_handle.clone()) {
             let project_id = project_id_clone.clone();
-            let app = app_handle_clone.clone();
             let project_type = project.project_type.clone();
+            let port_detector = port_detector.clone();
+            let mut log_writer = log_manager
+                .project_log_writer(&project.name, "stdout")
+                .with_alerts(log_origin.clone(), log_alerts.clone());
             thread::spawn(move || {
                 let reader = BufReader::new(stdout);
                 let mut launched_notified = false;
@@ -300,8 +553,11 @@ impl ProcessManager {
                     if trimmed.is_empty() {
                         continue;
                     }
+                    let _ = log_writer.write_line(&line);
                     let lower = trimmed.to_lowercase();
 
+                    port_detector.inspect(&app, trimmed);
+
                     // Detect framework-specific readiness from stdout
                     if !launched_notified {
                         let is_ready = match project_type {
@@ -377,10 +633,12 @@ impl ProcessManager {
             });
         }
 
-        if let Some(stderr) = stderr {
+        if let (Some(stderr), Some(app)) = (stderr, app_handle.clone()) {
             let project_id = project_id_clone.clone();
-            let app = app_handle_clone.clone();
             let is_tauri = matches!(project.project_type, ProjectType::Tauri);
+            let mut log_writer = log_manager
+                .project_log_writer(&project.name, "stderr")
+                .with_alerts(log_origin.clone(), log_alerts.clone());
             thread::spawn(move || {
                 let reader = BufReader::new(stderr);
                 let mut build_notified = false;
@@ -390,6 +648,10 @@ impl ProcessManager {
                     if trimmed.is_empty() {
                         continue;
                     }
+                    let _ = log_writer.write_line(&line);
+
+                    // Python servers (Flask, Uvicorn) log their URL to stderr
+                    port_detector.inspect(&app, trimmed);
 
                     // Detect Tauri/Cargo build status from stderr
                     if is_tauri {
@@ -508,64 +770,196 @@ impl ProcessManager {
         }
 
         self.processes.insert(project_id.clone(), child);
+        self.stop_plans.insert(project_id.clone(), StopPlan::new(project));
 
-        // Emit starting status (before process-started so UI can show "Starting...")
-        let _ = app_handle.emit(
-            "build-status",
-            serde_json::json!({
-                "projectId": project_id,
-                "status": "starting"
-            }),
-        );
+        // Record the start time so a stable run resets the restart counter
+        let tracker = self
+            .restarts
+            .entry(project_id.clone())
+            .or_insert_with(RestartTracker::new);
+        tracker.started_at = Instant::now();
+        tracker.pending = false;
 
-        // Emit process started event
-        let _ = app_handle.emit(
-            "process-started",
-            serde_json::json!({
-                "projectId": project_id,
-                "pid": pid
-            }),
-        );
+        if let Some(app_handle) = &app_handle {
+            // Emit starting status (before process-started so UI can show "Starting...")
+            let _ = app_handle.emit(
+                "build-status",
+                serde_json::json!({
+                    "projectId": project_id,
+                    "status": "starting"
+                }),
+            );
+
+            // Emit process started event
+            let _ = app_handle.emit(
+                "process-started",
+                serde_json::json!({
+                    "projectId": project_id,
+                    "pid": pid
+                }),
+            );
+        }
 
         Ok(ProcessInfo::new(project_id, pid, project.port))
     }
 
-    pub fn stop_project(
-        &mut self,
-        project_id: &str,
-        app_handle: AppHandle,
-    ) -> Result<(), ProcessError> {
-        if let Some(mut child) = self.processes.remove(project_id) {
-            let pid = child.id();
-
-            // Kill the process tree on Windows
-            #[cfg(windows)]
-            let _ = Command::new("taskkill")
-                .args(["/F", "/T", "/PID", &pid.to_string()])
-                .creation_flags(CREATE_NO_WINDOW)
-                .output();
+    /// Take a running project out of the manager so it can be stopped without
+    /// holding the manager's lock; hand the result back with [`Self::finish_stop`].
+    pub fn take_for_stop(&mut self, project_id: &str) -> Result<StoppingProject, ProcessError> {
+        let child = self
+            .processes
+            .remove(project_id)
+            .ok_or_else(|| ProcessError::NotFound(project_id.to_string()))?;
+        let plan = self.stop_plans.remove(project_id).unwrap_or_else(StopPlan::fallback);
+        Ok(StoppingProject {
+            project_id: project_id.to_string(),
+            child,
+            plan,
+        })
+    }
 
-            #[cfg(not(windows))]
-            let _ = Command::new("kill")
-                .args(["-9", &pid.to_string()])
-                .output();
+    /// Take every running project, e.g. to stop them all on shutdown
+    pub fn take_all_for_stop(&mut self) -> Vec<StoppingProject> {
+        let ids: Vec<String> = self.processes.keys().cloned().collect();
+        ids.iter()
+            .filter_map(|id| self.take_for_stop(id).ok())
+            .collect()
+    }
 
-            // Also try to kill directly
-            let _ = child.kill();
-            let _ = child.wait();
+    /// Record the result of [`StoppingProject::stop`]. A process that is still
+    /// running (force kill disabled) is tracked again.
+    pub fn finish_stop(
+        &mut self,
+        stopping: StoppingProject,
+        result: Result<StopOutcome, String>,
+        app_handle: Option<AppHandle>,
+    ) -> Result<StopOutcome, ProcessError> {
+        let StoppingProject { project_id, child, plan } = stopping;
+        let outcome = match result {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                self.processes.insert(project_id.clone(), child);
+                self.stop_plans.insert(project_id, plan);
+                return Err(ProcessError::StopError(e));
+            }
+        };
+        self.restarts.remove(&project_id);
 
-            // Emit process stopped event
+        // Emit process stopped event
+        if let Some(app_handle) = app_handle {
             let _ = app_handle.emit(
                 "process-stopped",
                 serde_json::json!({
-                    "projectId": project_id
+                    "projectId": project_id,
+                    "exitCode": outcome.exit_code,
+                    "stoppedBy": outcome.stopped_by
                 }),
             );
+        }
 
-            Ok(())
-        } else {
-            Err(ProcessError::NotFound(project_id.to_string()))
+        Ok(outcome)
+    }
+
+    /// Stop a running project following its stop strategy and wait for it to exit.
+    /// Reports which step ended the process and the exit code if the platform gave one.
+    /// Blocks for up to the stop timeout; async callers should use
+    /// [`Self::take_for_stop`] and stop outside the lock instead.
+    pub fn stop_project(
+        &mut self,
+        project_id: &str,
+        app_handle: Option<AppHandle>,
+    ) -> Result<StopOutcome, ProcessError> {
+        let mut stopping = self.take_for_stop(project_id)?;
+        let result = stopping.stop();
+        self.finish_stop(stopping, result, app_handle)
+    }
+
+    /// Remove and report every child that has exited since the last call
+    pub fn reap_exited(&mut self) -> Vec<ProcessExit> {
+        let mut exited = Vec::new();
+        self.processes.retain(|project_id, child| match child.try_wait() {
+            Ok(Some(status)) => {
+                exited.push(ProcessExit {
+                    project_id: project_id.clone(),
+                    pid: child.id(),
+                    exit_code: status.code(),
+                    success: status.success(),
+                });
+                false
+            }
+            _ => true,
+        });
+
+        for exit in &exited {
+            self.stop_plans.remove(&exit.project_id);
+            // Don't leave orphaned grandchildren (vite, esbuild) holding the port
+            #[cfg(unix)]
+            if unix_process::group_alive(exit.pid) {
+                let _ = unix_process::signal_group(exit.pid, libc::SIGTERM);
+            }
         }
+        exited
+    }
+
+    /// Decide whether an exited project should be restarted according to its policy.
+    /// Consecutive restarts back off exponentially; a process that stayed up for
+    /// `reset_after` resets the counter.
+    pub fn schedule_restart(&mut self, exit: &ProcessExit, policy: &RestartPolicy) -> RestartDecision {
+        if !policy.should_restart(exit.success) {
+            self.restarts.remove(&exit.project_id);
+            return RestartDecision::None;
+        }
+
+        let tracker = self
+            .restarts
+            .entry(exit.project_id.clone())
+            .or_insert_with(RestartTracker::new);
+
+        if tracker.started_at.elapsed() >= Duration::from_millis(policy.reset_after) {
+            tracker.attempts = 0;
+        }
+
+        if tracker.attempts >= policy.max_restarts {
+            let attempts = tracker.attempts;
+            self.restarts.remove(&exit.project_id);
+            return RestartDecision::CrashLoop { attempts };
+        }
+
+        let delay_ms = policy.backoff_delay(tracker.attempts);
+        tracker.attempts += 1;
+        tracker.pending = true;
+        tracker.generation += 1;
+
+        RestartDecision::Restart {
+            attempt: tracker.attempts,
+            delay_ms,
+            generation: tracker.generation,
+        }
+    }
+
+    /// Claim a scheduled restart. Returns false if it was cancelled or superseded
+    /// (e.g. the user stopped or started the project during the backoff delay).
+    pub fn take_pending_restart(&mut self, project_id: &str, generation: u64) -> bool {
+        match self.restarts.get_mut(project_id) {
+            Some(tracker) if tracker.pending && tracker.generation == generation => {
+                tracker.pending = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Cancel a scheduled restart. Returns true if one was pending.
+    pub fn cancel_restart(&mut self, project_id: &str) -> bool {
+        self.restarts
+            .remove(project_id)
+            .map(|tracker| tracker.pending)
+            .unwrap_or(false)
+    }
+
+    /// Forget restart history, e.g. when the user starts a project manually
+    pub fn reset_restarts(&mut self, project_id: &str) {
+        self.restarts.remove(project_id);
     }
 
     pub fn is_running(&self, project_id: &str) -> bool {
@@ -582,3 +976,266 @@ impl Default for ProcessManager {
         Self::new()
     }
 }
+
+/// Shared process manager type
+pub type SharedProcessManager = Arc<Mutex<ProcessManager>>;
+
+pub fn init_process_manager() -> SharedProcessManager {
+    Arc::new(Mutex::new(ProcessManager::new()))
+}
+
+/// Spawn the background task that reaps exited project processes.
+/// Exited projects are removed from `AppState.running_processes`, a
+/// `process-exited` event carrying the exit code is emitted, and the
+/// project's restart policy decides whether it comes back.
+pub fn spawn_exit_monitor(
+    app_handle: AppHandle,
+    process_manager: SharedProcessManager,
+    app_state: Arc<Mutex<AppState>>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(EXIT_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let exited = process_manager.lock().await.reap_exited();
+            if exited.is_empty() {
+                continue;
+            }
+
+            {
+                let mut state = app_state.lock().await;
+                for exit in &exited {
+                    state.running_processes.remove(&exit.project_id);
+                    let _ = app_handle.emit("process-exited", exit);
+                }
+            }
+
+            for exit in exited {
+                handle_restart_policy(&app_handle, &process_manager, &app_state, &exit).await;
+            }
+        }
+    });
+}
+
+/// Apply the project's restart policy after an unexpected exit
+async fn handle_restart_policy(
+    app_handle: &AppHandle,
+    process_manager: &SharedProcessManager,
+    app_state: &Arc<Mutex<AppState>>,
+    exit: &ProcessExit,
+) {
+    let policy = match Storage::new().and_then(|s| s.get_project(&exit.project_id)) {
+        Ok(project) => project.restart_policy,
+        Err(_) => return,
+    };
+
+    let decision = process_manager.lock().await.schedule_restart(exit, &policy);
+
+    match decision {
+        RestartDecision::None => {}
+        RestartDecision::Restart { attempt, delay_ms, generation } => {
+            let _ = app_handle.emit(
+                "process-restarting",
+                serde_json::json!({
+                    "projectId": exit.project_id,
+                    "attempt": attempt,
+                    "delayMs": delay_ms,
+                    "exitCode": exit.exit_code
+                }),
+            );
+
+            let app = app_handle.clone();
+            let process_manager = process_manager.clone();
+            let app_state = app_state.clone();
+            let project_id = exit.project_id.clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                restart_project(&app, &process_manager, &app_state, &project_id, generation).await;
+            });
+        }
+        RestartDecision::CrashLoop { attempts } => {
+            let _ = app_handle.emit(
+                "build-status",
+                serde_json::json!({
+                    "projectId": exit.project_id,
+                    "status": "error",
+                    "message": format!(
+                        "Crash loop detected: exited {} times in a row (last exit code: {})",
+                        attempts + 1,
+                        exit.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "unknown".to_string())
+                    )
+                }),
+            );
+            let _ = app_handle.emit(
+                "process-crash-loop",
+                serde_json::json!({
+                    "projectId": exit.project_id,
+                    "restarts": attempts,
+                    "exitCode": exit.exit_code
+                }),
+            );
+        }
+    }
+}
+
+/// Start a project again once its backoff delay has elapsed
+async fn restart_project(
+    app_handle: &AppHandle,
+    process_manager: &SharedProcessManager,
+    app_state: &Arc<Mutex<AppState>>,
+    project_id: &str,
+    generation: u64,
+) {
+    let project = match Storage::new().and_then(|s| s.get_project(project_id)) {
+        Ok(project) => project,
+        Err(_) => return,
+    };
+
+    // The port may have been taken while the project was down; starting anyway
+    // would only fail to bind and use up the restart budget
+    let (id, port) = (project.id.clone(), project.port);
+    let conflict = tokio::task::spawn_blocking(move || find_port_conflict(&id, port))
+        .await
+        .ok()
+        .flatten();
+
+    let mut state = app_state.lock().await;
+    let mut manager = process_manager.lock().await;
+
+    if !manager.take_pending_restart(project_id, generation) {
+        return;
+    }
+
+    let result = match conflict {
+        Some(conflict) => Err(conflict.message()),
+        None => manager
+            .start_project(&project, Some(app_handle.clone()))
+            .map_err(|e| e.to_string()),
+    };
+
+    match result {
+        Ok(process_info) => {
+            state
+                .running_processes
+                .insert(project_id.to_string(), process_info);
+        }
+        Err(e) => {
+            manager.reset_restarts(project_id);
+            let _ = app_handle.emit(
+                "build-status",
+                serde_json::json!({
+                    "projectId": project_id,
+                    "status": "error",
+                    "message": format!("Auto-restart failed: {}", e)
+                }),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::project::RestartMode;
+
+    fn exit(success: bool) -> ProcessExit {
+        ProcessExit {
+            project_id: "api".to_string(),
+            pid: 4242,
+            exit_code: Some(if success { 0 } else { 1 }),
+            success,
+        }
+    }
+
+    fn policy() -> RestartPolicy {
+        RestartPolicy {
+            mode: RestartMode::OnFailure,
+            restart_delay: 1000,
+            max_delay: 5000,
+            max_restarts: 4,
+            reset_after: 60000,
+        }
+    }
+
+    fn generation(decision: RestartDecision) -> u64 {
+        match decision {
+            RestartDecision::Restart { generation, .. } => generation,
+            other => panic!("expected a restart, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_restart_backs_off_until_crash_loop() {
+        let mut manager = ProcessManager::new();
+        let delays: Vec<(u32, u64)> = (0..4)
+            .map(|_| match manager.schedule_restart(&exit(false), &policy()) {
+                RestartDecision::Restart { attempt, delay_ms, .. } => (attempt, delay_ms),
+                other => panic!("expected a restart, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(delays, vec![(1, 1000), (2, 2000), (3, 4000), (4, 5000)]);
+
+        assert_eq!(
+            manager.schedule_restart(&exit(false), &policy()),
+            RestartDecision::CrashLoop { attempts: 4 }
+        );
+        // The cutoff forgets the history, so a later manual start gets a full budget
+        assert!(matches!(
+            manager.schedule_restart(&exit(false), &policy()),
+            RestartDecision::Restart { attempt: 1, delay_ms: 1000, .. }
+        ));
+    }
+
+    #[test]
+    fn test_stable_run_resets_restart_counter() {
+        let mut manager = ProcessManager::new();
+        manager.schedule_restart(&exit(false), &policy());
+        manager.schedule_restart(&exit(false), &policy());
+
+        // Up for longer than reset_after before crashing again
+        let tracker = manager.restarts.get_mut("api").unwrap();
+        tracker.started_at = Instant::now()
+            .checked_sub(Duration::from_millis(policy().reset_after + 1000))
+            .unwrap();
+        assert!(matches!(
+            manager.schedule_restart(&exit(false), &policy()),
+            RestartDecision::Restart { attempt: 1, delay_ms: 1000, .. }
+        ));
+    }
+
+    #[test]
+    fn test_restart_mode_decides_on_exit_status() {
+        let mut manager = ProcessManager::new();
+        assert_eq!(manager.schedule_restart(&exit(true), &policy()), RestartDecision::None);
+
+        let never = RestartPolicy { mode: RestartMode::Never, ..policy() };
+        assert_eq!(manager.schedule_restart(&exit(false), &never), RestartDecision::None);
+
+        let always = RestartPolicy { mode: RestartMode::Always, ..policy() };
+        generation(manager.schedule_restart(&exit(true), &always));
+    }
+
+    #[test]
+    fn test_scheduled_restart_is_superseded() {
+        let mut manager = ProcessManager::new();
+
+        // A newer schedule replaces the older one, and a restart is claimed only once
+        let first = generation(manager.schedule_restart(&exit(false), &policy()));
+        let second = generation(manager.schedule_restart(&exit(false), &policy()));
+        assert!(!manager.take_pending_restart("api", first));
+        assert!(manager.take_pending_restart("api", second));
+        assert!(!manager.take_pending_restart("api", second));
+
+        // Stopped by the user during the backoff delay
+        let pending = generation(manager.schedule_restart(&exit(false), &policy()));
+        assert!(manager.cancel_restart("api"));
+        assert!(!manager.take_pending_restart("api", pending));
+        assert!(!manager.cancel_restart("api"));
+
+        // Started by the user during the backoff delay
+        let pending = generation(manager.schedule_restart(&exit(false), &policy()));
+        manager.reset_restarts("api");
+        assert!(!manager.take_pending_restart("api", pending));
+    }
+}
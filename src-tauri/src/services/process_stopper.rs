@@ -0,0 +1,302 @@
+use crate::models::{StopOutcome, StopSignal, StopStep, StopStrategy};
+use crate::services::health_checker::HealthChecker;
+use crate::services::process_manager::kill_process_tree;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use crate::services::unix_process;
+#[cfg(windows)]
+use crate::services::process_manager::CREATE_NO_WINDOW;
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+/// How often a stopping process is polled
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Process being stopped: one we spawned, or one found by PID (e.g. started by XAMPP)
+pub enum StopTarget<'a> {
+    Child(&'a mut Child),
+    External(u32),
+}
+
+impl StopTarget<'_> {
+    fn pid(&self) -> u32 {
+        match self {
+            StopTarget::Child(child) => child.id(),
+            StopTarget::External(pid) => *pid,
+        }
+    }
+
+    /// `Some(exit_code)` once the process has exited
+    fn poll(&mut self) -> Option<Option<i32>> {
+        match self {
+            StopTarget::Child(child) => child.try_wait().ok().flatten().map(|s| s.code()),
+            StopTarget::External(pid) => (!HealthChecker::is_pid_alive(*pid)).then_some(None),
+        }
+    }
+
+    /// Wait until the process has exited, or the timeout passes
+    fn wait(&mut self, timeout: Duration) -> Option<Option<i32>> {
+        let deadline = Instant::now() + timeout;
+
+        // Our own children lead a process group; wait for their descendants too
+        #[cfg(unix)]
+        if let StopTarget::Child(child) = self {
+            return unix_process::wait_for_group(child, deadline).map(|s| s.code());
+        }
+
+        loop {
+            if let Some(code) = self.poll() {
+                return Some(code);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn signal(&self, signal: &StopSignal) {
+        let pid = self.pid();
+
+        #[cfg(unix)]
+        {
+            let signal = match signal {
+                StopSignal::Terminate => libc::SIGTERM,
+                StopSignal::Interrupt | StopSignal::CtrlBreak => libc::SIGINT,
+                StopSignal::None => return,
+            };
+            let _ = match self {
+                StopTarget::Child(_) => unix_process::signal_group(pid, signal),
+                StopTarget::External(_) => unix_process::signal_process(pid, signal),
+            };
+        }
+
+        #[cfg(windows)]
+        match signal {
+            StopSignal::Terminate => {
+                let _ = Command::new("taskkill")
+                    .args(["/T", "/PID", &pid.to_string()])
+                    .creation_flags(CREATE_NO_WINDOW)
+                    .output();
+            }
+            // Console control events only reach processes started with CREATE_NEW_PROCESS_GROUP
+            StopSignal::Interrupt | StopSignal::CtrlBreak => {
+                use winapi::um::wincon::{GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT};
+                // SAFETY: GenerateConsoleCtrlEvent takes plain integers
+                unsafe {
+                    GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, pid);
+                }
+            }
+            StopSignal::None => {}
+        }
+    }
+
+    fn force_kill(&mut self) -> Result<Option<i32>, String> {
+        let pid = self.pid();
+        match self {
+            StopTarget::Child(child) => {
+                kill_process_tree(pid);
+                // The tree kill can miss a child that is mid-exit; make sure the handle goes too
+                let _ = child.kill();
+                child.wait().map(|s| s.code()).map_err(|e| e.to_string())
+            }
+            StopTarget::External(_) => {
+                let result = kill_process_tree(pid);
+                if result.success {
+                    Ok(None)
+                } else {
+                    Err(result.error.unwrap_or_else(|| "Unknown error".to_string()))
+                }
+            }
+        }
+    }
+
+    /// Kill descendants left behind after the main process exited
+    fn reap_stragglers(&self) {
+        #[cfg(unix)]
+        if let StopTarget::Child(child) = self {
+            if unix_process::group_alive(child.id()) {
+                let _ = unix_process::signal_group(child.id(), libc::SIGKILL);
+            }
+        }
+    }
+}
+
+/// Stop a process following `strategy`: stop command, then signal, then force kill.
+/// Each graceful step gets `timeout` before the next one runs.
+pub fn stop_process(
+    mut target: StopTarget<'_>,
+    strategy: &StopStrategy,
+    timeout: Duration,
+    work_dir: Option<&str>,
+) -> Result<StopOutcome, String> {
+    let outcome = |stopped_by, exit_code| StopOutcome { stopped_by, exit_code };
+
+    if let Some(code) = target.poll() {
+        target.reap_stragglers();
+        return Ok(outcome(StopStep::AlreadyExited, code));
+    }
+
+    if let Some(command) = strategy.command.as_deref().filter(|c| !c.trim().is_empty()) {
+        match run_stop_command(command, work_dir, timeout) {
+            Ok(()) => {
+                if let Some(code) = target.wait(timeout) {
+                    if strategy.force_kill {
+                        target.reap_stragglers();
+                    }
+                    return Ok(outcome(StopStep::Command, code));
+                }
+            }
+            // Nothing is shutting the process down, so don't wait for it
+            Err(e) => eprintln!("Stop command failed for PID {}: {}", target.pid(), e),
+        }
+    }
+
+    if strategy.signal != StopSignal::None {
+        target.signal(&strategy.signal);
+        if let Some(code) = target.wait(timeout) {
+            if strategy.force_kill {
+                target.reap_stragglers();
+            }
+            return Ok(outcome(StopStep::Signal, code));
+        }
+    }
+
+    if !strategy.force_kill {
+        return Err(format!(
+            "Process {} is still running after {}ms and force kill is disabled",
+            target.pid(),
+            timeout.as_millis()
+        ));
+    }
+
+    let code = target.force_kill()?;
+    Ok(outcome(StopStep::ForceKill, code))
+}
+
+/// Run a stop command through the shell, giving up after `timeout`
+fn run_stop_command(command: &str, work_dir: Option<&str>, timeout: Duration) -> Result<(), String> {
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]).creation_flags(CREATE_NO_WINDOW);
+        cmd
+    };
+    #[cfg(not(windows))]
+    let mut cmd = {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    if let Some(dir) = work_dir.filter(|d| !d.is_empty()) {
+        cmd.current_dir(dir);
+    }
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) if status.success() => return Ok(()),
+            Some(status) => return Err(format!("exited with {}", status)),
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {}ms", timeout.as_millis()));
+            }
+            None => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn spawn(command: &str) -> Child {
+        let child = unix_process::shell_command(command).spawn().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        child
+    }
+
+    #[test]
+    fn test_signal_ends_process() {
+        let mut child = spawn("sleep 30");
+        let outcome = stop_process(
+            StopTarget::Child(&mut child),
+            &StopStrategy::default(),
+            Duration::from_secs(2),
+            None,
+        )
+        .unwrap();
+        assert_eq!(outcome.stopped_by, StopStep::Signal);
+    }
+
+    #[test]
+    fn test_force_kill_after_ignored_signal() {
+        let mut child = spawn("trap '' TERM; sleep 30");
+        let outcome = stop_process(
+            StopTarget::Child(&mut child),
+            &StopStrategy::default(),
+            Duration::from_millis(300),
+            None,
+        )
+        .unwrap();
+        assert_eq!(outcome.stopped_by, StopStep::ForceKill);
+    }
+
+    #[test]
+    fn test_failed_stop_command_skips_its_timeout() {
+        let mut child = spawn("sleep 30");
+        let strategy = StopStrategy {
+            command: Some("exit 1".to_string()),
+            ..StopStrategy::default()
+        };
+        let started = Instant::now();
+        let outcome = stop_process(
+            StopTarget::Child(&mut child),
+            &strategy,
+            Duration::from_secs(5),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.stopped_by, StopStep::Signal);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_stop_command_ends_process() {
+        let dir = std::env::temp_dir().join(format!("devport-stop-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut child = unix_process::shell_command("while [ ! -f stop ]; do sleep 0.1; done")
+            .current_dir(&dir)
+            .spawn()
+            .unwrap();
+
+        let strategy = StopStrategy {
+            signal: StopSignal::None,
+            command: Some("touch stop".to_string()),
+            force_kill: false,
+        };
+        let outcome = stop_process(
+            StopTarget::Child(&mut child),
+            &strategy,
+            Duration::from_secs(2),
+            dir.to_str(),
+        )
+        .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(outcome.stopped_by, StopStep::Command);
+        assert_eq!(outcome.exit_code, Some(0));
+    }
+}
@@ -53,7 +53,12 @@ impl ProjectDetector {
                 .map_err(|e| format!("Failed to write tauri.conf.json: {}", e))?;
         }
 
-        // 2. Update vite.config.ts: port: NNNN (all occurrences)
+        // 2. Update vite.config.ts
+        Self::update_vite_port(project_path, new_port)
+    }
+
+    /// Update `port: NNNN` (all occurrences) in the first vite.config.* found
+    pub fn update_vite_port(project_path: &Path, new_port: u16) -> Result<(), String> {
         let vite_configs = ["vite.config.ts", "vite.config.js", "vite.config.mts", "vite.config.mjs"];
         for config_name in &vite_configs {
             let config_path = project_path.join(config_name);
@@ -76,6 +81,46 @@ impl ProjectDetector {
         Ok(())
     }
 
+    /// Update the `-p`/`--port` flag of the package.json dev script (Next.js).
+    /// Edited as text so the file's key order and formatting are kept.
+    pub fn update_next_port(project_path: &Path, new_port: u16) -> Result<(), String> {
+        let package_json = project_path.join("package.json");
+        if !package_json.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&package_json)
+            .map_err(|e| format!("Failed to read package.json: {}", e))?;
+
+        let re = regex::Regex::new(r#"("dev"\s*:\s*"[^"]*?(?:-p|--port)(?:\s+|=))\d+"#)
+            .map_err(|e| format!("Regex error: {}", e))?;
+        let new_content = re.replace(&content, format!("${{1}}{}", new_port).as_str());
+
+        // No port flag in the script: the port is passed on the command line instead
+        if new_content != content {
+            fs::write(&package_json, new_content.as_ref())
+                .map_err(|e| format!("Failed to write package.json: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Rewrite whichever config file pins the dev server port for this project type
+    pub fn update_configured_port(
+        project_path: &Path,
+        project_type: &ProjectType,
+        new_port: u16,
+    ) -> Result<(), String> {
+        match project_type {
+            ProjectType::Tauri => Self::update_tauri_port(project_path, new_port),
+            ProjectType::Vite | ProjectType::React | ProjectType::Vue | ProjectType::Svelte => {
+                Self::update_vite_port(project_path, new_port)
+            }
+            ProjectType::NextJs => Self::update_next_port(project_path, new_port),
+            _ => Ok(()),
+        }
+    }
+
     /// Check for pnpm-lock.yaml
     fn has_pnpm_lock(project_path: &Path) -> bool {
         project_path.join("pnpm-lock.yaml").exists()
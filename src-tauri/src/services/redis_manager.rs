@@ -0,0 +1,397 @@
+//! Redis-compatible cache service (Redis, Valkey, Memurai)
+//!
+//! DevPort runs one server for every project and gives each project its own logical
+//! database, so queues, caches and sessions of different projects never share keys.
+//! The project's index is handed to it as `REDIS_URL=redis://127.0.0.1:6379/<index>`.
+
+use crate::models::Service;
+use crate::services::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 6379;
+
+/// Eviction policies accepted by `maxmemory-policy`
+const MAXMEMORY_POLICIES: &[&str] = &[
+    "noeviction",
+    "allkeys-lru",
+    "allkeys-lfu",
+    "allkeys-random",
+    "volatile-lru",
+    "volatile-lfu",
+    "volatile-random",
+    "volatile-ttl",
+];
+
+/// Settings DevPort manages in `redis.conf`; other directives are left alone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedisConfig {
+    pub port: u16,
+    /// Memory limit, e.g. "256mb"
+    pub maxmemory: String,
+    pub maxmemory_policy: String,
+    /// Where snapshots and the append-only file are written
+    pub data_dir: String,
+    /// Number of logical databases, i.e. how many projects get one
+    pub databases: u16,
+    /// Log every write to an append-only file in addition to snapshots
+    pub appendonly: bool,
+}
+
+impl Default for RedisConfig {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            maxmemory: "256mb".to_string(),
+            maxmemory_policy: "allkeys-lru".to_string(),
+            data_dir: Service::redis_data_dir().to_string_lossy().to_string(),
+            databases: 16,
+            appendonly: false,
+        }
+    }
+}
+
+impl RedisConfig {
+    /// Settings of `redis.conf`, or the defaults when it doesn't exist yet
+    pub fn load() -> Self {
+        fs::read_to_string(Service::redis_config_path())
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    /// Managed settings of a config file, defaults for those it lacks
+    pub fn parse(content: &str) -> Self {
+        let mut config = Self::default();
+        for line in content.lines() {
+            let Some((key, value)) = split_directive(line) else {
+                continue;
+            };
+            match key.as_str() {
+                "port" => config.port = value.parse().unwrap_or(config.port),
+                "maxmemory" => config.maxmemory = value,
+                "maxmemory-policy" => config.maxmemory_policy = value,
+                "dir" => config.data_dir = value,
+                "databases" => config.databases = value.parse().unwrap_or(config.databases),
+                "appendonly" => config.appendonly = value == "yes",
+                _ => {}
+            }
+        }
+        config
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.port == 0 {
+            return Err("Port must be between 1 and 65535".to_string());
+        }
+        let digits = self.maxmemory.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let unit = self.maxmemory[digits.len()..].to_ascii_lowercase();
+        if digits.is_empty()
+            || !digits.chars().all(|c| c.is_ascii_digit())
+            || !["", "b", "k", "kb", "m", "mb", "g", "gb"].contains(&unit.as_str())
+        {
+            return Err(format!(
+                "Invalid maxmemory '{}': use a size such as 256mb or 1gb",
+                self.maxmemory
+            ));
+        }
+        if !MAXMEMORY_POLICIES.contains(&self.maxmemory_policy.as_str()) {
+            return Err(format!("Unknown maxmemory policy '{}'", self.maxmemory_policy));
+        }
+        if self.data_dir.trim().is_empty() {
+            return Err("Data directory must not be empty".to_string());
+        }
+        if self.databases < 2 {
+            return Err("At least 2 databases are needed: index 0 is kept for shared use".to_string());
+        }
+        Ok(())
+    }
+
+    fn directives(&self) -> Vec<(&'static str, String)> {
+        vec![
+            // Local connections only
+            ("bind", "127.0.0.1".to_string()),
+            ("port", self.port.to_string()),
+            ("maxmemory", self.maxmemory.clone()),
+            ("maxmemory-policy", self.maxmemory_policy.clone()),
+            ("dir", quote(&self.data_dir)),
+            ("databases", self.databases.to_string()),
+            ("appendonly", if self.appendonly { "yes" } else { "no" }.to_string()),
+        ]
+    }
+
+    /// `content` with the managed directives set to this config; their lines are
+    /// replaced in place and missing ones appended, so user edits survive
+    pub fn apply(&self, content: &str) -> String {
+        let directives = self.directives();
+        let mut written = vec![false; directives.len()];
+        let mut lines: Vec<String> = Vec::new();
+
+        for line in content.lines() {
+            let key = split_directive(line).map(|(key, _)| key);
+            match directives.iter().position(|(name, _)| Some(*name) == key.as_deref()) {
+                // Drop repeated directives; the last one would win otherwise
+                Some(i) if written[i] => {}
+                Some(i) => {
+                    lines.push(format!("{} {}", directives[i].0, directives[i].1));
+                    written[i] = true;
+                }
+                None => lines.push(line.to_string()),
+            }
+        }
+
+        if lines.is_empty() {
+            lines.push("# Generated by DevPort. Edits to other directives are kept.".to_string());
+        }
+        for (i, (name, value)) in directives.iter().enumerate() {
+            if !written[i] {
+                lines.push(format!("{} {}", name, value));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Write the managed settings to `path`, creating the file and data directory
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        self.validate()?;
+        fs::create_dir_all(&self.data_dir)
+            .map_err(|e| format!("Failed to create {}: {}", self.data_dir, e))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let content = fs::read_to_string(path).unwrap_or_default();
+        fs::write(path, self.apply(&content))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Create `path` with the defaults on first start; an existing file is kept
+    pub fn ensure_written(path: &Path) -> Result<Self, String> {
+        if path.exists() {
+            let config = Self::parse(&fs::read_to_string(path).map_err(|e| e.to_string())?);
+            fs::create_dir_all(&config.data_dir)
+                .map_err(|e| format!("Failed to create {}: {}", config.data_dir, e))?;
+            return Ok(config);
+        }
+        let config = Self::default();
+        config.save(path)?;
+        Ok(config)
+    }
+}
+
+/// Directive name and (unquoted) value of a config line; `None` for comments
+fn split_directive(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (key, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    Some((key.to_ascii_lowercase(), unquote(value.trim())))
+}
+
+/// A config value in double quotes, which also keeps Windows paths intact
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => value.to_string(),
+    }
+}
+
+/// A project's logical database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedisAllocation {
+    pub project_id: String,
+    pub db_index: u16,
+    pub allocated_at: String,
+    /// `REDIS_URL` of the allocation, filled in when returned
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+}
+
+pub fn redis_url(port: u16, db_index: u16) -> String {
+    format!("redis://127.0.0.1:{}/{}", port, db_index)
+}
+
+/// Lowest index from 1 not in `used`; index 0, the default of every client,
+/// stays free for shared use
+pub fn lowest_free_index(used: &[u16], databases: u16) -> Option<u16> {
+    (1..databases).find(|index| !used.contains(index))
+}
+
+/// Whether a server with `databases` databases still has every index in `used`
+pub fn check_databases(databases: u16, used: &[u16]) -> Result<(), String> {
+    match used.iter().max() {
+        Some(&highest) if databases <= highest => Err(format!(
+            "`databases` must be at least {}: database {} is allocated to a project",
+            highest + 1,
+            highest
+        )),
+        _ => Ok(()),
+    }
+}
+
+pub struct RedisManager {
+    storage: Storage,
+}
+
+impl RedisManager {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self {
+            storage: Storage::new().map_err(|e| e.to_string())?,
+        })
+    }
+
+    fn load(&self) -> Result<Vec<RedisAllocation>, String> {
+        self.storage.load_redis_allocations().map_err(|e| e.to_string())
+    }
+
+    pub fn list(&self) -> Result<Vec<RedisAllocation>, String> {
+        let port = RedisConfig::load().port;
+        let mut allocations = self.load()?;
+        for allocation in &mut allocations {
+            allocation.url = redis_url(port, allocation.db_index);
+        }
+        allocations.sort_by_key(|a| a.db_index);
+        Ok(allocations)
+    }
+
+    /// Reject a config that would drop allocated databases
+    pub fn check_config(&self, config: &RedisConfig) -> Result<(), String> {
+        let used: Vec<u16> = self.load()?.iter().map(|a| a.db_index).collect();
+        check_databases(config.databases, &used)
+    }
+
+    /// The project's database, allocating one on first use
+    pub fn allocate(&self, project_id: &str) -> Result<RedisAllocation, String> {
+        let config = RedisConfig::load();
+        let mut allocations = self.load()?;
+
+        let mut allocation = match allocations.iter().find(|a| a.project_id == project_id) {
+            Some(existing) => existing.clone(),
+            None => {
+                let used: Vec<u16> = allocations.iter().map(|a| a.db_index).collect();
+                let db_index = lowest_free_index(&used, config.databases).ok_or_else(|| {
+                    format!(
+                        "All {} Redis databases are allocated; raise `databases` in redis.conf",
+                        config.databases - 1
+                    )
+                })?;
+                let allocation = RedisAllocation {
+                    project_id: project_id.to_string(),
+                    db_index,
+                    allocated_at: chrono::Utc::now().to_rfc3339(),
+                    url: String::new(),
+                };
+                allocations.push(allocation.clone());
+                self.storage
+                    .save_redis_allocations(&allocations)
+                    .map_err(|e| e.to_string())?;
+                allocation
+            }
+        };
+        allocation.url = redis_url(config.port, allocation.db_index);
+        Ok(allocation)
+    }
+
+    /// Free the project's database. Its keys are flushed if the server is running,
+    /// so the next project to get the index starts empty.
+    pub fn release(&self, project_id: &str) -> Result<(), String> {
+        let mut allocations = self.load()?;
+        let Some(position) = allocations.iter().position(|a| a.project_id == project_id) else {
+            return Ok(());
+        };
+        let allocation = allocations.remove(position);
+
+        let port = RedisConfig::load().port;
+        if let Err(e) = flush_database(port, allocation.db_index, Duration::from_secs(2)) {
+            eprintln!("Could not flush Redis database {}: {}", allocation.db_index, e);
+        }
+
+        self.storage
+            .save_redis_allocations(&allocations)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// `SELECT index` then `FLUSHDB`; SELECT only applies to its own connection
+fn flush_database(port: u16, index: u16, timeout: Duration) -> Result<(), String> {
+    let index = index.to_string();
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    let mut stream = TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+    let request = format!(
+        "*2\r\n$6\r\nSELECT\r\n${}\r\n{}\r\n*1\r\n$7\r\nFLUSHDB\r\n",
+        index.len(),
+        index
+    );
+    stream.write_all(request.as_bytes()).map_err(|e| e.to_string())?;
+
+    let mut reader = BufReader::new(stream);
+    for _ in 0..2 {
+        let mut reply = String::new();
+        reader.read_line(&mut reply).map_err(|e| e.to_string())?;
+        if reply.is_empty() {
+            return Err("Connection closed without a reply".to_string());
+        }
+        if !reply.starts_with('+') {
+            return Err(reply.trim_end().to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_apply_keeps_user_directives() {
+        let config = RedisConfig {
+            port: 6380,
+            maxmemory: "1gb".to_string(),
+            maxmemory_policy: "volatile-lru".to_string(),
+            data_dir: "C:\\Users\\dev\\redis data".to_string(),
+            databases: 32,
+            appendonly: true,
+        };
+        let existing = "# my tweaks\nport 6379\nnotify-keyspace-events Ex\nport 6381\n";
+        let applied = config.apply(existing);
+
+        assert!(applied.starts_with("# my tweaks\nport 6380\nnotify-keyspace-events Ex\n"));
+        assert_eq!(applied.matches("port ").count(), 1);
+        assert!(applied.contains("dir \"C:\\\\Users\\\\dev\\\\redis data\"\n"));
+        assert_eq!(RedisConfig::parse(&applied), config);
+        assert!(config.validate().is_ok());
+
+        let generated = RedisConfig::default().apply("");
+        assert!(generated.contains("bind 127.0.0.1\nport 6379\n"));
+
+        let invalid = RedisConfig { maxmemory: "lots".to_string(), ..config.clone() };
+        assert!(invalid.validate().is_err());
+        assert!(RedisConfig { maxmemory: "512MB".to_string(), ..config }.validate().is_ok());
+    }
+
+    #[test]
+    fn test_lowest_free_index() {
+        assert_eq!(lowest_free_index(&[], 16), Some(1));
+        assert_eq!(lowest_free_index(&[1, 2, 4], 16), Some(3));
+        assert_eq!(lowest_free_index(&[1, 2, 3], 4), None);
+        assert_eq!(redis_url(6379, 3), "redis://127.0.0.1:6379/3");
+    }
+
+    #[test]
+    fn test_check_databases_keeps_allocated_indexes() {
+        assert!(check_databases(16, &[]).is_ok());
+        assert!(check_databases(6, &[1, 5]).is_ok());
+        assert!(check_databases(5, &[1, 5]).is_err());
+        assert!(check_databases(2, &[3, 1]).is_err());
+    }
+}
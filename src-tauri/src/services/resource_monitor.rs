@@ -0,0 +1,502 @@
+use crate::services::ServiceManager;
+use crate::state::AppState;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+/// How often running projects and services are sampled
+const RESOURCE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Samples kept per process tree (10 minutes at the poll interval)
+const HISTORY_CAPACITY: usize = 120;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceOwner {
+    Project,
+    Service,
+}
+
+/// CPU and memory of a whole process tree at one point in time
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceSample {
+    pub timestamp: String,
+    /// Percent of one core, so a busy tree can exceed 100. `None` on the first sample.
+    pub cpu_usage: Option<f32>,
+    /// Resident memory in bytes
+    pub memory_usage: u64,
+    pub process_count: usize,
+}
+
+/// Latest sample of a project or service, sent with `resource-usage`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceUsage {
+    pub owner: ResourceOwner,
+    pub id: String,
+    pub pid: u32,
+    #[serde(flatten)]
+    pub sample: ResourceSample,
+}
+
+/// Raw counters of one process
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessUsage {
+    /// User + system CPU time in seconds
+    pub cpu_secs: f64,
+    pub memory_bytes: u64,
+}
+
+/// Raw counters of every process in a tree, keyed by PID
+#[derive(Debug, Default)]
+pub struct TreeUsage {
+    pub processes: HashMap<u32, ProcessUsage>,
+}
+
+impl TreeUsage {
+    fn memory_bytes(&self) -> u64 {
+        self.processes.values().map(|p| p.memory_bytes).sum()
+    }
+}
+
+/// Time series of one process tree
+struct ResourceSeries {
+    pid: u32,
+    last: Option<(Instant, HashMap<u32, ProcessUsage>)>,
+    samples: VecDeque<ResourceSample>,
+}
+
+impl ResourceSeries {
+    fn new(pid: u32) -> Self {
+        Self {
+            pid,
+            last: None,
+            samples: VecDeque::with_capacity(HISTORY_CAPACITY),
+        }
+    }
+
+    fn push(&mut self, tree: TreeUsage, at: Instant) -> ResourceSample {
+        let cpu_usage = self.last.as_ref().and_then(|(last_at, last)| {
+            let elapsed = at.duration_since(*last_at).as_secs_f64();
+            if elapsed <= 0.0 {
+                return None;
+            }
+            // Processes that appeared since the last sample count from zero;
+            // a reused PID can go backwards, so never subtract
+            let busy: f64 = tree
+                .processes
+                .iter()
+                .map(|(pid, now)| {
+                    let before = last.get(pid).map_or(0.0, |p| p.cpu_secs);
+                    (now.cpu_secs - before).max(0.0)
+                })
+                .sum();
+            Some((busy / elapsed * 100.0) as f32)
+        });
+
+        let sample = ResourceSample {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            cpu_usage,
+            memory_usage: tree.memory_bytes(),
+            process_count: tree.processes.len(),
+        };
+
+        if self.samples.len() == HISTORY_CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample.clone());
+        self.last = Some((at, tree.processes));
+        sample
+    }
+}
+
+/// CPU/memory history of every running project and service
+#[derive(Default)]
+pub struct ResourceMonitor {
+    series: HashMap<(ResourceOwner, String), ResourceSeries>,
+}
+
+impl ResourceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a measurement of the tree rooted at `pid`. A new root PID (restart) starts a new series.
+    pub fn record(
+        &mut self,
+        owner: ResourceOwner,
+        id: &str,
+        pid: u32,
+        tree: TreeUsage,
+        at: Instant,
+    ) -> ResourceUsage {
+        let series = self
+            .series
+            .entry((owner, id.to_string()))
+            .or_insert_with(|| ResourceSeries::new(pid));
+        if series.pid != pid {
+            *series = ResourceSeries::new(pid);
+        }
+
+        ResourceUsage {
+            owner,
+            id: id.to_string(),
+            pid,
+            sample: series.push(tree, at),
+        }
+    }
+
+    pub fn history(&self, owner: ResourceOwner, id: &str) -> Vec<ResourceSample> {
+        self.series
+            .get(&(owner, id.to_string()))
+            .map(|s| s.samples.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Latest sample of every tracked process tree
+    pub fn latest(&self) -> Vec<ResourceUsage> {
+        self.series
+            .iter()
+            .filter_map(|((owner, id), series)| {
+                Some(ResourceUsage {
+                    owner: *owner,
+                    id: id.clone(),
+                    pid: series.pid,
+                    sample: series.samples.back()?.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Forget projects and services that are no longer running
+    pub fn retain(&mut self, running: &[(ResourceOwner, String, u32)]) {
+        self.series.retain(|(owner, id), _| {
+            running.iter().any(|(o, i, _)| o == owner && i == id)
+        });
+    }
+}
+
+pub type SharedResourceMonitor = Arc<Mutex<ResourceMonitor>>;
+
+pub fn init_resource_monitor() -> SharedResourceMonitor {
+    Arc::new(Mutex::new(ResourceMonitor::new()))
+}
+
+/// `root` and all of its descendants, given a child -> parent map
+fn tree_pids(root: u32, parents: &HashMap<u32, u32>) -> Vec<u32> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (&pid, &ppid) in parents {
+        if pid != ppid {
+            children.entry(ppid).or_default().push(pid);
+        }
+    }
+
+    // Windows reuses PIDs, so a stale parent link can form a cycle
+    let mut seen = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(pid) = stack.pop() {
+        if seen.insert(pid) {
+            if let Some(kids) = children.get(&pid) {
+                stack.extend(kids);
+            }
+        }
+    }
+    seen.into_iter().collect()
+}
+
+/// Measure every tree in one pass over the process table
+pub fn measure_trees(roots: &[u32]) -> Vec<TreeUsage> {
+    let parents = process_parents();
+    roots
+        .iter()
+        .map(|&root| TreeUsage {
+            processes: tree_pids(root, &parents)
+                .into_iter()
+                .filter_map(|pid| Some((pid, process_usage(pid)?)))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Parse the fields of `/proc/<pid>/stat` we need: (ppid, utime + stime ticks, rss pages)
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_stat(stat: &str) -> Option<(u32, u64, u64)> {
+    // The command name is in parentheses and may itself contain spaces or ')'
+    let rest = &stat[stat.rfind(')')? + 1..];
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let ppid = fields.get(1)?.parse().ok()?;
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    let rss = fields.get(21)?.parse().ok()?;
+    Some((ppid, utime + stime, rss))
+}
+
+#[cfg(target_os = "linux")]
+fn process_parents() -> HashMap<u32, u32> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return HashMap::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+            let (ppid, _, _) = parse_proc_stat(&stat)?;
+            Some((pid, ppid))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn process_usage(pid: u32) -> Option<ProcessUsage> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let (_, ticks, rss_pages) = parse_proc_stat(&stat)?;
+    // SAFETY: sysconf has no memory-safety preconditions
+    let (ticks_per_sec, page_size) =
+        unsafe { (libc::sysconf(libc::_SC_CLK_TCK), libc::sysconf(libc::_SC_PAGESIZE)) };
+    Some(ProcessUsage {
+        cpu_secs: ticks as f64 / ticks_per_sec.max(1) as f64,
+        memory_bytes: rss_pages * page_size.max(0) as u64,
+    })
+}
+
+#[cfg(windows)]
+fn process_parents() -> HashMap<u32, u32> {
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::tlhelp32::{
+        CreateToolhelp32Snapshot, Process32First, Process32Next, PROCESSENTRY32,
+        TH32CS_SNAPPROCESS,
+    };
+
+    let mut parents = HashMap::new();
+    // SAFETY: the snapshot handle is checked before use and closed afterwards,
+    // and `entry.dwSize` is set as Process32First requires
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return parents;
+        }
+        let mut entry: PROCESSENTRY32 = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32>() as u32;
+        let mut ok = Process32First(snapshot, &mut entry);
+        while ok != 0 {
+            parents.insert(entry.th32ProcessID, entry.th32ParentProcessID);
+            ok = Process32Next(snapshot, &mut entry);
+        }
+        CloseHandle(snapshot);
+    }
+    parents
+}
+
+#[cfg(windows)]
+fn process_usage(pid: u32) -> Option<ProcessUsage> {
+    use winapi::shared::minwindef::FILETIME;
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{GetProcessTimes, OpenProcess};
+    use winapi::um::psapi::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+
+    // FILETIME counts 100ns intervals
+    let secs = |t: &FILETIME| {
+        (((t.dwHighDateTime as u64) << 32) | t.dwLowDateTime as u64) as f64 / 10_000_000.0
+    };
+
+    // SAFETY: the process handle is checked before use and closed afterwards;
+    // the out-parameters are plain structs sized as the APIs expect
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut created: FILETIME = std::mem::zeroed();
+        let mut exited: FILETIME = std::mem::zeroed();
+        let mut kernel: FILETIME = std::mem::zeroed();
+        let mut user: FILETIME = std::mem::zeroed();
+        let times_ok = GetProcessTimes(handle, &mut created, &mut exited, &mut kernel, &mut user) != 0;
+
+        let mut counters: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+        counters.cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        let memory_ok = GetProcessMemoryInfo(handle, &mut counters, counters.cb) != 0;
+        CloseHandle(handle);
+
+        if !times_ok {
+            return None;
+        }
+        Some(ProcessUsage {
+            cpu_secs: secs(&kernel) + secs(&user),
+            memory_bytes: if memory_ok { counters.WorkingSetSize as u64 } else { 0 },
+        })
+    }
+}
+
+// Other platforms: no sampling, the fields stay empty
+#[cfg(not(any(target_os = "linux", windows)))]
+fn process_parents() -> HashMap<u32, u32> {
+    HashMap::new()
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn process_usage(_pid: u32) -> Option<ProcessUsage> {
+    None
+}
+
+/// Spawn the background task that samples the process tree of every running
+/// project and service, fills `ProcessInfo.cpu_usage`/`memory_usage` and emits
+/// `resource-usage` with the latest samples.
+pub fn spawn_resource_monitor(
+    app_handle: AppHandle,
+    resource_monitor: SharedResourceMonitor,
+    app_state: Arc<Mutex<AppState>>,
+    service_manager: Arc<Mutex<ServiceManager>>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(RESOURCE_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let mut targets: Vec<(ResourceOwner, String, u32)> = app_state
+                .lock()
+                .await
+                .running_processes
+                .values()
+                .map(|p| (ResourceOwner::Project, p.project_id.clone(), p.pid))
+                .collect();
+            targets.extend(
+                service_manager
+                    .lock()
+                    .await
+                    .get_services()
+                    .iter()
+                    .filter(|s| s.is_running())
+                    .filter_map(|s| Some((ResourceOwner::Service, s.id.clone(), s.pid?))),
+            );
+
+            resource_monitor.lock().await.retain(&targets);
+            if targets.is_empty() {
+                continue;
+            }
+
+            let roots: Vec<u32> = targets.iter().map(|(_, _, pid)| *pid).collect();
+            let trees = match tauri::async_runtime::spawn_blocking(move || measure_trees(&roots)).await {
+                Ok(trees) => trees,
+                Err(_) => continue,
+            };
+            let at = Instant::now();
+
+            let usages: Vec<ResourceUsage> = {
+                let mut monitor = resource_monitor.lock().await;
+                targets
+                    .iter()
+                    .zip(trees)
+                    // An empty tree means the process is already gone
+                    .filter(|(_, tree)| !tree.processes.is_empty())
+                    .map(|((owner, id, pid), tree)| monitor.record(*owner, id, *pid, tree, at))
+                    .collect()
+            };
+
+            {
+                let mut state = app_state.lock().await;
+                for usage in usages.iter().filter(|u| u.owner == ResourceOwner::Project) {
+                    if let Some(info) = state.running_processes.get_mut(&usage.id) {
+                        if info.pid == usage.pid {
+                            info.cpu_usage = usage.sample.cpu_usage;
+                            info.memory_usage = Some(usage.sample.memory_usage);
+                        }
+                    }
+                }
+            }
+            {
+                let mut manager = service_manager.lock().await;
+                for usage in usages.iter().filter(|u| u.owner == ResourceOwner::Service) {
+                    if let Some(service) = manager.get_service_mut(&usage.id) {
+                        if service.pid == Some(usage.pid) {
+                            service.cpu_usage = usage.sample.cpu_usage;
+                            service.memory_usage = Some(usage.sample.memory_usage);
+                        }
+                    }
+                }
+            }
+
+            let _ = app_handle.emit("resource-usage", &usages);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(processes: &[(u32, f64, u64)]) -> TreeUsage {
+        TreeUsage {
+            processes: processes
+                .iter()
+                .map(|&(pid, cpu_secs, memory_bytes)| (pid, ProcessUsage { cpu_secs, memory_bytes }))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let stat = "4242 (node (vite)) S 4200 4200 4200 0 -1 4194560 9000 0 0 0 150 50 0 0 20 0 11 0 123456 1234567890 25600 18446744073709551615";
+        assert_eq!(parse_proc_stat(stat), Some((4200, 200, 25600)));
+        assert_eq!(parse_proc_stat("garbage"), None);
+    }
+
+    #[test]
+    fn test_tree_pids_follows_descendants() {
+        // 1 -> 10 -> 11 -> 12, 1 -> 20, and a cycle 30 <-> 31 from PID reuse
+        let parents: HashMap<u32, u32> =
+            [(10, 1), (11, 10), (12, 11), (20, 1), (30, 31), (31, 30)].into_iter().collect();
+
+        let mut pids = tree_pids(10, &parents);
+        pids.sort();
+        assert_eq!(pids, vec![10, 11, 12]);
+
+        let mut cycle = tree_pids(30, &parents);
+        cycle.sort();
+        assert_eq!(cycle, vec![30, 31]);
+    }
+
+    #[test]
+    fn test_cpu_usage_from_deltas() {
+        let mut monitor = ResourceMonitor::new();
+        let start = Instant::now();
+
+        let first = monitor.record(ResourceOwner::Project, "web", 10, tree(&[(10, 1.0, 100)]), start);
+        assert_eq!(first.sample.cpu_usage, None);
+
+        // 10 used 1s, the new child 11 used 0.5s, over 2s of wall time
+        let at = start + Duration::from_secs(2);
+        let second = monitor.record(
+            ResourceOwner::Project,
+            "web",
+            10,
+            tree(&[(10, 2.0, 100), (11, 0.5, 400)]),
+            at,
+        );
+        assert_eq!(second.sample.cpu_usage, Some(75.0));
+        assert_eq!(second.sample.memory_usage, 500);
+        assert_eq!(second.sample.process_count, 2);
+        assert_eq!(monitor.history(ResourceOwner::Project, "web").len(), 2);
+
+        // Restarted under a new PID: fresh series
+        let restarted = monitor.record(ResourceOwner::Project, "web", 50, tree(&[(50, 9.0, 100)]), at);
+        assert_eq!(restarted.sample.cpu_usage, None);
+        assert_eq!(monitor.history(ResourceOwner::Project, "web").len(), 1);
+
+        monitor.retain(&[]);
+        assert!(monitor.latest().is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_measure_own_process() {
+        let trees = measure_trees(&[std::process::id()]);
+        let usage = trees[0].processes.get(&std::process::id()).unwrap();
+        assert!(usage.memory_bytes > 0);
+    }
+}
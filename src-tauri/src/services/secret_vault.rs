@@ -0,0 +1,277 @@
+//! Project-scoped secret vault
+//!
+//! Secret values live in the credential store; `.env` files only reference them as
+//! `${vault:name}`. The placeholders are resolved into the child environment when a
+//! project starts, so API keys never have to be written to disk in plaintext.
+
+use crate::models::Project;
+use crate::services::credential_manager::{CredentialManager, SharedCredentialManager};
+use crate::services::env_manager::EnvManager;
+use crate::services::storage::Storage;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// A vault entry; the value itself is never part of it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultEntry {
+    pub project_id: String,
+    pub name: String,
+    pub created_at: String,
+    pub rotated_at: String,
+    /// Environment variables referencing the entry, filled in when listing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\$\{vault:([A-Za-z0-9_-]+)\}").unwrap())
+}
+
+/// Names of the vault entries referenced by `value`
+pub fn placeholder_names(value: &str) -> Vec<&str> {
+    placeholder_regex()
+        .captures_iter(value)
+        .filter_map(|caps| caps.get(1))
+        .map(|m| m.as_str())
+        .collect()
+}
+
+/// Replace every `${vault:name}` of `value` with `lookup(name)`
+pub fn resolve_placeholders<F>(value: &str, mut lookup: F) -> Result<String, String>
+where
+    F: FnMut(&str) -> Result<String, String>,
+{
+    let mut error = None;
+    let resolved = placeholder_regex().replace_all(value, |caps: &Captures| {
+        lookup(&caps[1]).unwrap_or_else(|e| {
+            error.get_or_insert(e);
+            String::new()
+        })
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(resolved.into_owned()),
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Invalid vault entry name '{}': use letters, digits, '_' and '-'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+pub struct SecretVault {
+    credentials: SharedCredentialManager,
+    storage: Storage,
+}
+
+impl SecretVault {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self {
+            credentials: CredentialManager::shared()?,
+            storage: Storage::new().map_err(|e| e.to_string())?,
+        })
+    }
+
+    fn credential_key(project_id: &str, name: &str) -> String {
+        format!("vault_{}_{}", project_id, name)
+    }
+
+    fn load_entries(&self) -> Result<Vec<VaultEntry>, String> {
+        self.storage.load_vault_entries().map_err(|e| e.to_string())
+    }
+
+    fn save_entries(&self, entries: &[VaultEntry]) -> Result<(), String> {
+        self.storage.save_vault_entries(entries).map_err(|e| e.to_string())
+    }
+
+    /// Entries of a project with the variables of `project_path` that reference them
+    pub fn list(&self, project_id: &str, project_path: &str) -> Result<Vec<VaultEntry>, String> {
+        let env = EnvManager::new(PathBuf::from(project_path)).vault_variables();
+        let mut entries: Vec<VaultEntry> = self
+            .load_entries()?
+            .into_iter()
+            .filter(|entry| entry.project_id == project_id)
+            .collect();
+        for entry in &mut entries {
+            entry.references = env
+                .iter()
+                .filter(|(_, value)| placeholder_names(value).contains(&entry.name.as_str()))
+                .map(|(key, _)| key.clone())
+                .collect();
+            entry.references.sort();
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    /// Store a secret, replacing the value of an existing entry
+    pub fn set(&self, project_id: &str, name: &str, value: &str) -> Result<VaultEntry, String> {
+        validate_name(name)?;
+        self.credentials
+            .save_credential(&Self::credential_key(project_id, name), value)?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut entries = self.load_entries()?;
+        let entry = match entries
+            .iter_mut()
+            .find(|e| e.project_id == project_id && e.name == name)
+        {
+            Some(entry) => {
+                entry.rotated_at = now;
+                entry.clone()
+            }
+            None => {
+                let entry = VaultEntry {
+                    project_id: project_id.to_string(),
+                    name: name.to_string(),
+                    created_at: now.clone(),
+                    rotated_at: now,
+                    references: Vec::new(),
+                };
+                entries.push(entry.clone());
+                entry
+            }
+        };
+        self.save_entries(&entries)?;
+        Ok(entry)
+    }
+
+    /// Replace the value of an existing entry. Without a new value a random
+    /// 64-character secret is generated.
+    pub fn rotate(
+        &self,
+        project_id: &str,
+        name: &str,
+        value: Option<&str>,
+    ) -> Result<VaultEntry, String> {
+        if !self
+            .load_entries()?
+            .iter()
+            .any(|e| e.project_id == project_id && e.name == name)
+        {
+            return Err(format!("Vault entry '{}' not found", name));
+        }
+        let generated;
+        let value = match value {
+            Some(value) => value,
+            None => {
+                generated = format!(
+                    "{}{}",
+                    uuid::Uuid::new_v4().simple(),
+                    uuid::Uuid::new_v4().simple()
+                );
+                &generated
+            }
+        };
+        self.set(project_id, name, value)
+    }
+
+    pub fn get(&self, project_id: &str, name: &str) -> Result<Option<String>, String> {
+        self.credentials
+            .load_credential(&Self::credential_key(project_id, name))
+    }
+
+    pub fn delete(&self, project_id: &str, name: &str) -> Result<(), String> {
+        self.credentials
+            .delete_credential(&Self::credential_key(project_id, name))?;
+        let mut entries = self.load_entries()?;
+        entries.retain(|e| !(e.project_id == project_id && e.name == name));
+        self.save_entries(&entries)
+    }
+
+    /// Remove every entry of a deleted project
+    pub fn delete_project(&self, project_id: &str) -> Result<(), String> {
+        let mut entries = self.load_entries()?;
+        for entry in entries.iter().filter(|e| e.project_id == project_id) {
+            self.credentials
+                .delete_credential(&Self::credential_key(project_id, &entry.name))?;
+        }
+        entries.retain(|e| e.project_id != project_id);
+        self.save_entries(&entries)
+    }
+}
+
+/// Variables of the project's `.env` files and settings that reference the vault,
+/// with their placeholders resolved. The vault is only opened when there are any.
+pub fn resolve_project_env(
+    project: &Project,
+    env_vars: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    let mut referencing = EnvManager::new(PathBuf::from(&project.path)).vault_variables();
+    for (key, value) in env_vars {
+        if !placeholder_names(value).is_empty() {
+            referencing.insert(key.clone(), value.clone());
+        }
+    }
+    if referencing.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let vault = SecretVault::new()?;
+    let mut cache: HashMap<String, String> = HashMap::new();
+    let mut resolved = HashMap::new();
+    for (key, value) in referencing {
+        let value = resolve_placeholders(&value, |name| {
+            if let Some(secret) = cache.get(name) {
+                return Ok(secret.clone());
+            }
+            let secret = vault.get(&project.id, name)?.ok_or_else(|| {
+                format!(
+                    "Vault entry '{}' referenced by {} is not set for project {}",
+                    name, key, project.name
+                )
+            })?;
+            cache.insert(name.to_string(), secret.clone());
+            Ok(secret)
+        })?;
+        resolved.insert(key, value);
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolves_vault_placeholders() {
+        let value = "postgres://app:${vault:db_password}@localhost/${vault:db-name}";
+        assert_eq!(placeholder_names(value), vec!["db_password", "db-name"]);
+        assert!(placeholder_names("${VAULT:x} $vault:y ${vault:}").is_empty());
+
+        let secrets = HashMap::from([("db_password", "p@ss"), ("db-name", "shop")]);
+        let lookup = |name: &str| {
+            secrets
+                .get(name)
+                .map(|s| s.to_string())
+                .ok_or_else(|| format!("missing {}", name))
+        };
+        assert_eq!(
+            resolve_placeholders(value, lookup).unwrap(),
+            "postgres://app:p@ss@localhost/shop"
+        );
+        assert_eq!(resolve_placeholders("plain", lookup).unwrap(), "plain");
+        assert_eq!(
+            resolve_placeholders("${vault:stripe_key}", lookup).unwrap_err(),
+            "missing stripe_key"
+        );
+
+        assert!(validate_name("stripe_key").is_ok());
+        assert!(validate_name("stripe key").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Address a dev server reports it is listening on, e.g. `http://localhost:5174/`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerUrl {
+    pub host: String,
+    pub port: u16,
+}
+
+impl ServerUrl {
+    pub fn url(&self) -> String {
+        format!("http://{}:{}", self.host, self.port)
+    }
+}
+
+fn ansi_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap())
+}
+
+fn url_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)https?://(localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1?\]):(\d{1,5})").unwrap()
+    })
+}
+
+/// Find the local URL in a line of dev server output.
+///
+/// Only loopback and wildcard hosts count, so "Network:" lines and proxy targets
+/// on other machines are ignored. Vite colours the port, so ANSI codes are
+/// stripped first. Wildcard hosts are reported as `localhost`.
+pub fn parse_server_url(line: &str) -> Option<ServerUrl> {
+    let plain = ansi_regex().replace_all(line, "");
+    let caps = url_regex().captures(&plain)?;
+    let port: u16 = caps[2].parse().ok().filter(|p| *p > 0)?;
+
+    let host = match caps[1].to_lowercase().as_str() {
+        "0.0.0.0" | "[::]" => "localhost".to_string(),
+        other => other.to_string(),
+    };
+
+    Some(ServerUrl { host, port })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vite_local_line() {
+        let line = "  \u{1b}[32m➜\u{1b}[39m  \u{1b}[1mLocal\u{1b}[22m:   \u{1b}[36mhttp://localhost:\u{1b}[1m5174\u{1b}[22m/\u{1b}[39m";
+        assert_eq!(
+            parse_server_url(line),
+            Some(ServerUrl { host: "localhost".to_string(), port: 5174 })
+        );
+    }
+
+    #[test]
+    fn test_python_servers() {
+        let django = "Starting development server at http://127.0.0.1:8001/";
+        assert_eq!(parse_server_url(django).map(|u| u.port), Some(8001));
+
+        let uvicorn = "INFO:     Uvicorn running on http://0.0.0.0:8000 (Press CTRL+C to quit)";
+        assert_eq!(
+            parse_server_url(uvicorn),
+            Some(ServerUrl { host: "localhost".to_string(), port: 8000 })
+        );
+    }
+
+    #[test]
+    fn test_ignores_remote_and_portless_urls() {
+        assert_eq!(parse_server_url("  ➜  Network: http://192.168.1.20:5173/"), None);
+        assert_eq!(parse_server_url("See https://nextjs.org/docs"), None);
+        assert_eq!(parse_server_url("proxy error: http://localhost:99999"), None);
+    }
+}
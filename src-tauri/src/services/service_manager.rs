@@ -1,21 +1,104 @@
-use crate::models::{Service, ServiceStatus, HealthCheckType};
-use crate::services::log_manager::LogManager;
+use crate::models::{Service, ServiceStatus, HealthCheckType, StopStep, StopStrategy};
+use crate::services::credential_manager::{CredentialManager, MARIADB_ROOT_KEY};
+use crate::services::health_checker::HealthChecker;
+use crate::services::log_manager::{LogManager, LogSource, LogSourceKind, SharedLogAlerts};
+use crate::services::log_tail::NativeLogAlerts;
+use crate::services::log_parser::{LogLevel, LogParserRegistry};
 use crate::services::port_scanner::PortScanner;
-use crate::services::process_manager::{kill_process_tree, kill_process_tree_silent, CREATE_NO_WINDOW};
+use crate::services::postgres_backend::init_cluster;
+use crate::services::redis_manager::RedisConfig;
+use crate::services::process_stopper::{stop_process, StopTarget};
+#[cfg(windows)]
+use crate::services::process_manager::{CREATE_NEW_PROCESS_GROUP, CREATE_NO_WINDOW};
 use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
 use std::thread;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
 use tokio::time::{sleep, Duration};
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+
+/// A service's process taken out of [`ServiceManager`] while it is being stopped
+struct ServiceStop {
+    id: String,
+    /// Process started by DevPort
+    child: Option<Child>,
+    /// Set for externally started processes too (e.g. XAMPP control panel)
+    pid: Option<u32>,
+    strategy: StopStrategy,
+    timeout: std::time::Duration,
+    work_dir: String,
+}
 
-#[cfg(windows)]
-const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+impl ServiceStop {
+    /// Run the stop strategy and wait for the process to exit.
+    /// Blocks for up to the stop timeouts.
+    fn run(&mut self) -> Result<Option<StopStep>, String> {
+        let client_config = (self.id == "mariadb" && self.strategy.command.is_some())
+            .then(Service::mariadb_client_config_path);
+        if let Some(path) = &client_config {
+            if let Err(e) = write_mariadb_client_config(path) {
+                eprintln!("Failed to write {}: {}", path.display(), e);
+            }
+        }
+
+        let work_dir = Some(self.work_dir.as_str());
+        let result = if let Some(child) = self.child.as_mut() {
+            stop_process(StopTarget::Child(child), &self.strategy, self.timeout, work_dir)
+                .map(|outcome| Some(outcome.stopped_by))
+        } else if let Some(pid) = self.pid {
+            stop_process(StopTarget::External(pid), &self.strategy, self.timeout, work_dir)
+                .map(|outcome| Some(outcome.stopped_by))
+                .map_err(|e| format!("Failed to stop external process (PID {}): {}", pid, e))
+        } else {
+            Ok(None)
+        };
+
+        if let Some(path) = &client_config {
+            let _ = fs::remove_file(path);
+        }
+        result
+    }
+}
+
+/// Option file giving `mysqladmin` the stored root login, readable by the owner only
+fn write_mariadb_client_config(path: &Path) -> Result<(), String> {
+    use std::io::Write;
+
+    let password = CredentialManager::shared()
+        .and_then(|m| m.load_credential(MARIADB_ROOT_KEY))
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to load the MariaDB root password: {}", e);
+            None
+        });
+
+    let mut content = String::from("[client]\nuser=root\n");
+    if let Some(password) = password.filter(|p| !p.is_empty()) {
+        let escaped = password.replace('\\', "\\\\").replace('"', "\\\"");
+        content.push_str(&format!("password=\"{}\"\n", escaped));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path).map_err(|e| e.to_string())?;
+    file.write_all(content.as_bytes()).map_err(|e| e.to_string())
+}
 
 pub struct ServiceManager {
     pub services: HashMap<String, Service>,
@@ -29,9 +112,13 @@ impl ServiceManager {
 
         let apache = Service::apache();
         let mariadb = Service::mariadb();
+        let postgresql = Service::postgresql();
+        let redis = Service::redis(RedisConfig::load().port);
 
         services.insert(apache.id.clone(), apache);
         services.insert(mariadb.id.clone(), mariadb);
+        services.insert(postgresql.id.clone(), postgresql);
+        services.insert(redis.id.clone(), redis);
 
         let mut manager = Self {
             services,
@@ -132,6 +219,32 @@ impl ServiceManager {
                     eprintln!("Warning: MariaDB security check failed: {}", e);
                 }
             }
+            "postgresql" => {
+                // The first start creates the cluster
+                let executable = Path::new(&service.executable);
+                if let Some(bin_dir) = executable.parent().filter(|_| executable.exists()) {
+                    let data_dir = Service::postgresql_data_dir();
+                    match init_cluster(bin_dir, &data_dir) {
+                        Ok(true) => eprintln!("Initialized PostgreSQL cluster in {}", data_dir.display()),
+                        Ok(false) => {}
+                        Err(e) => {
+                            let service = self.services.get_mut(id).unwrap();
+                            service.status = ServiceStatus::Error;
+                            service.error_message = Some(e.clone());
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+            "redis" => {
+                // The first start generates redis.conf and its data directory
+                if let Err(e) = RedisConfig::ensure_written(&Service::redis_config_path()) {
+                    let service = self.services.get_mut(id).unwrap();
+                    service.status = ServiceStatus::Error;
+                    service.error_message = Some(e.clone());
+                    return Err(e);
+                }
+            }
             "apache" => {
                 let port = service.port;
                 if let Err(e) = self.validate_apache_listen_port(port) {
@@ -155,6 +268,10 @@ impl ServiceManager {
         let env = service.env.clone();
         let service_id = id.to_string();
         let service_name = service.name.clone();
+        let log_manager = LogManager {
+            base_path: self.log_manager.base_path.clone(),
+            ..LogManager::with_config(&service.log_config)
+        };
 
         if !std::path::Path::new(&executable).exists() {
             let service = self.services.get_mut(id).unwrap();
@@ -163,6 +280,23 @@ impl ServiceManager {
             return Err(format!("Executable not found: {}", executable));
         }
 
+        // Logs the service writes itself are only free to rotate while it is down
+        for path in service.log_config.native_paths() {
+            if let Err(e) = log_manager.maintain_log(&path) {
+                eprintln!("Warning: failed to rotate {}: {}", path.display(), e);
+            }
+        }
+        if let Err(e) = log_manager.cleanup_service_logs(id) {
+            eprintln!("Warning: failed to clean up logs of {}: {}", service_name, e);
+        }
+
+        // Every captured line is also checked against the log alert rules
+        let log_alerts = app_handle
+            .as_ref()
+            .and_then(|app| app.try_state::<SharedLogAlerts>())
+            .map(|alerts| alerts.inner().clone());
+        let log_origin = LogSource { kind: LogSourceKind::Service, name: service_id.clone() };
+
         let mut cmd = Command::new(&executable);
         cmd.args(&args)
             .current_dir(&work_dir)
@@ -174,6 +308,11 @@ impl ServiceManager {
         {
             cmd.creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP);
         }
+        // Own process group, so stop signals reach the whole tree
+        #[cfg(unix)]
+        {
+            cmd.process_group(0);
+        }
 
         match cmd.spawn() {
             Ok(mut child) => {
@@ -183,14 +322,16 @@ impl ServiceManager {
                 if let Some(stdout) = child.stdout.take() {
                     let sid = service_id.clone();
                     let sname = service_name.clone();
-                    let log_path = self.log_manager.get_log_path(&sid, "stdout");
+                    let mut log_writer = log_manager
+                        .log_writer(log_manager.get_log_path(&sid, "stdout"), "stdout")
+                        .with_alerts(log_origin.clone(), log_alerts.clone());
                     let app = app_handle.clone();
 
                     thread::spawn(move || {
                         let reader = BufReader::new(stdout);
                         for line in reader.lines().map_while(Result::ok) {
                             // Write to log file
-                            let _ = LogManager::append_line_to_file(&log_path, &line);
+                            let _ = log_writer.write_line(&line);
 
                             // Emit event to frontend
                             if let Some(ref app) = app {
@@ -212,14 +353,16 @@ impl ServiceManager {
                 if let Some(stderr) = child.stderr.take() {
                     let sid = service_id.clone();
                     let sname = service_name.clone();
-                    let log_path = self.log_manager.get_log_path(&sid, "stderr");
+                    let mut log_writer = log_manager
+                        .log_writer(log_manager.get_log_path(&sid, "stderr"), "stderr")
+                        .with_alerts(log_origin.clone(), log_alerts.clone());
                     let app = app_handle.clone();
 
                     thread::spawn(move || {
                         let reader = BufReader::new(stderr);
                         for line in reader.lines().map_while(Result::ok) {
                             // Write to log file
-                            let _ = LogManager::append_line_to_file(&log_path, &line);
+                            let _ = log_writer.write_line(&line);
 
                             // Emit event to frontend
                             if let Some(ref app) = app {
@@ -278,54 +421,84 @@ impl ServiceManager {
         }
     }
 
-    pub async fn stop_service(&mut self, id: &str) -> Result<(), String> {
+    /// Stop a service following its stop strategy. The manager is only locked to
+    /// take the process out and to record the result, since waiting for the
+    /// process can take the whole stop timeout.
+    pub async fn stop_service(manager: &Arc<Mutex<Self>>, id: &str) -> Result<(), String> {
+        let Some(mut stop) = manager.lock().await.take_for_stop(id)? else {
+            return Ok(());
+        };
+        let (stop, result) = tokio::task::spawn_blocking(move || {
+            let result = stop.run();
+            (stop, result)
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        manager.lock().await.finish_stop(stop, result)
+    }
+
+    pub async fn restart_service(
+        manager: &Arc<Mutex<Self>>,
+        id: &str,
+        app_handle: Option<AppHandle>,
+    ) -> Result<(), String> {
+        Self::stop_service(manager, id).await?;
+        sleep(Duration::from_millis(1000)).await;
+        manager.lock().await.start_service(id, app_handle).await
+    }
+
+    /// Take a running service's process out of the manager; `None` if it isn't running
+    fn take_for_stop(&mut self, id: &str) -> Result<Option<ServiceStop>, String> {
         let service = self.services.get(id).ok_or("Service not found")?;
 
         if !service.is_running() {
-            return Ok(());
+            return Ok(None);
         }
 
-        let pid = service.pid;
+        Ok(Some(ServiceStop {
+            id: id.to_string(),
+            child: self.processes.remove(id),
+            pid: service.pid,
+            strategy: service.stop_strategy.clone(),
+            timeout: std::time::Duration::from_millis(service.stop_timeout),
+            work_dir: service.work_dir.clone(),
+        }))
+    }
 
-        if let Some(mut child) = self.processes.remove(id) {
-            // Process started by DevPort - kill via centralized function
-            if let Some(pid) = pid {
-                kill_process_tree_silent(pid);
-            }
-            let _ = child.wait();
-        } else if let Some(pid) = pid {
-            // Externally started process - kill by PID directly
-            let result = kill_process_tree(pid);
-            if !result.success {
-                return Err(format!(
-                    "Failed to stop external process (PID {}): {}",
-                    pid,
-                    result.error.unwrap_or_else(|| "Unknown error".to_string())
-                ));
+    /// Record the result of [`ServiceStop::run`]. A process that is still running
+    /// is tracked again.
+    fn finish_stop(
+        &mut self,
+        stop: ServiceStop,
+        result: Result<Option<StopStep>, String>,
+    ) -> Result<(), String> {
+        let stopped_by = match result {
+            Ok(stopped_by) => stopped_by,
+            Err(e) => {
+                if let Some(child) = stop.child {
+                    self.processes.insert(stop.id, child);
+                }
+                return Err(e);
             }
-        }
+        };
 
-        if let Some(service) = self.services.get_mut(id) {
+        if let Some(service) = self.services.get_mut(&stop.id) {
             service.status = ServiceStatus::Stopped;
             service.pid = None;
             service.last_stopped = Some(chrono::Utc::now().to_rfc3339());
 
-            let log_path = self.log_manager.get_log_path(id, "stdout");
-            let _ = self.log_manager.write_log(
-                &log_path,
-                &format!("Service {} stopped", service.name),
-            );
+            let log_path = self.log_manager.get_log_path(&stop.id, "stdout");
+            let message = match &stopped_by {
+                Some(step) => format!("Service {} stopped ({:?})", service.name, step),
+                None => format!("Service {} stopped", service.name),
+            };
+            let _ = self.log_manager.write_log(&log_path, &message);
+            service.stopped_by = stopped_by;
         }
 
         Ok(())
     }
 
-    pub async fn restart_service(&mut self, id: &str, app_handle: Option<AppHandle>) -> Result<(), String> {
-        self.stop_service(id).await?;
-        sleep(Duration::from_millis(1000)).await;
-        self.start_service(id, app_handle).await
-    }
-
     pub async fn check_health(&mut self, id: &str) -> ServiceStatus {
         let service = match self.services.get(id) {
             Some(s) => s.clone(),
@@ -352,9 +525,14 @@ impl ServiceManager {
         }
 
         let is_healthy = match service.health_check.check_type {
-            HealthCheckType::Http => self.check_http_health(&service).await,
-            HealthCheckType::Tcp => self.check_tcp_health(&service).await,
+            // Liveness was already checked above
             HealthCheckType::Process => true,
+            _ => {
+                let port = Some(service.port).filter(|p| *p > 0);
+                HealthChecker::run_check(&service.id, &service.health_check, port, service.pid, Some(&service.work_dir))
+                    .await
+                    .is_healthy
+            }
         };
 
         let new_status = if is_healthy {
@@ -370,32 +548,6 @@ impl ServiceManager {
         new_status
     }
 
-    async fn check_http_health(&self, service: &Service) -> bool {
-        if let Some(endpoint) = &service.health_check.endpoint {
-            let client = reqwest::Client::builder()
-                .timeout(Duration::from_millis(service.health_check.timeout))
-                .build();
-
-            if let Ok(client) = client {
-                if let Ok(response) = client.get(endpoint).send().await {
-                    return response.status().is_success() || response.status().as_u16() == 302;
-                }
-            }
-        }
-        false
-    }
-
-    async fn check_tcp_health(&self, service: &Service) -> bool {
-        if let Some(endpoint) = &service.health_check.endpoint {
-            let timeout = Duration::from_millis(service.health_check.timeout);
-            match tokio::time::timeout(timeout, tokio::net::TcpStream::connect(endpoint)).await {
-                Ok(Ok(_)) => return true,
-                _ => return false,
-            }
-        }
-        false
-    }
-
     pub async fn auto_restart_if_needed(&mut self, id: &str) -> bool {
         let service = match self.services.get(id) {
             Some(s) => s.clone(),
@@ -427,6 +579,19 @@ impl ServiceManager {
         self.start_service(id, None).await.is_ok()
     }
 
+    /// Log files services write themselves (Apache access/error, MariaDB), by service
+    pub fn native_logs(&self) -> Vec<(LogSource, PathBuf)> {
+        self.services
+            .values()
+            .flat_map(|service| {
+                service.log_config.native_paths().into_iter().map(|path| {
+                    let source = LogSource { kind: LogSourceKind::Service, name: service.id.clone() };
+                    (source, path)
+                })
+            })
+            .collect()
+    }
+
     pub fn get_all_statuses(&self) -> HashMap<String, ServiceStatus> {
         self.services
             .iter()
@@ -603,14 +768,81 @@ impl Default for ServiceManager {
 
 /// Detect log level from a log line based on keywords
 fn detect_log_level(line: &str) -> &'static str {
-    let lower = line.to_lowercase();
-    if lower.contains("error") || lower.contains("fatal") || lower.contains("fail") || lower.contains("crit") {
-        "error"
-    } else if lower.contains("warn") {
-        "warning"
-    } else if lower.contains("note") || lower.contains("notice") || lower.contains("info") || lower.contains("ready for connections") {
-        "info"
-    } else {
-        "debug"
+    match LogParserRegistry::global().parse(line).level {
+        Some(LogLevel::Error) => "error",
+        Some(LogLevel::Warn) => "warning",
+        Some(LogLevel::Info) => "info",
+        Some(LogLevel::Debug) | None => "debug",
     }
 }
+
+/// How often the logs running services write themselves are checked against their size limit
+const NATIVE_LOG_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Rotate native logs (Apache access/error, MariaDB) of running services once they
+/// outgrow `max_size`; start-up rotation alone lets a long-running service's logs grow
+/// without bound
+pub fn spawn_native_log_rotation(service_manager: Arc<Mutex<ServiceManager>>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(NATIVE_LOG_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let logs: Vec<(LogManager, Vec<PathBuf>)> = {
+                let manager = service_manager.lock().await;
+                manager
+                    .get_services()
+                    .iter()
+                    .filter(|s| s.is_running())
+                    .map(|s| {
+                        let log_manager = LogManager {
+                            base_path: manager.log_manager.base_path.clone(),
+                            ..LogManager::with_config(&s.log_config)
+                        };
+                        (log_manager, s.log_config.native_paths())
+                    })
+                    .filter(|(_, paths)| !paths.is_empty())
+                    .collect()
+            };
+            if logs.is_empty() {
+                continue;
+            }
+
+            let _ = tokio::task::spawn_blocking(move || {
+                for (log_manager, paths) in logs {
+                    for path in paths {
+                        if let Err(e) = log_manager.truncate_log_if_needed(&path) {
+                            eprintln!("Warning: failed to rotate {}: {}", path.display(), e);
+                        }
+                    }
+                }
+            })
+            .await;
+        }
+    });
+}
+
+/// How often native logs are read for new lines to check against the alert rules
+const NATIVE_LOG_ALERT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Check new lines of the logs services write themselves against the alert rules;
+/// their captured stdout/stderr is checked as it's written
+pub fn spawn_native_log_alerts(service_manager: Arc<Mutex<ServiceManager>>, alerts: SharedLogAlerts) {
+    tauri::async_runtime::spawn(async move {
+        let mut watch = NativeLogAlerts::default();
+        let mut interval = tokio::time::interval(NATIVE_LOG_ALERT_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let native_logs = service_manager.lock().await.native_logs();
+            watch.set_files(&native_logs);
+            let alerts = alerts.clone();
+            watch = tokio::task::spawn_blocking(move || {
+                watch.poll(&alerts);
+                watch
+            })
+            .await
+            .unwrap_or_default();
+        }
+    });
+}
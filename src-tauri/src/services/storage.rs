@@ -1,4 +1,7 @@
-use crate::models::Project;
+use crate::models::{ControlApiConfig, PortReservation, Project, Workspace};
+use crate::services::log_manager::LogAlertRule;
+use crate::services::redis_manager::RedisAllocation;
+use crate::services::secret_vault::VaultEntry;
 use std::fs;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -11,6 +14,8 @@ pub enum StorageError {
     Json(#[from] serde_json::Error),
     #[error("Project not found: {0}")]
     NotFound(String),
+    #[error("Workspace not found: {0}")]
+    WorkspaceNotFound(String),
 }
 
 pub struct Storage {
@@ -86,9 +91,205 @@ impl Storage {
 
         projects.remove(index);
         self.save_projects(&projects)?;
+
+        // Drop the project from any workspace that referenced it
+        let mut workspaces = self.load_workspaces()?;
+        let mut changed = false;
+        for workspace in workspaces.iter_mut() {
+            let before = workspace.project_ids.len() + workspace.start_order.len();
+            workspace.project_ids.retain(|p| p != id);
+            workspace.start_order.retain(|p| p != id);
+            changed |= before != workspace.project_ids.len() + workspace.start_order.len();
+        }
+        if changed {
+            self.save_workspaces(&workspaces)?;
+        }
+        Ok(())
+    }
+
+    fn workspaces_file(&self) -> PathBuf {
+        self.data_dir.join("workspaces.json")
+    }
+
+    pub fn load_workspaces(&self) -> Result<Vec<Workspace>, StorageError> {
+        let path = self.workspaces_file();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let workspaces: Vec<Workspace> = serde_json::from_str(&content)?;
+        Ok(workspaces)
+    }
+
+    pub fn save_workspaces(&self, workspaces: &[Workspace]) -> Result<(), StorageError> {
+        let path = self.workspaces_file();
+        let content = serde_json::to_string_pretty(workspaces)?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+
+    pub fn get_workspace(&self, id: &str) -> Result<Workspace, StorageError> {
+        let workspaces = self.load_workspaces()?;
+        workspaces
+            .into_iter()
+            .find(|w| w.id == id)
+            .ok_or_else(|| StorageError::WorkspaceNotFound(id.to_string()))
+    }
+
+    pub fn create_workspace(&self, workspace: Workspace) -> Result<Workspace, StorageError> {
+        let mut workspaces = self.load_workspaces()?;
+        workspaces.push(workspace.clone());
+        self.save_workspaces(&workspaces)?;
+        Ok(workspace)
+    }
+
+    pub fn update_workspace(&self, workspace: Workspace) -> Result<Workspace, StorageError> {
+        let mut workspaces = self.load_workspaces()?;
+        let index = workspaces
+            .iter()
+            .position(|w| w.id == workspace.id)
+            .ok_or_else(|| StorageError::WorkspaceNotFound(workspace.id.clone()))?;
+
+        workspaces[index] = workspace.clone();
+        self.save_workspaces(&workspaces)?;
+        Ok(workspace)
+    }
+
+    pub fn delete_workspace(&self, id: &str) -> Result<(), StorageError> {
+        let mut workspaces = self.load_workspaces()?;
+        let index = workspaces
+            .iter()
+            .position(|w| w.id == id)
+            .ok_or_else(|| StorageError::WorkspaceNotFound(id.to_string()))?;
+
+        workspaces.remove(index);
+        self.save_workspaces(&workspaces)?;
+        Ok(())
+    }
+
+    fn port_reservations_file(&self) -> PathBuf {
+        self.data_dir.join("port_reservations.json")
+    }
+
+    pub fn load_port_reservations(&self) -> Result<Vec<PortReservation>, StorageError> {
+        let path = self.port_reservations_file();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let reservations: Vec<PortReservation> = serde_json::from_str(&content)?;
+        Ok(reservations)
+    }
+
+    pub fn save_port_reservations(&self, reservations: &[PortReservation]) -> Result<(), StorageError> {
+        let path = self.port_reservations_file();
+        let content = serde_json::to_string_pretty(reservations)?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+
+    fn control_api_file(&self) -> PathBuf {
+        self.data_dir.join("control_api.json")
+    }
+
+    pub fn load_control_api_config(&self) -> Result<ControlApiConfig, StorageError> {
+        let path = self.control_api_file();
+        if !path.exists() {
+            return Ok(ControlApiConfig::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let config: ControlApiConfig = serde_json::from_str(&content)?;
+        Ok(config)
+    }
+
+    pub fn save_control_api_config(&self, config: &ControlApiConfig) -> Result<(), StorageError> {
+        let path = self.control_api_file();
+        let content = serde_json::to_string_pretty(config)?;
+        fs::write(&path, content)?;
         Ok(())
     }
 
+    fn log_alerts_file(&self) -> PathBuf {
+        self.data_dir.join("log_alerts.json")
+    }
+
+    pub fn load_log_alert_rules(&self) -> Result<Vec<LogAlertRule>, StorageError> {
+        let path = self.log_alerts_file();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let rules: Vec<LogAlertRule> = serde_json::from_str(&content)?;
+        Ok(rules)
+    }
+
+    pub fn save_log_alert_rules(&self, rules: &[LogAlertRule]) -> Result<(), StorageError> {
+        let path = self.log_alerts_file();
+        let content = serde_json::to_string_pretty(rules)?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+
+    fn vault_file(&self) -> PathBuf {
+        self.data_dir.join("vault.json")
+    }
+
+    /// Index of the secret vault; the values are in the credential store
+    pub fn load_vault_entries(&self) -> Result<Vec<VaultEntry>, StorageError> {
+        let path = self.vault_file();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let entries: Vec<VaultEntry> = serde_json::from_str(&content)?;
+        Ok(entries)
+    }
+
+    pub fn save_vault_entries(&self, entries: &[VaultEntry]) -> Result<(), StorageError> {
+        let path = self.vault_file();
+        let content = serde_json::to_string_pretty(entries)?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+
+    fn redis_allocations_file(&self) -> PathBuf {
+        self.data_dir.join("redis_allocations.json")
+    }
+
+    /// Redis database indexes handed out to projects
+    pub fn load_redis_allocations(&self) -> Result<Vec<RedisAllocation>, StorageError> {
+        let path = self.redis_allocations_file();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let allocations: Vec<RedisAllocation> = serde_json::from_str(&content)?;
+        Ok(allocations)
+    }
+
+    pub fn save_redis_allocations(&self, allocations: &[RedisAllocation]) -> Result<(), StorageError> {
+        let path = self.redis_allocations_file();
+        let content = serde_json::to_string_pretty(allocations)?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// File holding the bearer token required by the local control API
+    pub fn control_api_token_file(&self) -> PathBuf {
+        self.data_dir.join("control_api.token")
+    }
+
+    /// Default Unix socket path of the local control API
+    pub fn control_api_socket_file(&self) -> PathBuf {
+        self.data_dir.join("control.sock")
+    }
+
     pub fn logs_dir(&self) -> PathBuf {
         let dir = self.data_dir.join("logs");
         let _ = fs::create_dir_all(&dir);
@@ -0,0 +1,91 @@
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a stopping process group is polled
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Build a command that runs `command_line` through `sh -c` in a new process group,
+/// so quoting, `&&` and `VAR=value` prefixes work and the whole tree
+/// (npm -> node -> esbuild) can be signalled at once.
+pub fn shell_command(command_line: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command_line).process_group(0);
+    cmd
+}
+
+/// Send `signal` to every process in the group led by `pgid`
+pub fn signal_group(pgid: u32, signal: i32) -> io::Result<()> {
+    // SAFETY: kill(2) has no memory-safety preconditions
+    if unsafe { libc::kill(-(pgid as i32), signal) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Whether any process of the group is still around
+pub fn group_alive(pgid: u32) -> bool {
+    match signal_group(pgid, 0) {
+        Ok(()) => true,
+        // The group exists but belongs to someone else
+        Err(e) => e.raw_os_error() == Some(libc::EPERM),
+    }
+}
+
+/// Whether `pid` leads its own process group
+pub fn is_group_leader(pid: u32) -> bool {
+    // SAFETY: getpgid(2) has no memory-safety preconditions
+    unsafe { libc::getpgid(pid as i32) == pid as i32 }
+}
+
+/// Signal a single process
+pub fn signal_process(pid: u32, signal: i32) -> io::Result<()> {
+    // SAFETY: kill(2) has no memory-safety preconditions
+    if unsafe { libc::kill(pid as i32, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Wait until the group leader has exited and the rest of the group is gone,
+/// or the deadline passes. Returns the leader's status if it exited.
+pub fn wait_for_group(child: &mut Child, deadline: Instant) -> Option<ExitStatus> {
+    let pgid = child.id();
+    let mut status = None;
+    loop {
+        if status.is_none() {
+            status = child.try_wait().ok().flatten();
+        }
+        // The leader's zombie keeps the group alive until it is reaped above
+        if status.is_some() && !group_alive(pgid) {
+            return status;
+        }
+        if Instant::now() >= deadline {
+            return status;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_signal_reaches_grandchildren() {
+        let mut child = shell_command("sleep 30 & sleep 30 && echo done").spawn().unwrap();
+        let pgid = child.id();
+        thread::sleep(Duration::from_millis(200));
+        assert!(group_alive(pgid));
+        assert!(is_group_leader(pgid));
+
+        signal_group(pgid, libc::SIGTERM).unwrap();
+        let status = wait_for_group(&mut child, Instant::now() + Duration::from_secs(2));
+        assert!(status.is_some_and(|s| !s.success()));
+        assert!(!group_alive(pgid));
+    }
+}
@@ -1,22 +1,26 @@
-use crate::services::ServiceManager;
+use crate::commands::workspace::{start_workspace_by_id, stop_workspace_by_id};
+use crate::services::log_manager::{LogAlert, LogSourceKind};
+use crate::services::storage::Storage;
+use crate::services::{ServiceManager, SharedProcessManager};
+use crate::state::AppState;
 use std::sync::Arc;
 use tauri::{
     image::Image,
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager, Runtime,
+    AppHandle, Emitter, Manager, Runtime, Wry,
 };
+use tauri_plugin_notification::NotificationExt;
 use tokio::sync::Mutex;
 
 const TRAY_ID: &str = "main-tray";
 
-pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error::Error>> {
-    let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
-    let hide_item = MenuItem::with_id(app, "hide", "Hide Window", true, None::<&str>)?;
-    let separator = PredefinedMenuItem::separator(app)?;
-    let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+/// Menu item ID prefixes for the per-workspace tray actions
+const WORKSPACE_START_PREFIX: &str = "workspace-start:";
+const WORKSPACE_STOP_PREFIX: &str = "workspace-stop:";
 
-    let menu = Menu::with_items(app, &[&show_item, &hide_item, &separator, &quit_item])?;
+pub fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_tray_menu(app)?;
 
     let icon = load_tray_icon()?;
 
@@ -46,7 +50,68 @@ pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// Build the tray menu, including a Start/Stop entry for each saved workspace
+fn build_tray_menu<M: Manager<Wry>>(app: &M) -> tauri::Result<Menu<Wry>> {
+    let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
+    let hide_item = MenuItem::with_id(app, "hide", "Hide Window", true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+
+    let menu = Menu::with_items(app, &[&show_item, &hide_item])?;
+
+    let workspaces = Storage::new()
+        .and_then(|s| s.load_workspaces())
+        .unwrap_or_default();
+    if !workspaces.is_empty() {
+        let submenu = Submenu::with_id(app, "workspaces", "Workspaces", true)?;
+        for workspace in &workspaces {
+            let start_item = MenuItem::with_id(
+                app,
+                format!("{}{}", WORKSPACE_START_PREFIX, workspace.id),
+                format!("Start {}", workspace.name),
+                true,
+                None::<&str>,
+            )?;
+            let stop_item = MenuItem::with_id(
+                app,
+                format!("{}{}", WORKSPACE_STOP_PREFIX, workspace.id),
+                format!("Stop {}", workspace.name),
+                true,
+                None::<&str>,
+            )?;
+            submenu.append_items(&[&start_item, &stop_item])?;
+        }
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+        menu.append(&submenu)?;
+    }
+
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&quit_item)?;
+
+    Ok(menu)
+}
+
+/// Rebuild the tray menu after workspaces change
+pub fn refresh_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => eprintln!("Failed to rebuild tray menu: {}", e),
+    }
+}
+
 fn load_tray_icon() -> Result<Image<'static>, Box<dyn std::error::Error>> {
+    let rgba_img = decode_tray_icon()?;
+    let (width, height) = rgba_img.dimensions();
+    let rgba_bytes = rgba_img.into_raw();
+
+    Ok(Image::new_owned(rgba_bytes, width, height))
+}
+
+fn decode_tray_icon() -> Result<image::RgbaImage, Box<dyn std::error::Error>> {
     // Load embedded PNG icon - convert to RGBA format
     // The 32x32 PNG is already included in the binary
     let icon_bytes = include_bytes!("../icons/32x32.png");
@@ -54,14 +119,89 @@ fn load_tray_icon() -> Result<Image<'static>, Box<dyn std::error::Error>> {
     // Decode the PNG to get raw RGBA pixels
     let img = image::load_from_memory(icon_bytes)
         .map_err(|e| format!("Failed to load icon: {}", e))?;
-    let rgba_img = img.to_rgba8();
+    Ok(img.to_rgba8())
+}
+
+/// Tray icon with a red dot in the top-right corner
+fn load_badged_tray_icon() -> Result<Image<'static>, Box<dyn std::error::Error>> {
+    let mut rgba_img = decode_tray_icon()?;
     let (width, height) = rgba_img.dimensions();
-    let rgba_bytes = rgba_img.into_raw();
+    let radius = width.min(height) as f32 / 4.0;
+    let (cx, cy) = (width as f32 - radius, radius);
 
-    Ok(Image::new_owned(rgba_bytes, width, height))
+    for (x, y, pixel) in rgba_img.enumerate_pixels_mut() {
+        let distance = ((x as f32 + 0.5 - cx).powi(2) + (y as f32 + 0.5 - cy).powi(2)).sqrt();
+        if distance <= radius - 1.0 {
+            *pixel = image::Rgba([220, 38, 38, 255]);
+        } else if distance <= radius {
+            *pixel = image::Rgba([255, 255, 255, 255]);
+        }
+    }
+
+    Ok(Image::new_owned(rgba_img.into_raw(), width, height))
+}
+
+/// Show a log alert: `log-alert` event, desktop notification and a badge on the tray
+pub fn notify_log_alert(app: &AppHandle, alert: &LogAlert) {
+    let _ = app.emit("log-alert", alert);
+
+    let kind = match alert.origin.kind {
+        LogSourceKind::Project => "project",
+        LogSourceKind::Service => "service",
+    };
+    let body: String = alert.message.chars().take(200).collect();
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(format!("{} ({} {})", alert.rule_name, kind, alert.origin.name))
+        .body(body)
+        .show()
+    {
+        eprintln!("Failed to show log alert notification: {}", e);
+    }
+
+    set_alert_badge(app, alert.unread);
+}
+
+/// Badge the tray icon while there are unread log alerts; 0 clears it
+pub fn set_alert_badge(app: &AppHandle, unread: u32) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let icon = if unread > 0 {
+        load_badged_tray_icon()
+    } else {
+        load_tray_icon()
+    };
+    match icon {
+        Ok(icon) => {
+            let _ = tray.set_icon(Some(icon));
+        }
+        Err(e) => eprintln!("Failed to update tray icon: {}", e),
+    }
+
+    let tooltip = if unread > 0 {
+        format!("ClickDevPort - {} unread log alert(s)", unread)
+    } else {
+        "ClickDevPort".to_string()
+    };
+    let _ = tray.set_tooltip(Some(tooltip));
+    // macOS shows the title next to the icon
+    #[cfg(target_os = "macos")]
+    let _ = tray.set_title((unread > 0).then(|| unread.to_string()));
 }
 
-fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, menu_id: &str) {
+fn handle_menu_event(app: &AppHandle, menu_id: &str) {
+    if let Some(workspace_id) = menu_id.strip_prefix(WORKSPACE_START_PREFIX) {
+        run_workspace_action(app, workspace_id.to_string(), true);
+        return;
+    }
+    if let Some(workspace_id) = menu_id.strip_prefix(WORKSPACE_STOP_PREFIX) {
+        run_workspace_action(app, workspace_id.to_string(), false);
+        return;
+    }
+
     match menu_id {
         "show" => {
             if let Some(window) = app.get_webview_window("main") {
@@ -80,11 +220,34 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, menu_id: &str) {
                 // Notify frontend about shutdown
                 let _ = app_handle.emit("app-shutting-down", ());
 
-                // Stop all services via ServiceManager
+                // Stop all running services via ServiceManager
                 if let Some(service_manager) = app_handle.try_state::<Arc<Mutex<ServiceManager>>>() {
-                    let mut sm = service_manager.lock().await;
-                    let _ = sm.stop_service("apache").await;
-                    let _ = sm.stop_service("mariadb").await;
+                    let running: Vec<String> = service_manager
+                        .lock()
+                        .await
+                        .get_services()
+                        .into_iter()
+                        .filter(|service| service.is_running())
+                        .map(|service| service.id.clone())
+                        .collect();
+                    for id in running {
+                        if let Err(e) = ServiceManager::stop_service(&service_manager, &id).await {
+                            eprintln!("Failed to stop {}: {}", id, e);
+                        }
+                    }
+                }
+
+                // Stop all running projects
+                if let Some(process_manager) = app_handle.try_state::<SharedProcessManager>() {
+                    let stopping = process_manager.lock().await.take_all_for_stop();
+                    let _ = tokio::task::spawn_blocking(move || {
+                        for mut project in stopping {
+                            if let Err(e) = project.stop() {
+                                eprintln!("Failed to stop {}: {}", project.project_id(), e);
+                            }
+                        }
+                    })
+                    .await;
                 }
 
                 // Wait for processes to terminate
@@ -101,6 +264,26 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, menu_id: &str) {
     }
 }
 
+/// Start or stop a workspace from the tray in the background
+fn run_workspace_action(app: &AppHandle, workspace_id: String, start: bool) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<Arc<Mutex<AppState>>>().inner().clone();
+        let process_manager = app_handle.state::<SharedProcessManager>().inner().clone();
+        let service_manager = app_handle.state::<Arc<Mutex<ServiceManager>>>().inner().clone();
+
+        let result = if start {
+            start_workspace_by_id(&workspace_id, &state, &process_manager, &service_manager, &app_handle).await
+        } else {
+            stop_workspace_by_id(&workspace_id, &state, &process_manager, &service_manager, &app_handle).await
+        };
+
+        if let Err(e) = result {
+            eprintln!("Workspace {} failed: {}", workspace_id, e.message);
+        }
+    });
+}
+
 /// Force kill remaining service processes on shutdown
 fn force_kill_services() {
     #[cfg(windows)]
@@ -108,7 +291,7 @@ fn force_kill_services() {
         use std::os::windows::process::CommandExt;
         const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-        for process_name in &["httpd.exe", "mysqld.exe", "mariadbd.exe"] {
+        for process_name in &["httpd.exe", "mysqld.exe", "mariadbd.exe", "postgres.exe", "redis-server.exe"] {
             let _ = std::process::Command::new("taskkill")
                 .args(["/F", "/IM", process_name])
                 .creation_flags(CREATE_NO_WINDOW)
@@ -143,6 +143,8 @@ function ServiceControl({ serviceId, serviceName, collapsed = false }: ServiceCo
 const SERVICES = [
   { id: "apache", name: "Apache" },
   { id: "mariadb", name: "MariaDB" },
+  { id: "postgresql", name: "PostgreSQL" },
+  { id: "redis", name: "Redis" },
 ] as const;
 
 interface ServiceQuickControlsProps {
@@ -154,7 +156,7 @@ export function ServiceQuickControls({ collapsed = false }: ServiceQuickControls
 
   // Check which services are installed
   const installedServices = SERVICES.filter(
-    (svc) => services.some((s) => s.id === svc.id)
+    (svc) => services.some((s) => s.id === svc.id && s.installed)
   );
 
   if (installedServices.length === 0) {
@@ -1,5 +1,6 @@
 export { useTauriEvents, useHealthCheck } from "./useTauriEvents";
 export { useLogStream } from "./useLogStream";
+export { useLogTail } from "./useLogTail";
 export { useServiceControl, type ServiceAction, type UseServiceControlReturn } from "./useServiceControl";
 export { useProjectActions, type UseProjectActionsReturn } from "./useProjectActions";
 export { useBuildElapsedTime } from "./useBuildElapsedTime";
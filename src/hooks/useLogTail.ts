@@ -0,0 +1,120 @@
+import { useEffect, useRef, useCallback, useState } from "react";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
+import { startLogTail, ackLogTail, stopLogTail } from "@/services/tauriCommands";
+import type { LogSearchHit, LogTailBatch, LogTailOptions } from "@/types";
+
+interface UseLogTailOptions extends LogTailOptions {
+  maxEntries?: number;
+  onError?: (error: Error) => void;
+}
+
+interface UseLogTailResult {
+  lines: LogSearchHit[];
+  isTailing: boolean;
+  startTail: () => Promise<void>;
+  stopTail: () => Promise<void>;
+  clearLines: () => void;
+}
+
+/**
+ * Merged, time-ordered tail of several project and service logs.
+ * Each batch is acknowledged once added, so the backend never runs ahead of the view.
+ */
+export function useLogTail({
+  maxEntries = 2000,
+  onError,
+  ...options
+}: UseLogTailOptions): UseLogTailResult {
+  const [lines, setLines] = useState<LogSearchHit[]>([]);
+  const [isTailing, setIsTailing] = useState(false);
+  const unlistenRef = useRef<UnlistenFn | null>(null);
+  const tailIdRef = useRef<string | null>(null);
+  const optionsRef = useRef(options);
+  optionsRef.current = options;
+
+  const reportError = useCallback(
+    (error: unknown, message: string) => {
+      const err = error instanceof Error ? error : new Error(String(error));
+      onError?.(err);
+      console.error(message, err);
+    },
+    [onError]
+  );
+
+  const startTail = useCallback(async () => {
+    if (tailIdRef.current) return;
+
+    try {
+      // Batches can arrive before start_log_tail returns; keep them until the id is known
+      const early: LogTailBatch[] = [];
+      const handle = (batch: LogTailBatch) => {
+        setLines((prev) => {
+          const combined = [...prev, ...batch.lines];
+          return combined.length > maxEntries ? combined.slice(-maxEntries) : combined;
+        });
+        ackLogTail(batch.tailId).catch(() => {
+          // The tail may have been stopped meanwhile
+        });
+      };
+
+      unlistenRef.current = await listen<LogTailBatch>("log-tail", (event) => {
+        if (tailIdRef.current === null) {
+          early.push(event.payload);
+        } else if (event.payload.tailId === tailIdRef.current) {
+          handle(event.payload);
+        }
+      });
+
+      const tailId = await startLogTail(optionsRef.current);
+      tailIdRef.current = tailId;
+      early.filter((batch) => batch.tailId === tailId).forEach(handle);
+      setIsTailing(true);
+    } catch (error) {
+      unlistenRef.current?.();
+      unlistenRef.current = null;
+      reportError(error, "Failed to start log tail:");
+    }
+  }, [maxEntries, reportError]);
+
+  const stopTail = useCallback(async () => {
+    const tailId = tailIdRef.current;
+    if (!tailId) return;
+
+    try {
+      await stopLogTail(tailId);
+    } catch (error) {
+      reportError(error, "Failed to stop log tail:");
+    } finally {
+      unlistenRef.current?.();
+      unlistenRef.current = null;
+      tailIdRef.current = null;
+      setIsTailing(false);
+    }
+  }, [reportError]);
+
+  const clearLines = useCallback(() => {
+    setLines([]);
+  }, []);
+
+  // Stop the backend tail on unmount
+  useEffect(() => {
+    return () => {
+      unlistenRef.current?.();
+      unlistenRef.current = null;
+      if (tailIdRef.current) {
+        stopLogTail(tailIdRef.current).catch(() => {
+          // Ignore errors during cleanup
+        });
+        tailIdRef.current = null;
+      }
+    };
+  }, []);
+
+  return {
+    lines,
+    isTailing,
+    startTail,
+    stopTail,
+    clearLines,
+  };
+}
@@ -1,5 +1,6 @@
 import { useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { useProcessStore } from "@/stores";
 import type { Project } from "@/types";
 
 /**
@@ -37,6 +38,10 @@ export interface UseProjectActionsReturn {
  * ```
  */
 export function useProjectActions(project: Project): UseProjectActionsReturn {
+  // Prefer the port the dev server actually reported over the configured one
+  const runningPort = useProcessStore((state) => state.processes[project.id]?.port);
+  const port = runningPort || project.port;
+
   const openInVscode = useCallback(async () => {
     try {
       await invoke("open_in_vscode", { path: project.path });
@@ -56,12 +61,12 @@ export function useProjectActions(project: Project): UseProjectActionsReturn {
   const openInBrowser = useCallback(async () => {
     try {
       await invoke("open_in_browser", {
-        url: `http://localhost:${project.port}`,
+        url: `http://localhost:${port}`,
       });
     } catch (error) {
       console.error("Failed to open browser:", error);
     }
-  }, [project.port]);
+  }, [port]);
 
   const openInExplorer = useCallback(async () => {
     try {
@@ -1,7 +1,7 @@
 import { useEffect } from "react";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
-import { useLogStore, useProcessStore, useActivityLogStore, useProjectStore } from "@/stores";
-import type { ProcessLog } from "@/types";
+import { useLogStore, useProcessStore, useActivityLogStore, useProjectStore, useServiceStore } from "@/stores";
+import type { HealthStatus, LogAlert, ProcessLog, ResourceUsage, StopStep } from "@/types";
 
 interface ProcessStartedPayload {
   projectId: string;
@@ -10,6 +10,14 @@ interface ProcessStartedPayload {
 
 interface ProcessStoppedPayload {
   projectId: string;
+  stoppedBy?: StopStep;
+}
+
+interface ProcessExitedPayload {
+  projectId: string;
+  pid: number;
+  exitCode: number | null;
+  success: boolean;
 }
 
 interface ProcessLogPayload {
@@ -18,6 +26,22 @@ interface ProcessLogPayload {
   stream: "stdout" | "stderr";
 }
 
+interface ProjectPortChangedPayload {
+  projectId: string;
+  host: string;
+  port: number;
+  previousPort: number;
+  url: string;
+  persisted: boolean;
+}
+
+interface ProjectHealthChangedPayload {
+  projectId: string;
+  isHealthy: boolean;
+  wasHealthy: boolean | null;
+  status: HealthStatus;
+}
+
 interface BuildStatusPayload {
   projectId: string;
   status: "starting" | "compiling" | "compiled" | "launched" | "error" | "progress";
@@ -54,6 +78,22 @@ export function useTauriEvents() {
       useProcessStore.getState().clearBuildLoading(projectId);
     }).then((unlisten) => unlisteners.push(unlisten));
 
+    // Listen for process exited events (process ended on its own)
+    listen<ProcessExitedPayload>("process-exited", (event) => {
+      const { projectId, exitCode, success } = event.payload;
+      setProcessInfo(projectId, null);
+      useProcessStore.getState().clearBuildLoading(projectId);
+
+      const project = useProjectStore.getState().getProjectById(projectId);
+      const name = project?.name ?? projectId;
+      const code = exitCode ?? "unknown";
+      addActivityLog(
+        name,
+        success ? "Process exited" : `Process exited with code ${code}`,
+        success ? "info" : "error"
+      );
+    }).then((unlisten) => unlisteners.push(unlisten));
+
     // Listen for process log events
     listen<ProcessLogPayload>("process-log", (event) => {
       const { projectId, line, stream } = event.payload;
@@ -66,6 +106,72 @@ export function useTauriEvents() {
       addLog(log);
     }).then((unlisten) => unlisteners.push(unlisten));
 
+    // Listen for dev servers that bound a different port than configured
+    listen<ProjectPortChangedPayload>("project-port-changed", (event) => {
+      const { projectId, port, previousPort, url, persisted } = event.payload;
+      const processInfo = useProcessStore.getState().getProcessInfo(projectId);
+      if (processInfo) {
+        setProcessInfo(projectId, { ...processInfo, port });
+      }
+      if (persisted) {
+        useProjectStore.getState().fetchProjects();
+      }
+
+      const project = useProjectStore.getState().getProjectById(projectId);
+      const name = project?.name ?? projectId;
+      addActivityLog(name, `Port ${previousPort} was taken, server is on ${url}`, "warning");
+    }).then((unlisten) => unlisteners.push(unlisten));
+
+    // Listen for CPU/memory samples of running projects and services
+    listen<ResourceUsage[]>("resource-usage", (event) => {
+      useProcessStore.setState((state) => {
+        for (const usage of event.payload) {
+          const info = state.processes[usage.id];
+          if (usage.owner !== "project" || !info || info.pid !== usage.pid) continue;
+          info.cpuUsage = usage.cpuUsage;
+          info.memoryUsage = usage.memoryUsage;
+        }
+      });
+      useServiceStore.setState((state) => ({
+        services: state.services.map((service) => {
+          const usage = event.payload.find(
+            (u) => u.owner === "service" && u.id === service.id && u.pid === service.pid
+          );
+          return usage
+            ? { ...service, cpuUsage: usage.cpuUsage, memoryUsage: usage.memoryUsage }
+            : service;
+        }),
+      }));
+    }).then((unlisten) => unlisteners.push(unlisten));
+
+    // Listen for health transitions reported by the background monitor
+    listen<ProjectHealthChangedPayload>("project-health-changed", (event) => {
+      const { projectId, isHealthy, wasHealthy, status } = event.payload;
+      useProcessStore.setState((state) => {
+        state.healthStatuses[projectId] = status;
+      });
+
+      // Only report changes, not the first sample of a freshly started project
+      if (wasHealthy === null) return;
+      const project = useProjectStore.getState().getProjectById(projectId);
+      const name = project?.name ?? projectId;
+      if (isHealthy) {
+        addActivityLog(name, "Health check recovered", "success");
+      } else {
+        addActivityLog(name, `Health check failing: ${status.error ?? "unhealthy"}`, "error");
+      }
+    }).then((unlisten) => unlisteners.push(unlisten));
+
+    // Listen for log alert rules firing
+    listen<LogAlert>("log-alert", (event) => {
+      const { ruleName, origin, message } = event.payload;
+      const name =
+        origin.kind === "project"
+          ? origin.name
+          : useServiceStore.getState().services.find((s) => s.id === origin.name)?.name ?? origin.name;
+      addActivityLog(name, `${ruleName}: ${message}`, "error");
+    }).then((unlisten) => unlisteners.push(unlisten));
+
     // Listen for build status events
     listen<BuildStatusPayload>("build-status", (event) => {
       const { projectId, status, message } = event.payload;
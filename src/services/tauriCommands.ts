@@ -6,7 +6,21 @@ import type {
   DetectedProjectInfo,
   PortInfo,
   ProcessInfo,
+  PortConflictAction,
   HealthStatus,
+  HealthSummary,
+  StopOutcome,
+  ResourceOwner,
+  ResourceSample,
+  ResourceUsage,
+  LogSearchQuery,
+  LogSearchHit,
+  LogDiskUsage,
+  LogTailOptions,
+  LogAlertRule,
+  VaultEntry,
+  RedisConfig,
+  RedisAllocation,
 } from "@/types";
 
 // Project commands
@@ -35,14 +49,22 @@ export async function startProject(projectId: string): Promise<ProcessInfo> {
   return invoke<ProcessInfo>("start_project", { projectId });
 }
 
-export async function stopProject(projectId: string): Promise<void> {
-  return invoke<void>("stop_project", { projectId });
+/** Resolves to null if the project was not running */
+export async function stopProject(projectId: string): Promise<StopOutcome | null> {
+  return invoke<StopOutcome | null>("stop_project", { projectId });
 }
 
 export async function restartProject(projectId: string): Promise<ProcessInfo> {
   return invoke<ProcessInfo>("restart_project", { projectId });
 }
 
+export async function resolvePortConflict(
+  projectId: string,
+  action: PortConflictAction
+): Promise<ProcessInfo | null> {
+  return invoke<ProcessInfo | null>("resolve_port_conflict", { projectId, action });
+}
+
 export async function getRunningProcesses(): Promise<ProcessInfo[]> {
   return invoke<ProcessInfo[]>("get_running_processes");
 }
@@ -57,8 +79,120 @@ export async function checkPortAvailable(port: number): Promise<boolean> {
 }
 
 // Health commands
-export async function checkHealth(projectId: string, url: string): Promise<HealthStatus> {
-  return invoke<HealthStatus>("check_health", { projectId, url });
+export async function checkHealth(
+  projectId: string,
+  url: string,
+  timeoutSecs?: number
+): Promise<HealthStatus> {
+  return invoke<HealthStatus>("check_health", { projectId, url, timeoutSecs });
+}
+
+export async function getHealthHistory(projectId: string): Promise<HealthStatus[]> {
+  return invoke<HealthStatus[]>("get_health_history", { projectId });
+}
+
+export async function getHealthSummary(projectId: string): Promise<HealthSummary | null> {
+  return invoke<HealthSummary | null>("get_health_summary", { projectId });
+}
+
+export async function getHealthSummaries(): Promise<HealthSummary[]> {
+  return invoke<HealthSummary[]>("get_health_summaries");
+}
+
+// Log commands
+export async function searchLogs(query: LogSearchQuery): Promise<LogSearchHit[]> {
+  return invoke<LogSearchHit[]>("search_logs", { query });
+}
+
+export async function getLogDiskUsage(): Promise<LogDiskUsage[]> {
+  return invoke<LogDiskUsage[]>("get_log_disk_usage");
+}
+
+export async function startLogTail(options: LogTailOptions): Promise<string> {
+  return invoke<string>("start_log_tail", { options });
+}
+
+export async function ackLogTail(tailId: string): Promise<boolean> {
+  return invoke<boolean>("ack_log_tail", { tailId });
+}
+
+export async function stopLogTail(tailId: string): Promise<boolean> {
+  return invoke<boolean>("stop_log_tail", { tailId });
+}
+
+// Log alert commands
+export async function getLogAlertRules(): Promise<LogAlertRule[]> {
+  return invoke<LogAlertRule[]>("get_log_alert_rules");
+}
+
+export async function setLogAlertRules(rules: LogAlertRule[]): Promise<LogAlertRule[]> {
+  return invoke<LogAlertRule[]>("set_log_alert_rules", { rules });
+}
+
+export async function getUnreadLogAlerts(): Promise<number> {
+  return invoke<number>("get_unread_log_alerts");
+}
+
+export async function clearLogAlerts(): Promise<void> {
+  return invoke("clear_log_alerts");
+}
+
+// Secret vault commands
+export async function listVaultEntries(projectId: string): Promise<VaultEntry[]> {
+  return invoke<VaultEntry[]>("list_vault_entries", { projectId });
+}
+
+export async function setVaultEntry(
+  projectId: string,
+  name: string,
+  value: string
+): Promise<VaultEntry> {
+  return invoke<VaultEntry>("set_vault_entry", { projectId, name, value });
+}
+
+export async function rotateVaultEntry(
+  projectId: string,
+  name: string,
+  value?: string
+): Promise<VaultEntry> {
+  return invoke<VaultEntry>("rotate_vault_entry", { projectId, name, value: value ?? null });
+}
+
+export async function deleteVaultEntry(projectId: string, name: string): Promise<void> {
+  return invoke("delete_vault_entry", { projectId, name });
+}
+
+// Redis commands
+export async function getRedisConfig(): Promise<RedisConfig> {
+  return invoke<RedisConfig>("get_redis_config");
+}
+
+export async function updateRedisConfig(config: RedisConfig): Promise<RedisConfig> {
+  return invoke<RedisConfig>("update_redis_config", { config });
+}
+
+export async function listRedisAllocations(): Promise<RedisAllocation[]> {
+  return invoke<RedisAllocation[]>("list_redis_allocations");
+}
+
+export async function allocateRedisDatabase(projectId: string): Promise<RedisAllocation> {
+  return invoke<RedisAllocation>("allocate_redis_database", { projectId });
+}
+
+export async function releaseRedisDatabase(projectId: string): Promise<void> {
+  return invoke("release_redis_database", { projectId });
+}
+
+// Resource usage commands
+export async function getResourceUsage(): Promise<ResourceUsage[]> {
+  return invoke<ResourceUsage[]>("get_resource_usage");
+}
+
+export async function getResourceHistory(
+  owner: ResourceOwner,
+  id: string
+): Promise<ResourceSample[]> {
+  return invoke<ResourceSample[]>("get_resource_history", { owner, id });
 }
 
 // Apache port/vhost entry type
@@ -0,0 +1,15 @@
+export type ControlApiTransport = "tcp" | "unix";
+
+export interface ControlApiConfig {
+  enabled: boolean;
+  transport: ControlApiTransport;
+  port: number;
+  socketPath: string | null;
+}
+
+export interface ControlApiStatus {
+  config: ControlApiConfig;
+  running: boolean;
+  address: string | null;
+  tokenPath: string;
+}
@@ -39,3 +39,13 @@ export interface ProfileComparison {
   onlyInB: string[];
   differentValues: ProfileDiff[];
 }
+
+/** Project secret referenced from .env files as `${vault:name}` */
+export interface VaultEntry {
+  projectId: string;
+  name: string;
+  createdAt: string;
+  rotatedAt: string;
+  /** Variables referencing the entry */
+  references?: string[];
+}
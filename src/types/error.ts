@@ -53,6 +53,11 @@ export enum ErrorCode {
   PROJECT_DETECTION_FAILED = "PROJECT_DETECTION_FAILED",
   PROJECT_ALREADY_EXISTS = "PROJECT_ALREADY_EXISTS",
 
+  // Dependency errors
+  DEPENDENCY_CYCLE = "DEPENDENCY_CYCLE",
+  DEPENDENCY_NOT_FOUND = "DEPENDENCY_NOT_FOUND",
+  DEPENDENCY_FAILED = "DEPENDENCY_FAILED",
+
   // Domain/Hosts errors
   DOMAIN_INVALID = "DOMAIN_INVALID",
   DOMAIN_CONFLICT = "DOMAIN_CONFLICT",
@@ -77,6 +82,8 @@ export interface CommandError {
   message: string;
   details?: string;
   isRetryable: boolean;
+  /** Structured payload for errors the UI can act on, e.g. PortConflict */
+  data?: unknown;
 }
 
 /**
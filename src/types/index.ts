@@ -12,3 +12,5 @@ export * from "./uninstaller";
 export * from "./inventory";
 export * from "./installer";
 export * from "./error";
+export * from "./workspace";
+export * from "./controlApi";
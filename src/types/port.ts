@@ -7,3 +7,12 @@ export interface PortInfo {
   state: string;
   localAddress: string;
 }
+
+export type PortOwnerKind = "project" | "service" | "apache" | "manual";
+
+export interface PortReservation {
+  port: number;
+  kind: PortOwnerKind;
+  ownerId: string;
+  ownerName: string;
+}
@@ -15,6 +15,18 @@ export interface ProcessInfo {
   memoryUsage: number | null;
 }
 
+export type PortConflictAction = "killOwner" | "useNextPort" | "abort";
+
+/** `data` of the PORT_IN_USE error returned by start_project */
+export interface PortConflict {
+  projectId: string;
+  port: number;
+  ownerPid: number | null;
+  ownerName: string | null;
+  nextFreePort: number | null;
+  actions: PortConflictAction[];
+}
+
 export interface ProcessLog {
   projectId: string;
   line: string;
@@ -30,3 +42,32 @@ export interface HealthStatus {
   error: string | null;
   checkedAt: string;
 }
+
+export type ResourceOwner = "project" | "service";
+
+/** CPU and memory of a whole process tree at one point in time */
+export interface ResourceSample {
+  timestamp: string;
+  cpuUsage: number | null;  // Percent of one core (can exceed 100); null on the first sample
+  memoryUsage: number;      // Resident bytes
+  processCount: number;
+}
+
+/** Latest sample of a project or service, as sent with `resource-usage` */
+export interface ResourceUsage extends ResourceSample {
+  owner: ResourceOwner;
+  id: string;
+  pid: number;
+}
+
+/** Uptime and latency over a running project's recent health samples */
+export interface HealthSummary {
+  projectId: string;
+  isHealthy: boolean;
+  since: string;  // When the project last turned healthy/unhealthy
+  lastCheck: HealthStatus;
+  samples: number;
+  uptimePercent: number;
+  avgResponseMs: number | null;
+  p95ResponseMs: number | null;
+}
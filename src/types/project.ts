@@ -1,3 +1,5 @@
+import type { HealthCheckConfig, LogConfig, StopStrategy } from "./service";
+
 export type ProjectType =
   | "tauri"     // Desktop app with Tauri (highest priority)
   | "electron"  // Desktop app with Electron
@@ -22,6 +24,16 @@ export type ProjectType =
   | "express"
   | "unknown";
 
+export type RestartMode = "never" | "on-failure" | "always";
+
+export interface RestartPolicy {
+  mode: RestartMode;
+  restartDelay: number;  // ms before the first restart, doubled per attempt
+  maxDelay: number;      // ms cap for the backoff delay
+  maxRestarts: number;   // consecutive restarts before the project is marked as errored
+  resetAfter: number;    // ms of uptime after which the restart counter resets
+}
+
 export interface Project {
   id: string;
   name: string;
@@ -32,13 +44,23 @@ export interface Project {
   envVars: Record<string, string>;
   autoStart: boolean;
   healthCheckUrl: string | null;
+  healthCheck: HealthCheckConfig | null;  // Overrides healthCheckUrl when set
   domain: string | null;  // Custom domain for hosts file (e.g., "my-app.test")
   githubUrl: string | null;  // GitHub repository URL
   launchMode: "web" | "app";  // "web" = open browser, "app" = native window
+  restartPolicy: RestartPolicy;
+  dependsOn: string[];  // IDs of projects or services that must be up first
+  persistDetectedPort: boolean;  // Save the port the dev server reports when it differs
+  stopTimeout: number;  // Time (ms) each graceful stop step gets before the next
+  stopStrategy: StopStrategy;
+  logConfig: LogConfig;  // Rotation of the persisted stdout/stderr logs
   createdAt: string;
   updatedAt: string;
 }
 
+/** Server a project database is created on */
+export type DatabaseEngine = "mysql" | "postgres";
+
 export interface CreateProjectInput {
   name: string;
   path: string;
@@ -47,11 +69,21 @@ export interface CreateProjectInput {
   startCommand: string;
   autoStart?: boolean;
   healthCheckUrl?: string | null;
+  healthCheck?: HealthCheckConfig | null;
   domain?: string | null;  // Custom domain for hosts file
   githubUrl?: string | null;  // GitHub repository URL
   launchMode?: "web" | "app";
+  restartPolicy?: RestartPolicy;
+  dependsOn?: string[];
+  persistDetectedPort?: boolean;
+  stopTimeout?: number;
+  stopStrategy?: StopStrategy;
+  logConfig?: Partial<LogConfig>;
   createDatabase?: boolean;
   databaseName?: string | null;
+  databaseEngine?: DatabaseEngine;  // Defaults to "mysql"
+  allocateRedis?: boolean;  // Reserve a Redis database and write REDIS_URL to .env
+  allowPortConflict?: boolean;  // Save even if another project/service reserves the port
 }
 
 export interface UpdateProjectInput {
@@ -61,7 +93,15 @@ export interface UpdateProjectInput {
   startCommand?: string;
   autoStart?: boolean;
   healthCheckUrl?: string | null;
+  healthCheck?: HealthCheckConfig | null;
   launchMode?: "web" | "app";
+  restartPolicy?: RestartPolicy;
+  dependsOn?: string[];
+  persistDetectedPort?: boolean;
+  stopTimeout?: number;
+  stopStrategy?: StopStrategy;
+  logConfig?: Partial<LogConfig>;
+  allowPortConflict?: boolean;
 }
 
 export interface DetectedProjectInfo {
@@ -1,6 +1,56 @@
 export type ServiceStatus = 'running' | 'stopped' | 'error' | 'unhealthy' | 'notinstalled';
 export type ServiceType = 'webserver' | 'database' | 'runtime' | 'tool';
 
+export type HealthCheckType = 'http' | 'tcp' | 'process' | 'command';
+
+/** Health probe shared by services and projects */
+export interface HealthCheckConfig {
+  checkType: HealthCheckType;
+  endpoint: string | null;
+  interval: number;  // ms
+  timeout: number;   // ms
+  retries: number;
+  expectedStatus?: string | null;  // e.g. "200-299,302"; default any 2xx/3xx
+  bodyContains?: string | null;
+  bodyRegex?: string | null;
+  jsonPointer?: string | null;     // e.g. "/status"
+  jsonValue?: unknown;             // Expected value at jsonPointer
+  headers?: Record<string, string>;
+  insecureTls?: boolean;           // Accept self-signed local certificates
+  command?: string | null;         // For checkType "command": exit 0 = healthy
+  tcpSend?: string | null;         // Sent by a "tcp" probe once connected, e.g. "PING\r\n"
+  tcpExpect?: string | null;       // Required start of the reply line, e.g. "+PONG"
+}
+
+/** Graceful request sent before a force kill */
+export type StopSignal = 'terminate' | 'interrupt' | 'ctrl-break' | 'none';
+
+/** How a project or service is shut down: stop command, then signal, then force kill */
+export interface StopStrategy {
+  signal: StopSignal;
+  command: string | null;  // e.g. "mysqladmin -u root shutdown"
+  forceKill: boolean;
+}
+
+/** Which stop step ended the process */
+export type StopStep = 'already-exited' | 'command' | 'signal' | 'force-kill';
+
+export interface StopOutcome {
+  stoppedBy: StopStep;
+  exitCode: number | null;
+}
+
+/** Size, count and retention of a project's or service's log files */
+export interface LogConfig {
+  stdoutPath: string;
+  stderrPath: string;
+  maxSize: string;         // e.g. "50MB"
+  maxFiles: number;
+  rotationPolicy: 'size' | 'daily' | 'hourly';
+  retentionDays: number;   // 0 = keep forever
+  compress: boolean;       // Gzip rotated files
+}
+
 export interface ConfigFileInfo {
   name: string;
   path: string;
@@ -21,6 +71,11 @@ export interface ServiceInfo {
   errorMessage: string | null;
   installed: boolean;
   configFiles: ConfigFileInfo[];
+  stopStrategy: StopStrategy;
+  stopTimeout: number;  // ms per graceful stop step
+  stoppedBy: StopStep | null;
+  cpuUsage: number | null;     // Percent of one core for the process tree
+  memoryUsage: number | null;  // Resident bytes for the process tree
 }
 
 export interface LogEntry {
@@ -30,6 +85,81 @@ export interface LogEntry {
   source: string;
 }
 
+export type LogLevel = 'DEBUG' | 'INFO' | 'WARN' | 'ERROR';
+
+/** Parser that recognised a log line */
+export type LogFormat =
+  | 'dev-port'
+  | 'json-lines'
+  | 'apache-error'
+  | 'apache-access'
+  | 'mariadb'
+  | 'laravel'
+  | 'python'
+  | 'plain';
+
+/** A service (by id) or project (by name) with logs kept by DevPort */
+export interface LogSource {
+  kind: 'project' | 'service';
+  name: string;
+}
+
+export interface LogSearchQuery {
+  levels?: LogLevel[];      // Empty = all levels
+  since?: string | null;    // RFC 3339
+  until?: string | null;
+  pattern?: string | null;  // Regex matched against the message
+  caseSensitive?: boolean;
+  sources?: LogSource[];    // Empty = every project and service
+  limit?: number | null;    // Default 500
+}
+
+export interface LogSearchHit extends LogEntry {
+  origin: LogSource;
+  logType: string;  // e.g. "stderr" or "stdout.1" for a rotated file
+  format: LogFormat;
+}
+
+export interface LogTailOptions {
+  sources?: LogSource[];    // Empty = every project and service
+  levels?: LogLevel[];      // Empty = all levels
+  pattern?: string | null;  // Regex matched against the message, e.g. a request id
+  caseSensitive?: boolean;
+  backlog?: number;         // Lines per file replayed first (default 50)
+  maxBatch?: number;        // Lines per event (default 200)
+}
+
+export interface LogTailBatch {
+  tailId: string;
+  lines: LogSearchHit[];  // Oldest first, tagged with their origin
+  pending: number;        // Lines already read and held for the next batches
+}
+
+export interface LogAlertRule {
+  id: string;                 // Empty = assigned on save
+  name: string;
+  source?: LogSource | null;  // null = every project and service
+  pattern?: string | null;    // Regex matched against the message
+  minLevel?: LogLevel | null; // Lowest level that fires
+  cooldownSecs: number;       // Quiet time per source after firing (default 300)
+  enabled: boolean;
+}
+
+export interface LogAlert {
+  ruleId: string;
+  ruleName: string;
+  origin: LogSource;
+  level: LogLevel;
+  message: string;
+  timestamp: string;
+  unread: number;  // Alerts raised since last cleared
+}
+
+export interface LogDiskUsage extends LogSource {
+  files: number;   // Rotated and compressed files included
+  bytes: number;
+}
+
 export interface LogReadResult {
   entries: LogEntry[];
   totalSize: number;
@@ -84,3 +214,21 @@ export const SUPPORTED_SERVICES: SupportedService[] = [
   { id: 'redis', name: 'Redis', serviceType: 'database', defaultPort: 6379, description: 'In-memory data store' },
   { id: 'mongodb', name: 'MongoDB', serviceType: 'database', defaultPort: 27017, description: 'NoSQL document database' },
 ];
+
+/** Settings DevPort manages in redis.conf; applied on the next start */
+export interface RedisConfig {
+  port: number;
+  maxmemory: string;        // e.g. "256mb", "0" for no limit
+  maxmemoryPolicy: string;  // e.g. "allkeys-lru"
+  dataDir: string;
+  databases: number;
+  appendonly: boolean;
+}
+
+/** Logical database index reserved for one project */
+export interface RedisAllocation {
+  projectId: string;
+  dbIndex: number;
+  allocatedAt: string;
+  url?: string;  // redis://127.0.0.1:<port>/<dbIndex>
+}
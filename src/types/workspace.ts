@@ -0,0 +1,24 @@
+export interface Workspace {
+  id: string;
+  name: string;
+  projectIds: string[];
+  serviceIds: string[];
+  startOrder: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CreateWorkspaceInput {
+  name: string;
+  projectIds?: string[];
+  serviceIds?: string[];
+  startOrder?: string[];
+}
+
+export interface UpdateWorkspaceInput {
+  id: string;
+  name?: string;
+  projectIds?: string[];
+  serviceIds?: string[];
+  startOrder?: string[];
+}